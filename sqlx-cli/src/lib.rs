@@ -1,6 +1,7 @@
 use crate::opt::{Command, DatabaseCommand, MigrateCommand};
 use anyhow::{anyhow, bail};
 use dotenv::dotenv;
+use sqlx::query_data;
 use std::env;
 use std::path::Path;
 
@@ -23,10 +24,16 @@ hint: This command only works in the manifest directory of a Cargo package."#
 
     dotenv().ok();
 
+    // `prepare --db <name>` reads the URL of the named database instead
+    let url_var = match &opt.command {
+        Command::Prepare { db, .. } => query_data::database_url_var(db.as_deref()),
+        _ => query_data::database_url_var(None),
+    };
+
     let database_url = match opt.database_url {
         Some(db_url) => db_url,
-        None => env::var("DATABASE_URL")
-            .map_err(|_| anyhow!("The DATABASE_URL environment variable must be set"))?,
+        None => env::var(&url_var)
+            .map_err(|_| anyhow!("The {} environment variable must be set", url_var))?,
     };
 
     match opt.command {
@@ -56,14 +63,16 @@ hint: This command only works in the manifest directory of a Cargo package."#
         Command::Prepare {
             check: false,
             merged,
+            db,
             args,
-        } => prepare::run(&database_url, merged, args)?,
+        } => prepare::run(&database_url, merged, db.as_deref(), args)?,
 
         Command::Prepare {
             check: true,
             merged,
+            db,
             args,
-        } => prepare::check(&database_url, merged, args)?,
+        } => prepare::check(&database_url, merged, db.as_deref(), args)?,
     };
 
     Ok(())
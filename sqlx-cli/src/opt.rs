@@ -33,6 +33,11 @@ pub enum Command {
         #[clap(long)]
         merged: bool,
 
+        /// Prepare the queries checked against the database named `db` with `query!(db = "...")`
+        /// instead, reading its URL from `DATABASE_URL_<DB>` and saving to `sqlx-data-<db>.json`.
+        #[clap(long)]
+        db: Option<String>,
+
         /// Arguments to be passed to `cargo rustc ...`.
         #[clap(last = true)]
         args: Vec<String>,
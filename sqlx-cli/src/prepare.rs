@@ -3,6 +3,7 @@ use cargo_metadata::MetadataCommand;
 use console::style;
 use remove_dir_all::remove_dir_all;
 use sqlx::any::{AnyConnectOptions, AnyKind};
+use sqlx::query_data;
 use std::collections::BTreeMap;
 use std::fs::File;
 use std::process::Command;
@@ -13,7 +14,12 @@ use std::{env, fs};
 type QueryData = BTreeMap<String, serde_json::Value>;
 type JsonObject = serde_json::Map<String, serde_json::Value>;
 
-pub fn run(url: &str, merge: bool, cargo_args: Vec<String>) -> anyhow::Result<()> {
+pub fn run(
+    url: &str,
+    merge: bool,
+    db: Option<&str>,
+    cargo_args: Vec<String>,
+) -> anyhow::Result<()> {
     #[derive(serde::Serialize)]
     struct DataFile {
         db: &'static str,
@@ -22,7 +28,8 @@ pub fn run(url: &str, merge: bool, cargo_args: Vec<String>) -> anyhow::Result<()
     }
 
     let db_kind = get_db_kind(url)?;
-    let data = run_prepare_step(merge, cargo_args)?;
+    let data = run_prepare_step(merge, db, cargo_args)?;
+    let data_file = query_data::data_file_name(db);
 
     if data.is_empty() {
         println!(
@@ -32,26 +39,37 @@ pub fn run(url: &str, merge: bool, cargo_args: Vec<String>) -> anyhow::Result<()
     }
 
     serde_json::to_writer_pretty(
-        File::create("sqlx-data.json").context("failed to create/open `sqlx-data.json`")?,
+        File::create(&data_file)
+            .with_context(|| format!("failed to create/open `{}`", data_file))?,
         &DataFile { db: db_kind, data },
     )
-    .context("failed to write to `sqlx-data.json`")?;
+    .with_context(|| format!("failed to write to `{}`", data_file))?;
 
     println!(
-        "query data written to `sqlx-data.json` in the current directory; \
-         please check this into version control"
+        "query data written to `{}` in the current directory; \
+         please check this into version control",
+        data_file
     );
 
     Ok(())
 }
 
-pub fn check(url: &str, merge: bool, cargo_args: Vec<String>) -> anyhow::Result<()> {
+pub fn check(
+    url: &str,
+    merge: bool,
+    db: Option<&str>,
+    cargo_args: Vec<String>,
+) -> anyhow::Result<()> {
     let db_kind = get_db_kind(url)?;
-    let data = run_prepare_step(merge, cargo_args)?;
+    let data = run_prepare_step(merge, db, cargo_args)?;
+    let data_file_name = query_data::data_file_name(db);
 
-    let data_file = fs::read("sqlx-data.json").context(
-        "failed to open `sqlx-data.json`; you may need to run `cargo sqlx prepare` first",
-    )?;
+    let data_file = fs::read(&data_file_name).with_context(|| {
+        format!(
+            "failed to open `{}`; you may need to run `cargo sqlx prepare` first",
+            data_file_name
+        )
+    })?;
 
     let mut saved_data: QueryData = serde_json::from_slice(&data_file)?;
 
@@ -65,9 +83,10 @@ pub fn check(url: &str, merge: bool, cargo_args: Vec<String>) -> anyhow::Result<
 
     if db_kind != expected_db {
         bail!(
-            "saved prepare data is for {}, not {} (inferred from `DATABASE_URL`)",
+            "saved prepare data is for {}, not {} (inferred from `{}`)",
             expected_db,
-            db_kind
+            db_kind,
+            query_data::database_url_var(db)
         )
     }
 
@@ -78,7 +97,11 @@ pub fn check(url: &str, merge: bool, cargo_args: Vec<String>) -> anyhow::Result<
     Ok(())
 }
 
-fn run_prepare_step(merge: bool, cargo_args: Vec<String>) -> anyhow::Result<QueryData> {
+fn run_prepare_step(
+    merge: bool,
+    db: Option<&str>,
+    cargo_args: Vec<String>,
+) -> anyhow::Result<QueryData> {
     // path to the Cargo executable
     let cargo = env::var("CARGO")
         .context("`prepare` subcommand may only be invoked as `cargo sqlx prepare`")?;
@@ -90,7 +113,7 @@ fn run_prepare_step(merge: bool, cargo_args: Vec<String>) -> anyhow::Result<Quer
 
     // try removing the target/sqlx directory before running, as stale files
     // have repeatedly caused issues in the past.
-    let _ = remove_dir_all(metadata.target_directory.join("sqlx"));
+    let _ = remove_dir_all(query_data::query_data_dir(&metadata.target_directory, None));
 
     let check_status = if merge {
         let check_status = Command::new(&cargo).arg("clean").status()?;
@@ -132,7 +155,7 @@ fn run_prepare_step(merge: bool, cargo_args: Vec<String>) -> anyhow::Result<Quer
         bail!("`cargo check` failed with status: {}", check_status);
     }
 
-    let pattern = metadata.target_directory.join("sqlx/query-*.json");
+    let pattern = query_data::query_data_dir(&metadata.target_directory, db).join("query-*.json");
 
     let mut data = BTreeMap::new();
 
@@ -1,11 +1,31 @@
-use crate::any::Any;
+use std::fmt::{self, Write};
+
+use crate::any::{Any, AnyKind};
 use crate::arguments::Arguments;
 use crate::encode::Encode;
 use crate::types::Type;
 
+/// Arguments for the `Any` driver.
+///
+/// The placeholders written by [`QueryBuilder`](crate::query_builder::QueryBuilder) depend on
+/// the database, which is only known once connected. Arguments created with
+/// [`for_kind`](Self::for_kind) write the placeholders of that database (`$N` for PostgreSQL,
+/// `@pN` for MSSQL); the default arguments write `?`.
 #[derive(Default)]
 pub struct AnyArguments<'q> {
     values: Vec<Box<dyn Encode<'q, Any> + Send + 'q>>,
+    kind: Option<AnyKind>,
+}
+
+impl AnyArguments<'_> {
+    /// Create empty arguments for the database `kind`, such as the one returned by
+    /// [`AnyConnection::kind`](crate::any::AnyConnection::kind).
+    pub fn for_kind(kind: AnyKind) -> Self {
+        Self {
+            values: Vec::new(),
+            kind: Some(kind),
+        }
+    }
 }
 
 impl<'q> Arguments<'q> for AnyArguments<'q> {
@@ -21,6 +41,25 @@ impl<'q> Arguments<'q> for AnyArguments<'q> {
     {
         self.values.push(Box::new(value));
     }
+
+    fn format_placeholder<W: Write>(&self, writer: &mut W) -> fmt::Result {
+        match self.kind {
+            #[cfg(feature = "postgres")]
+            Some(AnyKind::Postgres) => write!(writer, "${}", self.values.len()),
+
+            #[cfg(feature = "mssql")]
+            Some(AnyKind::Mssql) => write!(writer, "@p{}", self.values.len()),
+
+            _ => writer.write_str("?"),
+        }
+    }
+
+    fn empty_like(&self) -> Self {
+        Self {
+            values: Vec::new(),
+            kind: self.kind,
+        }
+    }
 }
 
 pub struct AnyArgumentBuffer<'q>(pub(crate) AnyArgumentBufferKind<'q>);
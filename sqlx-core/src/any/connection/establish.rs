@@ -35,6 +35,6 @@ impl AnyConnection {
                     .map(AnyConnectionKind::Mssql)
             }
         }
-        .map(AnyConnection)
+        .map(|kind| AnyConnection(kind, None))
     }
 }
@@ -4,10 +4,12 @@ use crate::database::Database;
 use crate::describe::Describe;
 use crate::error::Error;
 use crate::executor::{Execute, Executor};
+use crate::type_overrides::TypeOverrides;
 use either::Either;
 use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
 use futures_util::{StreamExt, TryStreamExt};
+use std::sync::Arc;
 
 impl<'c> Executor<'c> for &'c mut AnyConnection {
     type Database = Any;
@@ -22,8 +24,9 @@ impl<'c> Executor<'c> for &'c mut AnyConnection {
     {
         let arguments = query.take_arguments();
         let query = query.sql();
+        let type_overrides = self.1.clone();
 
-        match &mut self.0 {
+        let stream: BoxStream<'e, Result<Either<AnyDone, AnyRow>, Error>> = match &mut self.0 {
             #[cfg(feature = "postgres")]
             AnyConnectionKind::Postgres(conn) => conn
                 .fetch_many((query, arguments.map(Into::into)))
@@ -47,6 +50,14 @@ impl<'c> Executor<'c> for &'c mut AnyConnection {
                 .fetch_many((query, arguments.map(Into::into)))
                 .map_ok(|v| v.map_right(Into::into).map_left(Into::into))
                 .boxed(),
+        };
+
+        match type_overrides {
+            Some(type_overrides) => stream
+                .map_ok(move |v| v.map_right(|row| with_overrides(row, &type_overrides)))
+                .boxed(),
+
+            None => stream,
         }
     }
 
@@ -62,7 +73,7 @@ impl<'c> Executor<'c> for &'c mut AnyConnection {
         let query = query.sql();
 
         Box::pin(async move {
-            Ok(match &mut self.0 {
+            let row: Option<AnyRow> = match &mut self.0 {
                 #[cfg(feature = "postgres")]
                 AnyConnectionKind::Postgres(conn) => conn
                     .fetch_optional((query, arguments.map(Into::into)))
@@ -86,6 +97,11 @@ impl<'c> Executor<'c> for &'c mut AnyConnection {
                     .fetch_optional((query, arguments.map(Into::into)))
                     .await?
                     .map(Into::into),
+            };
+
+            Ok(match &self.1 {
+                Some(type_overrides) => row.map(|row| with_overrides(row, type_overrides)),
+                None => row,
             })
         })
     }
@@ -141,6 +157,11 @@ impl<'c> Executor<'c> for &'c mut AnyConnection {
     }
 }
 
+fn with_overrides(mut row: AnyRow, type_overrides: &Arc<TypeOverrides<Any>>) -> AnyRow {
+    row.type_overrides = Some(Arc::clone(type_overrides));
+    row
+}
+
 fn map_describe<DB: Database>(info: Describe<DB>) -> Describe<Any>
 where
     AnyTypeInfo: From<DB::TypeInfo>,
@@ -1,7 +1,8 @@
 use futures_core::future::BoxFuture;
 
-use crate::any::{Any, AnyConnectOptions};
-use crate::connection::Connection;
+use crate::any::type_info::AnyTypeInfoKind;
+use crate::any::{Any, AnyConnectOptions, AnyKind};
+use crate::connection::{Capability, Connection, ConnectionState};
 use crate::error::Error;
 
 #[cfg(feature = "postgres")]
@@ -16,6 +17,8 @@ use crate::mssql;
 #[cfg(feature = "mysql")]
 use crate::mysql;
 use crate::transaction::Transaction;
+use crate::type_overrides::TypeOverrides;
+use std::sync::Arc;
 
 mod establish;
 mod executor;
@@ -30,7 +33,11 @@ mod executor;
 /// sqlite://a.sqlite
 /// ```
 #[derive(Debug)]
-pub struct AnyConnection(pub(super) AnyConnectionKind);
+pub struct AnyConnection(
+    pub(super) AnyConnectionKind,
+    // shared with the rows returned by this connection
+    pub(super) Option<Arc<TypeOverrides<Any>>>,
+);
 
 #[derive(Debug)]
 pub(crate) enum AnyConnectionKind {
@@ -83,6 +90,25 @@ macro_rules! delegate_to_mut {
     };
 }
 
+impl AnyConnection {
+    /// The database this connection is connected to.
+    pub fn kind(&self) -> AnyKind {
+        match &self.0 {
+            #[cfg(feature = "postgres")]
+            AnyConnectionKind::Postgres(_) => AnyKind::Postgres,
+
+            #[cfg(feature = "mysql")]
+            AnyConnectionKind::MySql(_) => AnyKind::MySql,
+
+            #[cfg(feature = "sqlite")]
+            AnyConnectionKind::Sqlite(_) => AnyKind::Sqlite,
+
+            #[cfg(feature = "mssql")]
+            AnyConnectionKind::Mssql(_) => AnyKind::Mssql,
+        }
+    }
+}
+
 impl Connection for AnyConnection {
     type Database = Any;
 
@@ -108,6 +134,10 @@ impl Connection for AnyConnection {
         delegate_to_mut!(self.ping())
     }
 
+    fn reset(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        delegate_to_mut!(self.reset())
+    }
+
     fn begin(&mut self) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
     where
         Self: Sized,
@@ -132,6 +162,44 @@ impl Connection for AnyConnection {
         }
     }
 
+    fn set_type_overrides(&mut self, mut overrides: TypeOverrides<Any>) {
+        // keep the targets declared for the database of this connection
+        overrides.retain_targets(|target| match (&self.0, &target.0) {
+            #[cfg(feature = "postgres")]
+            (AnyConnectionKind::Postgres(_), AnyTypeInfoKind::Postgres(_)) => true,
+
+            #[cfg(feature = "mysql")]
+            (AnyConnectionKind::MySql(_), AnyTypeInfoKind::MySql(_)) => true,
+
+            #[cfg(feature = "sqlite")]
+            (AnyConnectionKind::Sqlite(_), AnyTypeInfoKind::Sqlite(_)) => true,
+
+            #[cfg(feature = "mssql")]
+            (AnyConnectionKind::Mssql(_), AnyTypeInfoKind::Mssql(_)) => true,
+
+            #[allow(unreachable_patterns)]
+            _ => false,
+        });
+
+        self.1 = overrides.into_shared();
+    }
+
+    fn dump_state(&self) -> ConnectionState {
+        delegate_to!(self.dump_state())
+    }
+
+    fn received_shutdown_notice(&self) -> bool {
+        delegate_to!(self.received_shutdown_notice())
+    }
+
+    fn is_broken(&self) -> bool {
+        delegate_to!(self.is_broken())
+    }
+
+    fn supports(&self, capability: Capability) -> bool {
+        delegate_to!(self.supports(capability))
+    }
+
     fn clear_cached_statements(&mut self) -> BoxFuture<'_, Result<(), Error>> {
         match &mut self.0 {
             #[cfg(feature = "postgres")]
@@ -149,6 +217,26 @@ impl Connection for AnyConnection {
         }
     }
 
+    fn close_cached_statement<'e>(
+        &'e mut self,
+        sql: &'e str,
+    ) -> BoxFuture<'e, Result<bool, Error>> {
+        match &mut self.0 {
+            #[cfg(feature = "postgres")]
+            AnyConnectionKind::Postgres(conn) => conn.close_cached_statement(sql),
+
+            #[cfg(feature = "mysql")]
+            AnyConnectionKind::MySql(conn) => conn.close_cached_statement(sql),
+
+            #[cfg(feature = "sqlite")]
+            AnyConnectionKind::Sqlite(conn) => conn.close_cached_statement(sql),
+
+            // no cache
+            #[cfg(feature = "mssql")]
+            AnyConnectionKind::Mssql(_) => Box::pin(futures_util::future::ok(false)),
+        }
+    }
+
     #[doc(hidden)]
     fn flush(&mut self) -> BoxFuture<'_, Result<(), Error>> {
         delegate_to_mut!(self.flush())
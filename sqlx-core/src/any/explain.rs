@@ -0,0 +1,33 @@
+use futures_core::future::BoxFuture;
+
+use crate::any::connection::AnyConnectionKind;
+use crate::any::{Any, AnyConnection};
+use crate::error::Error;
+use crate::executor::Execute;
+use crate::explain::{Explain, QueryPlan};
+
+impl Explain for AnyConnection {
+    fn explain<'e, 'q: 'e, E>(&'e mut self, mut query: E) -> BoxFuture<'e, Result<QueryPlan, Error>>
+    where
+        E: 'q + Execute<'q, Any>,
+    {
+        let arguments = query.take_arguments();
+        let query = query.sql();
+
+        match &mut self.0 {
+            #[cfg(feature = "postgres")]
+            AnyConnectionKind::Postgres(conn) => conn.explain((query, arguments.map(Into::into))),
+
+            #[cfg(feature = "mysql")]
+            AnyConnectionKind::MySql(conn) => conn.explain((query, arguments.map(Into::into))),
+
+            #[cfg(feature = "sqlite")]
+            AnyConnectionKind::Sqlite(conn) => conn.explain((query, arguments.map(Into::into))),
+
+            #[cfg(feature = "mssql")]
+            AnyConnectionKind::Mssql(_) => Box::pin(futures_util::future::err(
+                Error::Configuration("query plans are not supported for MSSQL".into()),
+            )),
+        }
+    }
+}
@@ -14,11 +14,13 @@ pub(crate) mod column;
 mod connection;
 mod database;
 mod done;
+mod explain;
 mod kind;
 mod options;
 pub(crate) mod row;
 mod statement;
 mod transaction;
+mod two_phase;
 pub(crate) mod type_info;
 pub mod types;
 pub(crate) mod value;
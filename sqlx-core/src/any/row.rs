@@ -1,8 +1,10 @@
-use crate::any::{Any, AnyColumn, AnyColumnIndex};
+use crate::any::{Any, AnyColumn, AnyColumnIndex, AnyValueRef};
 use crate::column::ColumnIndex;
 use crate::database::HasValueRef;
 use crate::error::Error;
 use crate::row::Row;
+use crate::type_overrides::TypeOverrides;
+use std::sync::Arc;
 
 #[cfg(feature = "postgres")]
 use crate::postgres::PgRow;
@@ -19,9 +21,26 @@ use crate::mssql::MssqlRow;
 pub struct AnyRow {
     pub(crate) kind: AnyRowKind,
     pub(crate) columns: Vec<AnyColumn>,
+    pub(crate) type_overrides: Option<Arc<TypeOverrides<Any>>>,
 }
 
-impl crate::row::private_row::Sealed for AnyRow {}
+impl crate::row::private_row::Sealed for AnyRow {
+    fn data_len(&self) -> usize {
+        match &self.kind {
+            #[cfg(feature = "postgres")]
+            AnyRowKind::Postgres(row) => row.data_len(),
+
+            #[cfg(feature = "mysql")]
+            AnyRowKind::MySql(row) => row.data_len(),
+
+            #[cfg(feature = "sqlite")]
+            AnyRowKind::Sqlite(row) => row.data_len(),
+
+            #[cfg(feature = "mssql")]
+            AnyRowKind::Mssql(row) => row.data_len(),
+        }
+    }
+}
 
 pub(crate) enum AnyRowKind {
     #[cfg(feature = "postgres")]
@@ -53,19 +72,27 @@ impl Row for AnyRow {
     {
         let index = index.index(self)?;
 
-        match &self.kind {
+        let mut value: AnyValueRef<'_> = match &self.kind {
             #[cfg(feature = "postgres")]
-            AnyRowKind::Postgres(row) => row.try_get_raw(index).map(Into::into),
+            AnyRowKind::Postgres(row) => row.try_get_raw(index)?.into(),
 
             #[cfg(feature = "mysql")]
-            AnyRowKind::MySql(row) => row.try_get_raw(index).map(Into::into),
+            AnyRowKind::MySql(row) => row.try_get_raw(index)?.into(),
 
             #[cfg(feature = "sqlite")]
-            AnyRowKind::Sqlite(row) => row.try_get_raw(index).map(Into::into),
+            AnyRowKind::Sqlite(row) => row.try_get_raw(index)?.into(),
 
             #[cfg(feature = "mssql")]
-            AnyRowKind::Mssql(row) => row.try_get_raw(index).map(Into::into),
-        }
+            AnyRowKind::Mssql(row) => row.try_get_raw(index)?.into(),
+        };
+
+        value.type_overrides = self.type_overrides.as_ref();
+
+        Ok(value)
+    }
+
+    fn type_overrides(&self) -> Option<&TypeOverrides<Any>> {
+        self.type_overrides.as_deref()
     }
 }
 
@@ -0,0 +1,58 @@
+use futures_core::future::BoxFuture;
+
+use crate::any::connection::AnyConnectionKind;
+use crate::any::AnyConnection;
+use crate::error::Error;
+use crate::two_phase::TwoPhase;
+
+macro_rules! delegate_two_phase {
+    ($self:ident.$method:ident($($arg:ident),*)) => {
+        match &mut $self.0 {
+            #[cfg(feature = "postgres")]
+            AnyConnectionKind::Postgres(conn) => conn.$method($($arg),*),
+
+            #[cfg(feature = "mysql")]
+            AnyConnectionKind::MySql(conn) => conn.$method($($arg),*),
+
+            #[cfg(feature = "sqlite")]
+            AnyConnectionKind::Sqlite(_) => unsupported("SQLite"),
+
+            #[cfg(feature = "mssql")]
+            AnyConnectionKind::Mssql(_) => unsupported("MSSQL"),
+        }
+    };
+}
+
+#[allow(dead_code)]
+fn unsupported<'e, T: 'e + Send>(database: &str) -> BoxFuture<'e, Result<T, Error>> {
+    let error =
+        Error::Configuration(format!("two-phase commit is not supported for {}", database).into());
+
+    Box::pin(futures_util::future::err(error))
+}
+
+impl TwoPhase for AnyConnection {
+    fn begin_branch<'e>(&'e mut self, xid: &'e str) -> BoxFuture<'e, Result<(), Error>> {
+        delegate_two_phase!(self.begin_branch(xid))
+    }
+
+    fn prepare_branch<'e>(&'e mut self, xid: &'e str) -> BoxFuture<'e, Result<(), Error>> {
+        delegate_two_phase!(self.prepare_branch(xid))
+    }
+
+    fn abort_branch<'e>(&'e mut self, xid: &'e str) -> BoxFuture<'e, Result<(), Error>> {
+        delegate_two_phase!(self.abort_branch(xid))
+    }
+
+    fn commit_prepared<'e>(&'e mut self, xid: &'e str) -> BoxFuture<'e, Result<(), Error>> {
+        delegate_two_phase!(self.commit_prepared(xid))
+    }
+
+    fn rollback_prepared<'e>(&'e mut self, xid: &'e str) -> BoxFuture<'e, Result<(), Error>> {
+        delegate_two_phase!(self.rollback_prepared(xid))
+    }
+
+    fn prepared_branches(&mut self) -> BoxFuture<'_, Result<Vec<String>, Error>> {
+        delegate_two_phase!(self.prepared_branches())
+    }
+}
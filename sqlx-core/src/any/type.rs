@@ -56,6 +56,23 @@ macro_rules! impl_any_type {
     };
 }
 
+// A type can be declared as the target of an override for every database compiled in; the
+// connection the overrides are set on keeps the one for its database.
+impl<T: ?Sized + crate::any::AnyType> crate::type_overrides::DecodeTarget<crate::any::Any> for T {
+    fn target_type_infos() -> Vec<crate::any::AnyTypeInfo> {
+        vec![
+            #[cfg(feature = "postgres")]
+            <T as Type<Postgres>>::type_info().into(),
+            #[cfg(feature = "mysql")]
+            <T as Type<MySql>>::type_info().into(),
+            #[cfg(feature = "sqlite")]
+            <T as Type<Sqlite>>::type_info().into(),
+            #[cfg(feature = "mssql")]
+            <T as Type<Mssql>>::type_info().into(),
+        ]
+    }
+}
+
 // FIXME: Find a nice way to auto-generate the below or petition Rust to add support for #[cfg]
 //        to trait bounds
 
@@ -1,7 +1,9 @@
 use crate::any::{Any, AnyTypeInfo};
 use crate::database::HasValueRef;
+use crate::type_overrides::TypeOverrides;
 use crate::value::{Value, ValueRef};
 use std::borrow::Cow;
+use std::sync::Arc;
 
 #[cfg(feature = "postgres")]
 use crate::postgres::{PgValue, PgValueRef};
@@ -18,6 +20,7 @@ use crate::mssql::{MssqlValue, MssqlValueRef};
 pub struct AnyValue {
     pub(crate) kind: AnyValueKind,
     pub(crate) type_info: AnyTypeInfo,
+    pub(crate) type_overrides: Option<Arc<TypeOverrides<Any>>>,
 }
 
 pub(crate) enum AnyValueKind {
@@ -37,6 +40,7 @@ pub(crate) enum AnyValueKind {
 pub struct AnyValueRef<'r> {
     pub(crate) kind: AnyValueRefKind<'r>,
     pub(crate) type_info: AnyTypeInfo,
+    pub(crate) type_overrides: Option<&'r Arc<TypeOverrides<Any>>>,
 }
 
 pub(crate) enum AnyValueRefKind<'r> {
@@ -57,7 +61,7 @@ impl Value for AnyValue {
     type Database = Any;
 
     fn as_ref(&self) -> <Self::Database as HasValueRef<'_>>::ValueRef {
-        match &self.kind {
+        let mut value: AnyValueRef<'_> = match &self.kind {
             #[cfg(feature = "postgres")]
             AnyValueKind::Postgres(value) => value.as_ref().into(),
 
@@ -69,7 +73,10 @@ impl Value for AnyValue {
 
             #[cfg(feature = "mssql")]
             AnyValueKind::Mssql(value) => value.as_ref().into(),
-        }
+        };
+
+        value.type_overrides = self.type_overrides.as_ref();
+        value
     }
 
     fn type_info(&self) -> Cow<'_, AnyTypeInfo> {
@@ -91,13 +98,17 @@ impl Value for AnyValue {
             AnyValueKind::Mssql(value) => value.is_null(),
         }
     }
+
+    fn type_overrides(&self) -> Option<&TypeOverrides<Any>> {
+        self.type_overrides.as_deref()
+    }
 }
 
 impl<'r> ValueRef<'r> for AnyValueRef<'r> {
     type Database = Any;
 
     fn to_owned(&self) -> AnyValue {
-        match &self.kind {
+        let mut value: AnyValue = match &self.kind {
             #[cfg(feature = "postgres")]
             AnyValueRefKind::Postgres(value) => ValueRef::to_owned(value).into(),
 
@@ -109,7 +120,10 @@ impl<'r> ValueRef<'r> for AnyValueRef<'r> {
 
             #[cfg(feature = "mssql")]
             AnyValueRefKind::Mssql(value) => ValueRef::to_owned(value).into(),
-        }
+        };
+
+        value.type_overrides = self.type_overrides.cloned();
+        value
     }
 
     fn type_info(&self) -> Cow<'_, AnyTypeInfo> {
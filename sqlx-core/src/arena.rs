@@ -0,0 +1,863 @@
+//! Compact storage of large result sets for batch post-processing.
+//!
+//! An [`Arena`] holds all the values of a result set in a few buffers shared by every row,
+//! instead of keeping a row, with its own allocations, for each of them. Values are stored in
+//! fixed-size slots, either column by column or row by row (see [`Layout`]), and are read back
+//! without copying:
+//!
+//! ```rust,ignore
+//! use sqlx::arena::{ArenaBuilder, Layout};
+//!
+//! let events = ArenaBuilder::<Postgres>::new()
+//!     .column::<i64>("user_id")
+//!     .column::<String>("kind")
+//!     .column::<Option<f64>>("score")
+//!     .layout(Layout::Columnar)
+//!     .fetch(&mut conn, "SELECT user_id, kind, score FROM events")
+//!     .await?;
+//!
+//! // the scores are stored next to each other
+//! let total: f64 = events.try_column::<Option<f64>, _>("score")?.flatten().sum();
+//!
+//! for event in events.rows() {
+//!     let kind: &str = event.try_get("kind")?;
+//!     // ...
+//! }
+//! ```
+//!
+//! The types of the columns are given up front, as the values are decoded as the rows arrive.
+//! Text and binary columns are declared as `String` and `Vec<u8>`, and read as `&str` and
+//! `&[u8]` borrowed from the arena.
+//!
+//! The rows are received from the database as with any other query, and each is dropped once
+//! its values are copied into the arena: this saves on the memory held by the result set, not
+//! on the allocations made while receiving it.
+
+use std::any::type_name;
+use std::convert::TryInto;
+use std::fmt::{self, Debug, Formatter};
+use std::marker::PhantomData;
+
+use futures_util::TryStreamExt;
+
+use crate::column::{Column, ColumnIndex};
+use crate::database::Database;
+use crate::decode::Decode;
+use crate::error::Error;
+use crate::executor::{Execute, Executor};
+use crate::row::Row;
+use crate::types::Type;
+use crate::value::ValueRef;
+
+/// How the values of an [`Arena`] are laid out in memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layout {
+    /// The values of each column are stored next to each other, which suits computations
+    /// over a few columns of many rows.
+    Columnar,
+
+    /// The values of each row are stored next to each other, which suits processing the
+    /// rows one after the other.
+    RowMajor,
+}
+
+// `#[default]` on enum variants needs a newer compiler than this crate supports
+#[allow(clippy::derivable_impls)]
+impl Default for Layout {
+    fn default() -> Self {
+        Layout::Columnar
+    }
+}
+
+/// How the values of a column are stored in an [`Arena`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArenaKind {
+    Bool,
+    I16,
+    I32,
+    I64,
+    F32,
+    F64,
+    Text,
+    Binary,
+}
+
+impl ArenaKind {
+    // the size of the slot of a value; text and binary values are stored elsewhere and their
+    // slot holds their 64-bit offset and 32-bit length
+    fn size(self) -> usize {
+        match self {
+            ArenaKind::Bool => 1,
+            ArenaKind::I16 => 2,
+            ArenaKind::I32 | ArenaKind::F32 => 4,
+            ArenaKind::I64 | ArenaKind::F64 => 8,
+            ArenaKind::Text | ArenaKind::Binary => 12,
+        }
+    }
+}
+
+/// A value of an [`Arena`], borrowed from it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArenaValue<'a> {
+    Null,
+    Bool(bool),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    F32(f32),
+    F64(f64),
+    Text(&'a str),
+    Binary(&'a [u8]),
+}
+
+impl ArenaValue<'_> {
+    fn kind(&self) -> Option<ArenaKind> {
+        Some(match self {
+            ArenaValue::Null => return None,
+            ArenaValue::Bool(_) => ArenaKind::Bool,
+            ArenaValue::I16(_) => ArenaKind::I16,
+            ArenaValue::I32(_) => ArenaKind::I32,
+            ArenaValue::I64(_) => ArenaKind::I64,
+            ArenaValue::F32(_) => ArenaKind::F32,
+            ArenaValue::F64(_) => ArenaKind::F64,
+            ArenaValue::Text(_) => ArenaKind::Text,
+            ArenaValue::Binary(_) => ArenaKind::Binary,
+        })
+    }
+}
+
+/// A column of an [`Arena`].
+#[derive(Debug, Clone)]
+pub struct ArenaColumn {
+    name: String,
+    kind: ArenaKind,
+    nullable: bool,
+}
+
+impl ArenaColumn {
+    /// The name of the column in the result set.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// How the values of the column are stored.
+    pub fn kind(&self) -> ArenaKind {
+        self.kind
+    }
+
+    /// Returns `true` if the column was declared as an `Option` and may hold `NULL`.
+    pub fn is_nullable(&self) -> bool {
+        self.nullable
+    }
+}
+
+/// A type that the values of a column can be stored as in an [`Arena`].
+///
+/// Implemented for `bool`, `i16`, `i32`, `i64`, `f32` and `f64`, for `String` and `Vec<u8>`,
+/// which are read back as `&str` and `&[u8]`, and for `Option` of any of these for columns
+/// that may hold `NULL`.
+pub trait ArenaType<DB: Database> {
+    #[doc(hidden)]
+    fn kind() -> ArenaKind;
+
+    #[doc(hidden)]
+    fn nullable() -> bool {
+        false
+    }
+
+    #[doc(hidden)]
+    fn decode(row: &DB::Row, index: usize) -> Result<ArenaValue<'_>, Error>;
+}
+
+macro_rules! impl_arena_type {
+    ($ty:ty, $decoded:ty, $type_of:ty, $kind:ident) => {
+        impl<DB: Database> ArenaType<DB> for $ty
+        where
+            $type_of: Type<DB>,
+            for<'r> $decoded: Decode<'r, DB>,
+            usize: ColumnIndex<DB::Row>,
+        {
+            fn kind() -> ArenaKind {
+                ArenaKind::$kind
+            }
+
+            fn decode(row: &DB::Row, index: usize) -> Result<ArenaValue<'_>, Error> {
+                row.try_get(index).map(ArenaValue::$kind)
+            }
+        }
+    };
+
+    ($ty:ident, $kind:ident) => {
+        impl_arena_type!($ty, $ty, $ty, $kind);
+    };
+}
+
+impl_arena_type!(bool, Bool);
+impl_arena_type!(i16, I16);
+impl_arena_type!(i32, I32);
+impl_arena_type!(i64, I64);
+impl_arena_type!(f32, F32);
+impl_arena_type!(f64, F64);
+impl_arena_type!(String, &'r str, str, Text);
+impl_arena_type!(Vec<u8>, &'r [u8], [u8], Binary);
+
+impl<DB: Database, T: ArenaType<DB>> ArenaType<DB> for Option<T>
+where
+    usize: ColumnIndex<DB::Row>,
+{
+    fn kind() -> ArenaKind {
+        T::kind()
+    }
+
+    fn nullable() -> bool {
+        true
+    }
+
+    fn decode(row: &DB::Row, index: usize) -> Result<ArenaValue<'_>, Error> {
+        if row.try_get_raw(index)?.is_null() {
+            Ok(ArenaValue::Null)
+        } else {
+            T::decode(row, index)
+        }
+    }
+}
+
+/// A type that can be read from the values of an [`Arena`].
+///
+/// Implemented for `bool`, `i16`, `i32`, `i64`, `f32`, `f64`, `&str` and `&[u8]`, which must
+/// match how the column is stored, for `Option` of any of these, which must be used for columns
+/// that may hold `NULL`, and for [`ArenaValue`], which reads any column.
+pub trait FromArena<'a>: Sized {
+    #[doc(hidden)]
+    fn accepts(kind: ArenaKind, nullable: bool) -> bool;
+
+    // only called with the values of the columns it accepts
+    #[doc(hidden)]
+    fn from_arena(value: ArenaValue<'a>) -> Self;
+}
+
+macro_rules! impl_from_arena {
+    ($ty:ty, $kind:ident) => {
+        impl<'a> FromArena<'a> for $ty {
+            fn accepts(kind: ArenaKind, nullable: bool) -> bool {
+                kind == ArenaKind::$kind && !nullable
+            }
+
+            fn from_arena(value: ArenaValue<'a>) -> Self {
+                match value {
+                    ArenaValue::$kind(value) => value,
+                    value => panic!(
+                        "unexpected {:?} in a column read as `{}`",
+                        value,
+                        type_name::<Self>()
+                    ),
+                }
+            }
+        }
+    };
+}
+
+impl_from_arena!(bool, Bool);
+impl_from_arena!(i16, I16);
+impl_from_arena!(i32, I32);
+impl_from_arena!(i64, I64);
+impl_from_arena!(f32, F32);
+impl_from_arena!(f64, F64);
+impl_from_arena!(&'a str, Text);
+impl_from_arena!(&'a [u8], Binary);
+
+impl<'a, T: FromArena<'a>> FromArena<'a> for Option<T> {
+    fn accepts(kind: ArenaKind, _nullable: bool) -> bool {
+        T::accepts(kind, false)
+    }
+
+    fn from_arena(value: ArenaValue<'a>) -> Self {
+        match value {
+            ArenaValue::Null => None,
+            value => Some(T::from_arena(value)),
+        }
+    }
+}
+
+impl<'a> FromArena<'a> for ArenaValue<'a> {
+    fn accepts(_kind: ArenaKind, _nullable: bool) -> bool {
+        true
+    }
+
+    fn from_arena(value: ArenaValue<'a>) -> Self {
+        value
+    }
+}
+
+/// A type that can index the columns of an [`Arena`]: the position of the column, in the order
+/// the columns were added to the [`ArenaBuilder`], or its name.
+pub trait ArenaIndex: Debug {
+    #[doc(hidden)]
+    fn index(&self, arena: &Arena) -> Result<usize, Error>;
+}
+
+impl ArenaIndex for usize {
+    fn index(&self, arena: &Arena) -> Result<usize, Error> {
+        if *self >= arena.columns.len() {
+            return Err(Error::ColumnIndexOutOfBounds {
+                len: arena.columns.len(),
+                index: *self,
+            });
+        }
+
+        Ok(*self)
+    }
+}
+
+impl ArenaIndex for &'_ str {
+    fn index(&self, arena: &Arena) -> Result<usize, Error> {
+        arena
+            .columns
+            .iter()
+            .position(|column| column.name == *self)
+            .ok_or_else(|| Error::ColumnNotFound((*self).into()))
+    }
+}
+
+type DecodeFn<DB> = for<'r> fn(&'r <DB as Database>::Row, usize) -> Result<ArenaValue<'r>, Error>;
+
+/// Declares the columns of an [`Arena`] and fills it with the rows of a query.
+pub struct ArenaBuilder<DB: Database> {
+    layout: Layout,
+    columns: Vec<ArenaColumn>,
+    decoders: Vec<DecodeFn<DB>>,
+}
+
+impl<DB: Database> Default for ArenaBuilder<DB> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<DB: Database> ArenaBuilder<DB> {
+    pub fn new() -> Self {
+        Self {
+            layout: Layout::default(),
+            columns: Vec::new(),
+            decoders: Vec::new(),
+        }
+    }
+
+    /// Store the column `name` of the result set as `T`.
+    ///
+    /// Only the columns that are added are stored; the others are skipped.
+    pub fn column<T>(mut self, name: impl Into<String>) -> Self
+    where
+        T: ArenaType<DB>,
+    {
+        self.columns.push(ArenaColumn {
+            name: name.into(),
+            kind: T::kind(),
+            nullable: T::nullable(),
+        });
+
+        self.decoders.push(T::decode);
+
+        self
+    }
+
+    /// Set how the values are laid out in memory. Defaults to [`Layout::Columnar`].
+    pub fn layout(mut self, layout: Layout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Execute the query and store all the rows it returns.
+    pub async fn fetch<'c, 'q, E, Q>(&self, executor: E, query: Q) -> Result<Arena, Error>
+    where
+        E: Executor<'c, Database = DB>,
+        Q: 'q + Execute<'q, DB>,
+        for<'a> &'a str: ColumnIndex<DB::Row>,
+    {
+        let mut writer = ArenaWriter::new(self.layout, self.columns.clone());
+        let mut indexes: Option<Vec<usize>> = None;
+
+        let mut rows = executor.fetch(query);
+
+        while let Some(row) = rows.try_next().await? {
+            // the columns are looked up by name once, on the first row
+            let indexes = match &mut indexes {
+                Some(indexes) => indexes,
+
+                None => indexes.get_or_insert(
+                    self.columns
+                        .iter()
+                        .map(|column| row.try_column(&*column.name).map(Column::ordinal))
+                        .collect::<Result<_, _>>()?,
+                ),
+            };
+
+            for (column, (decode, &index)) in self.decoders.iter().zip(&*indexes).enumerate() {
+                writer.push(column, decode(&row, index)?);
+            }
+
+            writer.finish_row();
+        }
+
+        Ok(writer.finish())
+    }
+}
+
+impl<DB: Database> Debug for ArenaBuilder<DB> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ArenaBuilder")
+            .field("layout", &self.layout)
+            .field("columns", &self.columns)
+            .finish()
+    }
+}
+
+/// The rows of a result set, stored compactly.
+///
+/// Returned by [`ArenaBuilder::fetch`].
+pub struct Arena {
+    layout: Layout,
+    columns: Vec<ArenaColumn>,
+
+    // where the values of each column start: in `data` for the columnar layout, and within
+    // each row for the row-major layout
+    offsets: Vec<usize>,
+
+    // the size of the slots of a row
+    row_size: usize,
+
+    len: usize,
+
+    // the slots of every value
+    data: Vec<u8>,
+
+    // the contents of the text and binary values
+    text: String,
+    binary: Vec<u8>,
+
+    // one bit per value, set if the value is NULL; empty if no column is nullable
+    nulls: Vec<u8>,
+}
+
+impl Arena {
+    /// Returns the number of rows.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the query returned no rows.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    /// The columns, in the order they were added to the [`ArenaBuilder`].
+    pub fn columns(&self) -> &[ArenaColumn] {
+        &self.columns
+    }
+
+    /// The row at `index`, or `None` if out of bounds.
+    pub fn row(&self, index: usize) -> Option<ArenaRow<'_>> {
+        if index < self.len {
+            Some(ArenaRow { arena: self, index })
+        } else {
+            None
+        }
+    }
+
+    pub fn rows(&self) -> impl ExactSizeIterator<Item = ArenaRow<'_>> {
+        (0..self.len).map(move |index| ArenaRow { arena: self, index })
+    }
+
+    /// Returns the values of a column as `T`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the column does not exist or is not stored as `T`.
+    /// See [`try_column`](Self::try_column) for a non-panicking version.
+    pub fn column<'a, T, I>(&'a self, column: I) -> ColumnValues<'a, T>
+    where
+        T: FromArena<'a>,
+        I: ArenaIndex,
+    {
+        self.try_column(column).unwrap()
+    }
+
+    /// Returns the values of a column as `T`, or an error if the column does not exist or is
+    /// not stored as `T`.
+    pub fn try_column<'a, T, I>(&'a self, column: I) -> Result<ColumnValues<'a, T>, Error>
+    where
+        T: FromArena<'a>,
+        I: ArenaIndex,
+    {
+        Ok(ColumnValues {
+            arena: self,
+            column: self.check::<T, I>(&column)?,
+            rows: 0..self.len,
+            ty: PhantomData,
+        })
+    }
+
+    /// Returns the number of bytes allocated to hold the values.
+    pub fn allocated_size(&self) -> usize {
+        self.data.capacity() + self.text.capacity() + self.binary.capacity() + self.nulls.capacity()
+    }
+
+    // resolves the index of the column, which must be readable as `T`
+    fn check<'a, T, I>(&self, index: &I) -> Result<usize, Error>
+    where
+        T: FromArena<'a>,
+        I: ArenaIndex,
+    {
+        let column = index.index(self)?;
+        let ArenaColumn { kind, nullable, .. } = self.columns[column];
+
+        if !T::accepts(kind, nullable) {
+            return Err(Error::ColumnDecode {
+                index: format!("{:?}", index),
+                source: format!(
+                    "column stored as {:?}{} cannot be read as `{}`",
+                    kind,
+                    if nullable { " with NULL values" } else { "" },
+                    type_name::<T>()
+                )
+                .into(),
+            });
+        }
+
+        Ok(column)
+    }
+
+    fn value(&self, row: usize, column: usize) -> ArenaValue<'_> {
+        let cell = row * self.columns.len() + column;
+
+        if !self.nulls.is_empty() && self.nulls[cell / 8] & (1 << (cell % 8)) != 0 {
+            return ArenaValue::Null;
+        }
+
+        let kind = self.columns[column].kind;
+
+        let start = match self.layout {
+            Layout::Columnar => self.offsets[column] + row * kind.size(),
+            Layout::RowMajor => row * self.row_size + self.offsets[column],
+        };
+
+        let slot = &self.data[start..start + kind.size()];
+
+        match kind {
+            ArenaKind::Bool => ArenaValue::Bool(slot[0] != 0),
+            ArenaKind::I16 => ArenaValue::I16(i16::from_le_bytes(slot.try_into().unwrap())),
+            ArenaKind::I32 => ArenaValue::I32(i32::from_le_bytes(slot.try_into().unwrap())),
+            ArenaKind::I64 => ArenaValue::I64(i64::from_le_bytes(slot.try_into().unwrap())),
+            ArenaKind::F32 => ArenaValue::F32(f32::from_le_bytes(slot.try_into().unwrap())),
+            ArenaKind::F64 => ArenaValue::F64(f64::from_le_bytes(slot.try_into().unwrap())),
+
+            ArenaKind::Text => {
+                let (start, end) = read_range(slot);
+                ArenaValue::Text(&self.text[start..end])
+            }
+
+            ArenaKind::Binary => {
+                let (start, end) = read_range(slot);
+                ArenaValue::Binary(&self.binary[start..end])
+            }
+        }
+    }
+}
+
+impl Debug for Arena {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Arena")
+            .field("layout", &self.layout)
+            .field("columns", &self.columns)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+/// A row of an [`Arena`].
+#[derive(Debug, Clone, Copy)]
+pub struct ArenaRow<'a> {
+    arena: &'a Arena,
+    index: usize,
+}
+
+impl<'a> ArenaRow<'a> {
+    /// The position of the row in the arena.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Read the value of a column as `T`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the column does not exist or is not stored as `T`.
+    /// See [`try_get`](Self::try_get) for a non-panicking version.
+    pub fn get<T, I>(&self, column: I) -> T
+    where
+        T: FromArena<'a>,
+        I: ArenaIndex,
+    {
+        self.try_get(column).unwrap()
+    }
+
+    /// Read the value of a column as `T`, or return an error if the column does not exist or
+    /// is not stored as `T`.
+    pub fn try_get<T, I>(&self, column: I) -> Result<T, Error>
+    where
+        T: FromArena<'a>,
+        I: ArenaIndex,
+    {
+        let column = self.arena.check::<T, I>(&column)?;
+
+        Ok(T::from_arena(self.arena.value(self.index, column)))
+    }
+}
+
+/// The values of a column of an [`Arena`], returned by [`Arena::column`].
+pub struct ColumnValues<'a, T> {
+    arena: &'a Arena,
+    column: usize,
+    rows: std::ops::Range<usize>,
+    ty: PhantomData<fn() -> T>,
+}
+
+impl<'a, T: FromArena<'a>> Iterator for ColumnValues<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let row = self.rows.next()?;
+
+        Some(T::from_arena(self.arena.value(row, self.column)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.rows.size_hint()
+    }
+}
+
+impl<'a, T: FromArena<'a>> ExactSizeIterator for ColumnValues<'a, T> {}
+
+impl<T> Debug for ColumnValues<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ColumnValues")
+            .field("column", &self.arena.columns[self.column].name)
+            .field("remaining", &self.rows.len())
+            .finish()
+    }
+}
+
+// fills an arena one row, and one value, at a time
+struct ArenaWriter {
+    arena: Arena,
+
+    // the slots of each column, joined once every row is written for the columnar layout
+    columns: Vec<Vec<u8>>,
+}
+
+impl ArenaWriter {
+    fn new(layout: Layout, columns: Vec<ArenaColumn>) -> Self {
+        let mut offsets = Vec::with_capacity(columns.len());
+        let mut row_size = 0;
+
+        for column in &columns {
+            offsets.push(row_size);
+            row_size += column.kind.size();
+        }
+
+        let slots = match layout {
+            Layout::Columnar => columns.iter().map(|_| Vec::new()).collect(),
+            Layout::RowMajor => Vec::new(),
+        };
+
+        Self {
+            arena: Arena {
+                layout,
+                columns,
+                offsets,
+                row_size,
+                len: 0,
+                data: Vec::new(),
+                text: String::new(),
+                binary: Vec::new(),
+                nulls: Vec::new(),
+            },
+            columns: slots,
+        }
+    }
+
+    // values are pushed in the order of the columns
+    fn push(&mut self, column: usize, value: ArenaValue<'_>) {
+        let arena = &mut self.arena;
+        let kind = arena.columns[column].kind;
+
+        debug_assert!(value == ArenaValue::Null || value.kind() == Some(kind));
+
+        let data = match arena.layout {
+            Layout::Columnar => &mut self.columns[column],
+            Layout::RowMajor => &mut arena.data,
+        };
+
+        match value {
+            ArenaValue::Null => {
+                let cell = arena.len * arena.columns.len() + column;
+
+                if arena.nulls.len() <= cell / 8 {
+                    arena.nulls.resize(cell / 8 + 1, 0);
+                }
+
+                arena.nulls[cell / 8] |= 1 << (cell % 8);
+
+                data.resize(data.len() + kind.size(), 0);
+            }
+
+            ArenaValue::Bool(value) => data.push(value as u8),
+            ArenaValue::I16(value) => data.extend_from_slice(&value.to_le_bytes()),
+            ArenaValue::I32(value) => data.extend_from_slice(&value.to_le_bytes()),
+            ArenaValue::I64(value) => data.extend_from_slice(&value.to_le_bytes()),
+            ArenaValue::F32(value) => data.extend_from_slice(&value.to_le_bytes()),
+            ArenaValue::F64(value) => data.extend_from_slice(&value.to_le_bytes()),
+
+            ArenaValue::Text(value) => {
+                write_range(data, arena.text.len(), value.len());
+                arena.text.push_str(value);
+            }
+
+            ArenaValue::Binary(value) => {
+                write_range(data, arena.binary.len(), value.len());
+                arena.binary.extend_from_slice(value);
+            }
+        }
+    }
+
+    fn finish_row(&mut self) {
+        self.arena.len += 1;
+    }
+
+    fn finish(mut self) -> Arena {
+        let arena = &mut self.arena;
+
+        if arena.layout == Layout::Columnar {
+            arena.data.reserve_exact(arena.row_size * arena.len);
+
+            for (column, slots) in self.columns.iter().enumerate() {
+                arena.offsets[column] = arena.data.len();
+                arena.data.extend_from_slice(slots);
+            }
+        }
+
+        // one bit for each value, rounded up; `usize::div_ceil` needs a newer compiler
+        #[allow(clippy::manual_div_ceil)]
+        if arena.columns.iter().any(|column| column.nullable) {
+            arena
+                .nulls
+                .resize((arena.len * arena.columns.len() + 7) / 8, 0);
+        }
+
+        self.arena
+    }
+}
+
+fn write_range(data: &mut Vec<u8>, start: usize, len: usize) {
+    data.extend_from_slice(&(start as u64).to_le_bytes());
+    data.extend_from_slice(&(len as u32).to_le_bytes());
+}
+
+fn read_range(slot: &[u8]) -> (usize, usize) {
+    let start = u64::from_le_bytes(slot[..8].try_into().unwrap()) as usize;
+    let len = u32::from_le_bytes(slot[8..].try_into().unwrap()) as usize;
+
+    (start, start + len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Arena, ArenaColumn, ArenaKind, ArenaValue, ArenaWriter, Layout};
+
+    fn column(name: &str, kind: ArenaKind, nullable: bool) -> ArenaColumn {
+        ArenaColumn {
+            name: name.to_owned(),
+            kind,
+            nullable,
+        }
+    }
+
+    fn arena(layout: Layout) -> Arena {
+        let mut writer = ArenaWriter::new(
+            layout,
+            vec![
+                column("id", ArenaKind::I64, false),
+                column("name", ArenaKind::Text, false),
+                column("score", ArenaKind::F64, true),
+                column("avatar", ArenaKind::Binary, true),
+            ],
+        );
+
+        let rows = vec![
+            (1, "ann", Some(1.5), Some(&b"\x89PNG"[..])),
+            (2, "bob", None, None),
+            (3, "", Some(-2.0), Some(&b""[..])),
+        ];
+
+        for (id, name, score, avatar) in rows {
+            writer.push(0, ArenaValue::I64(id));
+            writer.push(1, ArenaValue::Text(name));
+            writer.push(2, score.map_or(ArenaValue::Null, ArenaValue::F64));
+            writer.push(3, avatar.map_or(ArenaValue::Null, ArenaValue::Binary));
+            writer.finish_row();
+        }
+
+        writer.finish()
+    }
+
+    #[test]
+    fn it_reads_rows_and_columns() {
+        for &layout in &[Layout::Columnar, Layout::RowMajor] {
+            let arena = arena(layout);
+
+            assert_eq!(arena.len(), 3);
+
+            let bob = arena.row(1).unwrap();
+
+            assert_eq!(bob.get::<i64, _>("id"), 2);
+            assert_eq!(bob.get::<&str, _>(1), "bob");
+            assert_eq!(bob.get::<Option<f64>, _>("score"), None);
+            assert_eq!(bob.get::<Option<&[u8]>, _>("avatar"), None);
+            assert_eq!(bob.get::<ArenaValue<'_>, _>("score"), ArenaValue::Null);
+
+            let ids: Vec<i64> = arena.column("id").collect();
+            assert_eq!(ids, [1, 2, 3]);
+
+            let names: Vec<&str> = arena.column("name").collect();
+            assert_eq!(names, ["ann", "bob", ""]);
+
+            let total: f64 = arena.column::<Option<f64>, _>("score").flatten().sum();
+            assert_eq!(total, -0.5);
+
+            let avatars: Vec<Option<&[u8]>> = arena.column("avatar").collect();
+            assert_eq!(avatars, [Some(&b"\x89PNG"[..]), None, Some(&b""[..])]);
+
+            assert!(arena.row(3).is_none());
+        }
+    }
+
+    #[test]
+    fn it_rejects_mismatched_reads() {
+        let arena = arena(Layout::Columnar);
+        let row = arena.row(0).unwrap();
+
+        assert!(row.try_get::<i32, _>("id").is_err());
+        assert!(row.try_get::<&str, _>("id").is_err());
+
+        // a column that may hold NULL is only read as an `Option`
+        assert!(row.try_get::<f64, _>("score").is_err());
+        assert_eq!(row.try_get::<Option<f64>, _>("score").unwrap(), Some(1.5));
+
+        assert!(row.try_get::<i64, _>("missing").is_err());
+        assert!(row.try_get::<i64, _>(4).is_err());
+    }
+}
@@ -2,7 +2,9 @@
 
 use crate::database::{Database, HasArguments};
 use crate::encode::Encode;
+use crate::snapshot::SnapshotValue;
 use crate::types::Type;
+use std::fmt::{self, Write};
 
 /// A tuple of arguments to be sent to the database.
 pub trait Arguments<'q>: Send + Sized + Default {
@@ -16,6 +18,40 @@ pub trait Arguments<'q>: Send + Sized + Default {
     fn add<T>(&mut self, value: T)
     where
         T: 'q + Send + Encode<'q, Self::Database> + Type<Self::Database>;
+
+    /// Writes the bind parameter placeholder for the most recently added argument.
+    ///
+    /// Defaults to `?`. Drivers that use positional parameters (`$1 .. $N` for Postgres,
+    /// `@p1 .. @pN` for MSSQL) override this.
+    fn format_placeholder<W: Write>(&self, writer: &mut W) -> fmt::Result {
+        writer.write_str("?")
+    }
+
+    /// Returns a copy of the values added so far, for logging, or `None` if the driver
+    /// cannot read its encoded arguments back.
+    ///
+    /// Values of types the driver cannot render as text are captured as the bytes of their
+    /// encoding. Defaults to `None`; implemented for PostgreSQL, MySQL and SQLite.
+    fn snapshot(&self) -> Option<Vec<SnapshotValue>> {
+        None
+    }
+
+    /// Add a value captured by [`snapshot`][Self::snapshot] to the end of the arguments.
+    ///
+    /// Drivers that capture [typed][SnapshotValue::Typed] values override this to bind them as
+    /// the type they were captured as; by default they are bound as text.
+    fn add_snapshot_value(&mut self, value: &SnapshotValue)
+    where
+        SnapshotValue: Encode<'q, Self::Database> + Type<Self::Database>,
+    {
+        self.add(value.clone());
+    }
+
+    // empty arguments that write the same placeholders as `self`
+    #[doc(hidden)]
+    fn empty_like(&self) -> Self {
+        Self::default()
+    }
 }
 
 pub trait IntoArguments<'q, DB: HasArguments<'q>>: Sized + Send {
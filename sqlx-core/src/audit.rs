@@ -0,0 +1,495 @@
+//! Record the mutations executed through an executor for audit logging.
+
+use std::fmt::{self, Debug, Formatter};
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use either::Either;
+use futures_core::future::BoxFuture;
+use futures_core::stream::BoxStream;
+use futures_util::TryStreamExt;
+
+use crate::arguments::Arguments;
+use crate::database::{Database, HasStatement};
+use crate::describe::Describe;
+use crate::done::Done;
+use crate::error::Error;
+use crate::executor::{Captured, Execute, Executor};
+use crate::pool::Pool;
+use crate::snapshot::{QuerySnapshot, Redaction};
+
+/// A mutation, such as an `INSERT`, `UPDATE` or `DELETE`, executed through an [`Audited`] executor.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct AuditEvent {
+    /// The SQL and arguments of the query, redacted according to the [`AuditLog`].
+    pub query: QuerySnapshot,
+
+    /// `false` if the driver cannot read the arguments of a query back, in which case
+    /// `query` holds none. This is the case for MSSQL and the `Any` driver.
+    pub arguments_captured: bool,
+
+    /// The number of rows affected by the query.
+    pub rows_affected: u64,
+
+    /// The id given by [`AuditLog::transaction`] to the transaction the query ran in.
+    pub transaction_id: Option<u64>,
+}
+
+/// Receives the events of an [`AuditLog`].
+///
+/// Implemented for closures. Events are recorded as queries complete, on the task running
+/// them; send them to a channel to write them out in the background.
+pub trait AuditSink: Send + Sync + 'static {
+    fn record(&self, event: AuditEvent);
+}
+
+impl<F> AuditSink for F
+where
+    F: Fn(AuditEvent) + Send + Sync + 'static,
+{
+    fn record(&self, event: AuditEvent) {
+        self(event)
+    }
+}
+
+/// Captures every mutation executed through the executors it wraps and forwards it to a
+/// sink.
+///
+/// ```rust,ignore
+/// let audit = AuditLog::new(move |event: AuditEvent| {
+///     let _ = events.try_send(event);
+/// })
+/// .redaction(Redaction::new().bytes());
+///
+/// let mut tx = audit.transaction(pool.begin().await?);
+///
+/// sqlx::query("UPDATE accounts SET balance = balance - $1 WHERE id = $2")
+///     .bind(amount)
+///     .bind(id)
+///     .execute(&mut tx)
+///     .await?;
+///
+/// tx.into_inner().commit().await?;
+/// ```
+///
+/// A query is recorded once it completes. Queries that fail, and streams or futures dropped
+/// before the query completed, are not recorded. A query is considered a mutation if one of
+/// its statements starts with `INSERT`, `UPDATE`, `DELETE`, `REPLACE`, `MERGE` or `TRUNCATE`,
+/// or with a `WITH` clause mentioning one of them.
+///
+/// A mutation executed through `fetch_one` or `fetch_optional` completes with the statement
+/// that returned its row, and is recorded with the rows affected reported by the database for
+/// that statement.
+#[derive(Clone)]
+pub struct AuditLog {
+    sink: Arc<dyn AuditSink>,
+    redaction: Arc<Redaction>,
+}
+
+impl AuditLog {
+    /// Create an audit log forwarding events to `sink`.
+    pub fn new(sink: impl AuditSink) -> Self {
+        Self {
+            sink: Arc::new(sink),
+            redaction: Arc::new(Redaction::new()),
+        }
+    }
+
+    /// Redact the arguments of recorded queries according to `rules`.
+    pub fn redaction(mut self, rules: Redaction) -> Self {
+        self.redaction = Arc::new(rules);
+        self
+    }
+
+    /// Audit the mutations executed through `inner`, which may be a `&mut` connection,
+    /// a [`PoolConnection`] or a [`Pool`].
+    ///
+    /// [`PoolConnection`]: crate::pool::PoolConnection
+    pub fn wrap<C>(&self, inner: C) -> Audited<C> {
+        Audited {
+            inner,
+            log: self.clone(),
+            transaction_id: None,
+        }
+    }
+
+    /// Audit the mutations executed through the [`Transaction`] `inner`, tagging each of
+    /// them with an id unique to the transaction within this process.
+    ///
+    /// [`Transaction`]: crate::transaction::Transaction
+    pub fn transaction<C>(&self, inner: C) -> Audited<C> {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+        Audited {
+            inner,
+            log: self.clone(),
+            transaction_id: Some(NEXT_ID.fetch_add(1, Ordering::Relaxed)),
+        }
+    }
+
+    // take the arguments out of `query` to capture them if it is a mutation; the returned
+    // query hands them back to the executor
+    fn capture<'q, DB, E>(
+        &self,
+        transaction_id: Option<u64>,
+        mut query: E,
+    ) -> (Captured<'q, DB, E>, Option<Pending>)
+    where
+        DB: Database,
+        E: Execute<'q, DB>,
+    {
+        let arguments = query.take_arguments();
+
+        let pending = if is_mutation(query.sql()) {
+            let values = match &arguments {
+                Some(arguments) => arguments.snapshot(),
+                None => Some(Vec::new()),
+            };
+
+            let arguments_captured = values.is_some();
+            let snapshot = values
+                .into_iter()
+                .flatten()
+                .fold(QuerySnapshot::new(query.sql()), QuerySnapshot::bind);
+
+            Some(Pending {
+                sink: Arc::clone(&self.sink),
+                completed: false,
+                event: Some(AuditEvent {
+                    query: snapshot.redact(&self.redaction),
+                    arguments_captured,
+                    rows_affected: 0,
+                    transaction_id,
+                }),
+            })
+        } else {
+            None
+        };
+
+        let query = Captured::new(query, arguments);
+
+        (query, pending)
+    }
+}
+
+impl Debug for AuditLog {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AuditLog")
+            .field("redaction", &self.redaction)
+            .finish()
+    }
+}
+
+/// Wraps an executor so that the mutations executed through it are recorded to an
+/// [`AuditLog`].
+///
+/// Queries are executed with `&mut audited` (or `&audited` for a pool).
+#[derive(Debug)]
+pub struct Audited<C> {
+    inner: C,
+    log: AuditLog,
+    transaction_id: Option<u64>,
+}
+
+impl<C> Audited<C> {
+    /// Returns the id given to the audited transaction, if any.
+    pub fn transaction_id(&self) -> Option<u64> {
+        self.transaction_id
+    }
+
+    /// Unwrap the connection, transaction or pool, no longer auditing it.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+}
+
+impl<C> Deref for Audited<C> {
+    type Target = C;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<C> DerefMut for Audited<C> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+impl<'c, C, DB> Executor<'c> for &'c mut Audited<C>
+where
+    C: DerefMut + Send + Debug,
+    DB: Database,
+    for<'a> &'a mut C::Target: Executor<'a, Database = DB>,
+{
+    type Database = DB;
+
+    fn fetch_many<'e, 'q: 'e, E: 'q>(
+        self,
+        query: E,
+    ) -> BoxStream<'e, Result<Either<DB::Done, DB::Row>, Error>>
+    where
+        'c: 'e,
+        E: Execute<'q, Self::Database>,
+    {
+        let (query, pending) = self.log.capture(self.transaction_id, query);
+        let stream = (&mut *self.inner).fetch_many(query);
+
+        match pending {
+            Some(pending) => audit_stream::<DB>(pending, stream),
+            None => stream,
+        }
+    }
+
+    fn fetch_optional<'e, 'q: 'e, E: 'q>(
+        self,
+        query: E,
+    ) -> BoxFuture<'e, Result<Option<DB::Row>, Error>>
+    where
+        'c: 'e,
+        E: Execute<'q, Self::Database>,
+    {
+        let (query, pending) = self.log.capture(self.transaction_id, query);
+
+        match pending {
+            Some(pending) => first_row::<DB>(pending, (&mut *self.inner).fetch_many(query)),
+
+            None => (&mut *self.inner).fetch_optional(query),
+        }
+    }
+
+    fn prepare_with<'e, 'q: 'e>(
+        self,
+        sql: &'q str,
+        parameters: &'e [DB::TypeInfo],
+    ) -> BoxFuture<'e, Result<<DB as HasStatement<'q>>::Statement, Error>>
+    where
+        'c: 'e,
+    {
+        (&mut *self.inner).prepare_with(sql, parameters)
+    }
+
+    #[doc(hidden)]
+    fn describe<'e, 'q: 'e>(self, sql: &'q str) -> BoxFuture<'e, Result<Describe<DB>, Error>>
+    where
+        'c: 'e,
+    {
+        (&mut *self.inner).describe(sql)
+    }
+}
+
+impl<'p, DB: Database> Executor<'p> for &'_ Audited<Pool<DB>>
+where
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+{
+    type Database = DB;
+
+    fn fetch_many<'e, 'q: 'e, E: 'q>(
+        self,
+        query: E,
+    ) -> BoxStream<'e, Result<Either<DB::Done, DB::Row>, Error>>
+    where
+        E: Execute<'q, Self::Database>,
+    {
+        let (query, pending) = self.log.capture(self.transaction_id, query);
+        let stream = self.inner.fetch_many(query);
+
+        match pending {
+            Some(pending) => audit_stream::<DB>(pending, stream),
+            None => stream,
+        }
+    }
+
+    fn fetch_optional<'e, 'q: 'e, E: 'q>(
+        self,
+        query: E,
+    ) -> BoxFuture<'e, Result<Option<DB::Row>, Error>>
+    where
+        E: Execute<'q, Self::Database>,
+    {
+        let (query, pending) = self.log.capture(self.transaction_id, query);
+
+        match pending {
+            Some(pending) => first_row::<DB>(pending, self.inner.fetch_many(query)),
+            None => self.inner.fetch_optional(query),
+        }
+    }
+
+    fn prepare_with<'e, 'q: 'e>(
+        self,
+        sql: &'q str,
+        parameters: &'e [DB::TypeInfo],
+    ) -> BoxFuture<'e, Result<<DB as HasStatement<'q>>::Statement, Error>> {
+        self.inner.prepare_with(sql, parameters)
+    }
+
+    #[doc(hidden)]
+    fn describe<'e, 'q: 'e>(self, sql: &'q str) -> BoxFuture<'e, Result<Describe<DB>, Error>> {
+        self.inner.describe(sql)
+    }
+}
+
+// the event of a running mutation, recorded when dropped if the mutation completed
+struct Pending {
+    sink: Arc<dyn AuditSink>,
+    completed: bool,
+    event: Option<AuditEvent>,
+}
+
+impl Pending {
+    fn add_done<D: Done>(&mut self, done: &D) {
+        if let Some(event) = &mut self.event {
+            event.rows_affected += done.rows_affected();
+        }
+    }
+
+    fn complete(&mut self) {
+        self.completed = true;
+    }
+
+    fn discard(&mut self) {
+        self.event = None;
+    }
+}
+
+impl Drop for Pending {
+    fn drop(&mut self) {
+        if !self.completed {
+            return;
+        }
+
+        if let Some(event) = self.event.take() {
+            self.sink.record(event);
+        }
+    }
+}
+
+fn audit_stream<'e, DB: Database>(
+    mut pending: Pending,
+    mut stream: BoxStream<'e, Result<Either<DB::Done, DB::Row>, Error>>,
+) -> BoxStream<'e, Result<Either<DB::Done, DB::Row>, Error>> {
+    Box::pin(try_stream! {
+        loop {
+            let item = match stream.try_next().await {
+                Ok(Some(item)) => item,
+
+                Ok(None) => {
+                    pending.complete();
+                    break;
+                }
+
+                Err(error) => {
+                    pending.discard();
+                    return Err(error);
+                }
+            };
+
+            if let Either::Left(done) = &item {
+                pending.add_done(done);
+            }
+
+            r#yield!(item);
+        }
+
+        Ok(())
+    })
+}
+
+// read a mutation up to the end of the statement returning the first row, whose done
+// message counts the rows it affected; like `fetch_optional` of the drivers, the rest of
+// the response is discarded by the connection before its next query
+fn first_row<'e, DB: Database>(
+    mut pending: Pending,
+    mut stream: BoxStream<'e, Result<Either<DB::Done, DB::Row>, Error>>,
+) -> BoxFuture<'e, Result<Option<DB::Row>, Error>> {
+    Box::pin(async move {
+        let mut first = None;
+
+        loop {
+            match stream.try_next().await {
+                Ok(Some(Either::Right(row))) => {
+                    first.get_or_insert(row);
+                }
+
+                Ok(Some(Either::Left(done))) => {
+                    pending.add_done(&done);
+
+                    if first.is_some() {
+                        break;
+                    }
+                }
+
+                Ok(None) => break,
+
+                Err(error) => {
+                    pending.discard();
+                    return Err(error);
+                }
+            }
+        }
+
+        pending.complete();
+
+        Ok(first)
+    })
+}
+
+const MUTATIONS: &[&str] = &["INSERT", "UPDATE", "DELETE", "REPLACE", "MERGE", "TRUNCATE"];
+
+// whether any statement of `sql` starts with a data-modifying keyword, or with a `WITH`
+// clause containing one; this errs on the side of auditing too much
+fn is_mutation(sql: &str) -> bool {
+    let is_keyword = |word: &str| MUTATIONS.iter().any(|kw| word.eq_ignore_ascii_case(kw));
+
+    sql.split(';').any(|statement| {
+        let mut words = skip_comments(statement)
+            .split(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+            .filter(|word| !word.is_empty());
+
+        match words.next() {
+            Some(word) if word.eq_ignore_ascii_case("WITH") => words.any(is_keyword),
+            Some(word) => is_keyword(word),
+            None => false,
+        }
+    })
+}
+
+fn skip_comments(mut sql: &str) -> &str {
+    loop {
+        sql = sql.trim_start_matches(|c: char| c.is_whitespace() || c == '(');
+
+        if let Some(rest) = sql.strip_prefix("--") {
+            sql = rest.find('\n').map_or("", |end| &rest[end..]);
+        } else if let Some(rest) = sql.strip_prefix("/*") {
+            sql = rest.find("*/").map_or("", |end| &rest[end + 2..]);
+        } else {
+            return sql;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_mutation;
+
+    #[test]
+    fn it_detects_mutations() {
+        assert!(is_mutation("INSERT INTO users (name) VALUES ($1)"));
+        assert!(is_mutation("  update users SET name = $1"));
+        assert!(is_mutation("-- remove\n/* all */ DELETE FROM users"));
+        assert!(is_mutation(
+            "WITH moved AS (DELETE FROM a RETURNING *) INSERT INTO b SELECT * FROM moved"
+        ));
+        assert!(is_mutation("SELECT 1; DELETE FROM users"));
+        assert!(is_mutation("TRUNCATE TABLE users"));
+        assert!(is_mutation("truncate users, orders RESTART IDENTITY"));
+
+        assert!(!is_mutation("SELECT * FROM users WHERE deleted"));
+        assert!(!is_mutation("WITH t AS (SELECT 1) SELECT * FROM t"));
+        assert!(!is_mutation("-- DELETE\nSELECT 1"));
+        assert!(!is_mutation(""));
+    }
+}
@@ -0,0 +1,335 @@
+//! Batched `UPDATE` statements keyed by a single column.
+
+use crate::arguments::Arguments;
+use crate::database::{Database, HasArguments};
+use crate::encode::Encode;
+use crate::query_builder::QueryBuilder;
+use crate::types::Type;
+
+type BindFn<'args, DB> = Box<dyn FnOnce(&mut QueryBuilder<'args, DB>) + Send + 'args>;
+
+/// The set of columns to change on a single row of a [`BulkUpdate`].
+pub struct Changes<'args, DB: Database> {
+    fields: Vec<(String, BindFn<'args, DB>)>,
+}
+
+impl<'args, DB: Database> Default for Changes<'args, DB> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'args, DB: Database> Changes<'args, DB> {
+    pub fn new() -> Self {
+        Self { fields: Vec::new() }
+    }
+
+    /// Set `column` to `value` on this row.
+    ///
+    /// The column name is inserted into the generated SQL verbatim.
+    pub fn set<T>(mut self, column: impl Into<String>, value: T) -> Self
+    where
+        T: 'args + Send + Encode<'args, DB> + Type<DB>,
+    {
+        self.fields.push((
+            column.into(),
+            Box::new(move |builder: &mut QueryBuilder<'args, DB>| {
+                builder.push_bind(value);
+            }),
+        ));
+
+        self
+    }
+
+    /// Returns `true` if no columns have been set.
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+}
+
+/// Builds batched `UPDATE` statements for many rows identified by a key column.
+///
+/// Each row may change a different set of columns. Rows are grouped into batches of at most
+/// [`batch_size`][Self::batch_size] rows and every batch becomes one statement of the form:
+///
+/// ```sql
+/// UPDATE users
+/// SET name = CASE id WHEN ? THEN ? WHEN ? THEN ? ELSE name END,
+///     age = CASE id WHEN ? THEN ? ELSE age END
+/// WHERE id IN (?, ?)
+/// ```
+///
+/// `CASE` expressions are used instead of joining against a `VALUES` list as they are
+/// understood by every supported database and do not require casting the bind parameters.
+///
+/// Table and column names are inserted into the SQL verbatim and so must not come from
+/// untrusted input; all keys and values are sent as bind parameters.
+///
+/// ```rust,ignore
+/// let mut update = BulkUpdate::<Postgres, i64>::new("users", "id");
+///
+/// update.push(1, Changes::new().set("name", "Alice"));
+/// update.push(2, Changes::new().set("name", "Bob").set("age", 42_i32));
+///
+/// let mut tx = pool.begin().await?;
+///
+/// for mut builder in update.into_builders() {
+///     builder.build().execute(&mut tx).await?;
+/// }
+///
+/// tx.commit().await?;
+/// ```
+///
+/// With [`returning`][Self::returning], the updated rows can be read back along with the
+/// number of rows updated by each batch:
+///
+/// ```rust,ignore
+/// let update = BulkUpdate::<Postgres, i64>::new("users", "id").returning("id, name");
+///
+/// for mut builder in update.into_builders() {
+///     let updated = builder.build().execute_returning(&mut tx).await?;
+///
+///     for row in updated.rows() {
+///         // ...
+///     }
+/// }
+/// ```
+pub struct BulkUpdate<'args, DB: Database, K> {
+    table: String,
+    key_column: String,
+    batch_size: usize,
+    returning: Option<String>,
+    arguments: <DB as HasArguments<'args>>::Arguments,
+    rows: Vec<(K, Changes<'args, DB>)>,
+}
+
+impl<'args, DB, K> BulkUpdate<'args, DB, K>
+where
+    DB: Database,
+    K: 'args + Clone + Send + Encode<'args, DB> + Type<DB>,
+{
+    /// Start a bulk update of `table`, matching rows on `key_column`.
+    pub fn new(table: impl Into<String>, key_column: impl Into<String>) -> Self {
+        Self {
+            table: table.into(),
+            key_column: key_column.into(),
+            batch_size: 100,
+            returning: None,
+            arguments: Default::default(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// Set the maximum number of rows to update in a single statement.
+    ///
+    /// Defaults to `100`. Keep in mind that each row uses two bind parameters per changed
+    /// column plus one for the `WHERE` clause; databases limit the number of parameters
+    /// in a statement (e.g. `65535` in PostgreSQL, `2100` in MSSQL).
+    ///
+    /// # Panics
+    /// If `size` is zero.
+    pub fn batch_size(mut self, size: usize) -> Self {
+        assert!(size > 0, "batch size must be greater than zero");
+
+        self.batch_size = size;
+        self
+    }
+
+    /// End every statement with a `RETURNING` clause listing `columns`, inserted into the SQL
+    /// verbatim.
+    ///
+    /// Supported by PostgreSQL, and by SQLite from version 3.35.
+    pub fn returning(mut self, columns: impl Into<String>) -> Self {
+        self.returning = Some(columns.into());
+        self
+    }
+
+    /// Bind the parameters of every statement to arguments like `arguments`, which should be
+    /// empty.
+    ///
+    /// Only needed with the `Any` driver, to write the placeholders of the database the
+    /// statements will run on: pass [`AnyArguments::for_kind`].
+    ///
+    /// [`AnyArguments::for_kind`]: crate::any::AnyArguments::for_kind
+    pub fn arguments(mut self, arguments: <DB as HasArguments<'args>>::Arguments) -> Self {
+        self.arguments = arguments;
+        self
+    }
+
+    /// Queue an update of the row identified by `key`.
+    ///
+    /// Rows with no changes are skipped.
+    pub fn push(&mut self, key: K, changes: Changes<'args, DB>) -> &mut Self {
+        if !changes.is_empty() {
+            self.rows.push((key, changes));
+        }
+
+        self
+    }
+
+    /// Returns the number of rows queued for update.
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns `true` if no rows are queued for update.
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Generate one [`QueryBuilder`] per batch of rows.
+    pub fn into_builders(self) -> Vec<QueryBuilder<'args, DB>> {
+        let BulkUpdate {
+            table,
+            key_column,
+            batch_size,
+            returning,
+            arguments,
+            rows,
+        } = self;
+
+        let mut builders = Vec::new();
+        let mut rows = rows.into_iter().peekable();
+
+        while rows.peek().is_some() {
+            let batch: Vec<_> = rows.by_ref().take(batch_size).collect();
+
+            builders.push(build_batch(
+                &table,
+                &key_column,
+                returning.as_deref(),
+                arguments.empty_like(),
+                batch,
+            ));
+        }
+
+        builders
+    }
+}
+
+impl<'args, DB, K> Extend<(K, Changes<'args, DB>)> for BulkUpdate<'args, DB, K>
+where
+    DB: Database,
+    K: 'args + Clone + Send + Encode<'args, DB> + Type<DB>,
+{
+    fn extend<I: IntoIterator<Item = (K, Changes<'args, DB>)>>(&mut self, iter: I) {
+        for (key, changes) in iter {
+            self.push(key, changes);
+        }
+    }
+}
+
+fn build_batch<'args, DB, K>(
+    table: &str,
+    key_column: &str,
+    returning: Option<&str>,
+    arguments: <DB as HasArguments<'args>>::Arguments,
+    batch: Vec<(K, Changes<'args, DB>)>,
+) -> QueryBuilder<'args, DB>
+where
+    DB: Database,
+    K: 'args + Clone + Send + Encode<'args, DB> + Type<DB>,
+{
+    // columns, in order of first appearance, each with the (row, value) pairs that change it
+    let mut columns: Vec<(String, Vec<(usize, BindFn<'args, DB>)>)> = Vec::new();
+    let mut keys = Vec::with_capacity(batch.len());
+
+    for (row, (key, changes)) in batch.into_iter().enumerate() {
+        keys.push(key);
+
+        for (column, bind) in changes.fields {
+            match columns.iter_mut().find(|(name, _)| *name == column) {
+                Some((_, values)) => values.push((row, bind)),
+                None => columns.push((column, vec![(row, bind)])),
+            }
+        }
+    }
+
+    let mut builder = QueryBuilder::with_arguments(format!("UPDATE {} SET ", table), arguments);
+
+    for (i, (column, values)) in columns.into_iter().enumerate() {
+        if i > 0 {
+            builder.push(", ");
+        }
+
+        builder.push(format_args!("{} = CASE {}", column, key_column));
+
+        for (row, bind) in values {
+            builder.push(" WHEN ");
+            builder.push_bind(keys[row].clone());
+            builder.push(" THEN ");
+            bind(&mut builder);
+        }
+
+        builder.push(format_args!(" ELSE {} END", column));
+    }
+
+    builder.push(format_args!(" WHERE {} IN (", key_column));
+
+    let mut list = builder.separated(", ");
+
+    for key in keys {
+        list.push_bind(key);
+    }
+
+    list.push_unseparated(")");
+
+    if let Some(columns) = returning {
+        builder.push(format_args!(" RETURNING {}", columns));
+    }
+
+    builder
+}
+
+#[cfg(all(test, feature = "postgres"))]
+mod tests {
+    use super::{BulkUpdate, Changes};
+    use crate::postgres::Postgres;
+
+    #[test]
+    fn it_generates_case_expressions_per_column() {
+        let mut update = BulkUpdate::<Postgres, i64>::new("users", "id");
+
+        update.push(1, Changes::new().set("name", "Alice"));
+        update.push(2, Changes::new().set("name", "Bob").set("age", 42_i32));
+        update.push(3, Changes::new());
+
+        let builders = update.into_builders();
+
+        assert_eq!(builders.len(), 1);
+        assert_eq!(
+            builders[0].sql(),
+            "UPDATE users SET \
+             name = CASE id WHEN $1 THEN $2 WHEN $3 THEN $4 ELSE name END, \
+             age = CASE id WHEN $5 THEN $6 ELSE age END \
+             WHERE id IN ($7, $8)"
+        );
+    }
+
+    #[test]
+    fn it_splits_rows_into_batches() {
+        let mut update = BulkUpdate::<Postgres, i32>::new("t", "k").batch_size(2);
+
+        update.extend((0..5).map(|key| (key, Changes::new().set("v", key * 10))));
+
+        let builders = update.into_builders();
+
+        assert_eq!(builders.len(), 3);
+        assert_eq!(
+            builders[2].sql(),
+            "UPDATE t SET v = CASE k WHEN $1 THEN $2 ELSE v END WHERE k IN ($3)"
+        );
+    }
+
+    #[test]
+    fn it_appends_a_returning_clause() {
+        let mut update = BulkUpdate::<Postgres, i32>::new("t", "k").returning("k, v");
+
+        update.push(1, Changes::new().set("v", 10));
+
+        assert_eq!(
+            update.into_builders()[0].sql(),
+            "UPDATE t SET v = CASE k WHEN $1 THEN $2 ELSE v END WHERE k IN ($3) RETURNING k, v"
+        );
+    }
+}
@@ -43,13 +43,17 @@ impl<T> StatementCache<T> {
         self.inner.len()
     }
 
+    /// Removes the statement for the given key from the cache, if any.
+    pub fn remove(&mut self, k: &str) -> Option<T> {
+        self.inner.remove(k)
+    }
+
     /// Removes the least recently used item from the cache.
     pub fn remove_lru(&mut self) -> Option<T> {
         self.inner.remove_lru().map(|(_, v)| v)
     }
 
     /// Clear all cached statements from the cache.
-    #[cfg(feature = "sqlite")]
     pub fn clear(&mut self) {
         self.inner.clear();
     }
@@ -1,9 +1,13 @@
 use crate::database::{Database, HasStatementCache};
 use crate::error::Error;
+use crate::sqlstate::SqlStateClass;
 use crate::transaction::Transaction;
+use crate::type_overrides::TypeOverrides;
 use futures_core::future::BoxFuture;
 use log::LevelFilter;
+use std::collections::BTreeMap;
 use std::fmt::Debug;
+use std::io;
 use std::str::FromStr;
 use std::time::Duration;
 
@@ -23,6 +27,23 @@ pub trait Connection: Send {
     /// Checks if a connection to the database is still valid.
     fn ping(&mut self) -> BoxFuture<'_, Result<(), Error>>;
 
+    /// Reset the session of this connection to the state of a new session: the open
+    /// transaction is rolled back and temporary tables, session variables and statements
+    /// prepared on the server are dropped.
+    ///
+    /// This runs `DISCARD ALL` on PostgreSQL, `COM_RESET_CONNECTION` on MySQL and a batch with
+    /// the `RESETCONNECTION` flag on MSSQL. SQLite has no session to reset: the open transaction
+    /// is rolled back and the objects of the `temp` schema are dropped. The statement cache of
+    /// the connection is emptied on every database.
+    ///
+    /// A [`Pool`] calls this on the connections returned to it when created with
+    /// [`reset_on_release`][crate::pool::PoolOptions::reset_on_release].
+    ///
+    /// [`Pool`]: crate::pool::Pool
+    fn reset(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move { Ok(()) })
+    }
+
     /// Begin a new transaction or establish a savepoint within the active transaction.
     ///
     /// Returns a [`Transaction`] for controlling and tracking the new transaction.
@@ -96,6 +117,84 @@ pub trait Connection: Send {
         Box::pin(async move { Ok(()) })
     }
 
+    /// Removes the statement prepared for `sql` from the cache, closing it on the server if
+    /// needed, instead of waiting for it to be evicted.
+    ///
+    /// Returns `false` if no statement was cached for `sql`.
+    fn close_cached_statement<'e>(&'e mut self, sql: &'e str) -> BoxFuture<'e, Result<bool, Error>>
+    where
+        Self::Database: HasStatementCache,
+    {
+        let _ = sql;
+
+        Box::pin(async move { Ok(false) })
+    }
+
+    /// Set the overrides consulted when getting values from the rows subsequently returned
+    /// by this connection, replacing the overrides previously set.
+    ///
+    /// See [`TypeOverrides`]. Connections of drivers outside of SQLx that do not support
+    /// overrides ignore them.
+    fn set_type_overrides(&mut self, overrides: TypeOverrides<Self::Database>) {
+        let _ = overrides;
+    }
+
+    /// Returns a snapshot of the current state of this connection.
+    ///
+    /// Intended for error reports and support tooling; see [`ConnectionState`].
+    fn dump_state(&self) -> ConnectionState {
+        ConnectionState::default()
+    }
+
+    /// Returns `true` if the server has notified this connection that it is shutting down or
+    /// has terminated the session. Such a connection cannot be used again.
+    ///
+    /// A [`Pool`] that gets back a connection that received such a notice closes its idle
+    /// connections, which are likely terminated too, and reconnects as needed.
+    ///
+    /// See also `is_server_shutdown()` on [`DatabaseError`].
+    ///
+    /// [`Pool`]: crate::pool::Pool
+    /// [`DatabaseError`]: crate::error::DatabaseError
+    fn received_shutdown_notice(&self) -> bool {
+        false
+    }
+
+    /// Returns `true` if an operation on this connection was cancelled at a point the
+    /// connection cannot recover from. Such a connection cannot be used again.
+    ///
+    /// Dropping the future or stream of an operation on a connection does not, as a rule,
+    /// break it: see [cancellation](crate::executor::Executor#cancellation). A [`Pool`] closes
+    /// connections that are broken instead of reusing them.
+    ///
+    /// [`Pool`]: crate::pool::Pool
+    fn is_broken(&self) -> bool {
+        false
+    }
+
+    /// Returns `true` if the database this connection is connected to supports `capability`,
+    /// going by the version of the server and the capabilities of the driver.
+    ///
+    /// Applications targeting several databases, or several versions of one, can check for
+    /// optional features and fall back to other SQL when they are missing:
+    ///
+    /// ```rust,ignore
+    /// use sqlx::connection::Capability;
+    ///
+    /// let sql = if conn.supports(Capability::SkipLocked) {
+    ///     "SELECT id FROM jobs LIMIT 1 FOR UPDATE SKIP LOCKED"
+    /// } else {
+    ///     "SELECT id FROM jobs LIMIT 1 FOR UPDATE"
+    /// };
+    /// ```
+    ///
+    /// Returns `false` for capabilities the driver does not know how to detect.
+    fn supports(&self, capability: Capability) -> bool {
+        let _ = capability;
+
+        false
+    }
+
     #[doc(hidden)]
     fn flush(&mut self) -> BoxFuture<'_, Result<(), Error>>;
 
@@ -125,6 +224,58 @@ pub trait Connection: Send {
     }
 }
 
+/// A point-in-time snapshot of the state of a connection, returned by
+/// [`Connection::dump_state`].
+///
+/// Values that a driver does not track are left at their defaults.
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct ConnectionState {
+    /// The number of transactions and savepoints currently open.
+    pub transaction_depth: usize,
+
+    /// The number of statements in the client-side statement cache.
+    pub cached_statements: usize,
+
+    /// The allocated size of the read buffer, in bytes.
+    pub read_buffer_capacity: usize,
+
+    /// The allocated size of the write buffer, in bytes.
+    pub write_buffer_capacity: usize,
+
+    /// The SQL of the most recently executed query.
+    pub last_sql: Option<String>,
+
+    /// Parameters reported by the server, such as `server_version`.
+    pub server_parameters: BTreeMap<String, String>,
+}
+
+/// An optional feature of a database, as checked by [`Connection::supports`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Capability {
+    /// A `RETURNING` clause on `INSERT`, `UPDATE` and `DELETE`.
+    ///
+    /// PostgreSQL, SQLite 3.35+ and MariaDB 10.5+. MariaDB only accepts it on `INSERT` and
+    /// `DELETE`.
+    Returning,
+
+    /// `SKIP LOCKED` on `SELECT .. FOR UPDATE`.
+    ///
+    /// PostgreSQL 9.5+, MySQL 8.0.1+ and MariaDB 10.6+.
+    SkipLocked,
+
+    /// The `->` and `->>` operators to extract values from JSON documents.
+    ///
+    /// PostgreSQL 9.3+, MySQL 5.7.13+ and SQLite 3.38+.
+    JsonOperators,
+
+    /// An `ON CONFLICT` clause on `INSERT`, to update or ignore conflicting rows.
+    ///
+    /// PostgreSQL 9.5+ and SQLite 3.24+.
+    OnConflict,
+}
+
 #[derive(Clone, Debug)]
 pub(crate) struct LogSettings {
     pub(crate) statements_level: LevelFilter,
@@ -173,3 +324,182 @@ pub trait ConnectOptions: 'static + Send + Sync + FromStr<Err = Error> + Debug {
             .log_slow_statements(LevelFilter::Off, Duration::default())
     }
 }
+
+/// How [`connect_with_retry`] retries a connection attempt that failed for a transient
+/// reason.
+///
+/// The delay before each retry grows exponentially from the initial backoff, up to the
+/// maximum backoff. By default, a connection is attempted up to 5 times, waiting 100
+/// milliseconds before the first retry and at most 5 seconds between attempts.
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    multiplier: f64,
+}
+
+impl RetryPolicy {
+    pub fn new() -> Self {
+        Self {
+            max_attempts: 5,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(5),
+            multiplier: 2.0,
+        }
+    }
+
+    /// Set the number of connection attempts to make, including the first one.
+    ///
+    /// A value of `1` disables retrying.
+    pub fn max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = attempts.max(1);
+        self
+    }
+
+    /// Set the delay before the first retry.
+    pub fn initial_backoff(mut self, backoff: Duration) -> Self {
+        self.initial_backoff = backoff;
+        self
+    }
+
+    /// Set the maximum delay between two attempts.
+    pub fn max_backoff(mut self, backoff: Duration) -> Self {
+        self.max_backoff = backoff;
+        self
+    }
+
+    /// Set the factor the delay is multiplied by after each retry.
+    pub fn multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    // the delay before retrying after the failed attempt `attempt`, counting from 1
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = self.multiplier.powi(attempt as i32 - 1);
+        let backoff = self.initial_backoff.as_secs_f64() * factor;
+
+        if backoff.is_finite() && backoff < self.max_backoff.as_secs_f64() {
+            Duration::from_secs_f64(backoff)
+        } else {
+            self.max_backoff
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Establish a new database connection with the provided options, retrying according to
+/// `policy` while the connection fails for a reason that may go away, such as the server
+/// refusing connections while starting up or a failed DNS lookup.
+///
+/// Errors that retrying cannot fix, such as invalid credentials, are returned immediately,
+/// as is the last error once every attempt has been made.
+///
+/// This is meant for establishing a connection when an application starts. A [`Pool`]
+/// already waits for the server to accept connections when opening new ones.
+///
+/// ```rust,ignore
+/// let options: PgConnectOptions = env::var("DATABASE_URL")?.parse()?;
+///
+/// let mut conn = connect_with_retry(&options, &RetryPolicy::new().max_attempts(10)).await?;
+/// ```
+///
+/// [`Pool`]: crate::pool::Pool
+pub async fn connect_with_retry<O>(
+    options: &O,
+    policy: &RetryPolicy,
+) -> Result<O::Connection, Error>
+where
+    O: ConnectOptions,
+    O::Connection: Sized,
+{
+    let mut attempt = 1;
+
+    loop {
+        match options.connect().await {
+            Err(error) if attempt < policy.max_attempts && is_transient_connect_error(&error) => {
+                let backoff = policy.backoff(attempt);
+
+                log::warn!(
+                    "failed to connect (attempt {} of {}), retrying in {:?}: {}",
+                    attempt,
+                    policy.max_attempts,
+                    backoff,
+                    error
+                );
+
+                sqlx_rt::sleep(backoff).await;
+                attempt += 1;
+            }
+
+            result => return result,
+        }
+    }
+}
+
+// whether establishing a connection may succeed if attempted again
+fn is_transient_connect_error(error: &Error) -> bool {
+    match error {
+        // refused or reset connections, timeouts and failed DNS lookups, but not invalid
+        // addresses or a lack of permission to open a socket
+        Error::Io(error) => !matches!(
+            error.kind(),
+            io::ErrorKind::InvalidInput | io::ErrorKind::PermissionDenied
+        ),
+
+        // the server is starting up, shutting down or out of connection slots
+        Error::Database(error) => {
+            let class = error
+                .code()
+                .and_then(|code| SqlStateClass::from_code(&code));
+
+            error.is_server_shutdown() || matches!(class, Some(class) if class.is_transient())
+        }
+
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_transient_connect_error, RetryPolicy};
+    use crate::error::Error;
+    use std::io;
+    use std::time::Duration;
+
+    #[test]
+    fn it_backs_off_exponentially() {
+        let policy = RetryPolicy::new()
+            .initial_backoff(Duration::from_millis(100))
+            .max_backoff(Duration::from_secs(1));
+
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(4), Duration::from_millis(800));
+        assert_eq!(policy.backoff(5), Duration::from_secs(1));
+        assert_eq!(policy.backoff(1000), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn it_retries_transient_errors_only() {
+        let io_error = |kind| Error::Io(io::Error::new(kind, "connect"));
+
+        assert!(is_transient_connect_error(&io_error(
+            io::ErrorKind::ConnectionRefused
+        )));
+        assert!(is_transient_connect_error(&io_error(io::ErrorKind::Other)));
+
+        assert!(!is_transient_connect_error(&io_error(
+            io::ErrorKind::PermissionDenied
+        )));
+        assert!(!is_transient_connect_error(&Error::Configuration(
+            "bad url".into()
+        )));
+    }
+}
@@ -0,0 +1,214 @@
+//! A registry of conversions used to get values from rows without matching types exactly.
+//!
+//! [`Row::try_get`] only succeeds when the Rust type is compatible with the SQL type of the
+//! column. On PostgreSQL lossless widening is built in, so an `i64` can be read from any integer
+//! column and an `f64` from any floating point column; MySQL and MSSQL require the exact type,
+//! and [`Conversions::with_numeric_widening`] provides the same widening for every driver.
+//! Either way a `UserId` cannot be read from a `BIGINT` column even if it implements
+//! `TryFrom<i64>`. Generic data access code can get such values through a [`Conversions`]
+//! registry, which tries each conversion registered for the requested type until it finds one
+//! whose source type matches the column.
+//!
+//! The registry is not consulted by [`Row::try_get`]: it finds conversions by the [`TypeId`] of
+//! the requested type, which only `'static` types have, while [`Row::try_get`] also decodes
+//! borrowed types such as `&str`.
+//!
+//! ```rust,ignore
+//! use sqlx::convert::Conversions;
+//!
+//! let conversions = Conversions::<Postgres>::new()
+//!     .with_numeric_widening()
+//!     .register::<String, Email>() // where Email: TryFrom<String>
+//!     .register::<i64, UserId>(); // where UserId: From<i64>
+//!
+//! let id: i64 = conversions.try_get(&row, "id")?; // INT2, INT4 or INT8
+//! let email: Email = conversions.try_get(&row, "email")?;
+//! ```
+//!
+//! [`Row::try_get`]: crate::row::Row::try_get
+//! [`TypeId`]: std::any::TypeId
+
+use std::any::{type_name, Any, TypeId};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt::{self, Debug, Formatter};
+
+use crate::column::ColumnIndex;
+use crate::database::Database;
+use crate::decode::Decode;
+use crate::error::{BoxDynError, Error, UnexpectedNullError};
+use crate::row::Row;
+use crate::type_info::TypeInfo;
+use crate::type_overrides::TypeOverrides;
+use crate::types::Type;
+use crate::value::ValueRef;
+
+// attempts a conversion from the column at the index; `None` if the column is not of the
+// source type of the conversion
+type Converter<DB> = Box<
+    dyn Fn(&<DB as Database>::Row, usize) -> Option<Result<Box<dyn Any>, BoxDynError>>
+        + Send
+        + Sync,
+>;
+
+/// A set of conversions from SQL values to Rust types.
+///
+/// See the [module documentation](self) for an example.
+pub struct Conversions<DB: Database> {
+    converters: HashMap<TypeId, Vec<Converter<DB>>>,
+}
+
+impl<DB> Conversions<DB>
+where
+    DB: Database,
+    usize: ColumnIndex<DB::Row>,
+{
+    /// Create an empty registry.
+    ///
+    /// Note that an empty registry cannot get any value; to read a type as-is, register it as
+    /// a conversion from itself.
+    pub fn new() -> Self {
+        Self {
+            converters: HashMap::new(),
+        }
+    }
+
+    /// Register a conversion to `T` from columns that can be decoded as `S`.
+    ///
+    /// Conversions to the same type are tried in the order they were registered, and the
+    /// first one whose source type is compatible with the column is used. Registering `T` as
+    /// a conversion from itself lets it be read without conversion.
+    pub fn register<S, T>(mut self) -> Self
+    where
+        S: for<'r> Decode<'r, DB> + Type<DB>,
+        T: 'static + TryFrom<S>,
+        T::Error: Into<BoxDynError>,
+    {
+        let converter: Converter<DB> = Box::new(|row, index| {
+            let value = match row.try_get_raw(index) {
+                Ok(value) => value,
+                Err(error) => return Some(Err(error.into())),
+            };
+
+            let ty = value.type_info();
+
+            if !ty.is_null() && !TypeOverrides::compatible::<S>(row.type_overrides(), &ty) {
+                return None;
+            }
+
+            Some(
+                S::decode(value)
+                    .and_then(|value| T::try_from(value).map_err(Into::into))
+                    .map(|value| Box::new(value) as Box<dyn Any>),
+            )
+        });
+
+        self.converters
+            .entry(TypeId::of::<T>())
+            .or_default()
+            .push(converter);
+
+        self
+    }
+
+    /// Register reading `i64` from any integer column and `f64` from any floating point
+    /// column, as well as `i32` from 16- and 32-bit integer columns.
+    pub fn with_numeric_widening(self) -> Self
+    where
+        i16: for<'r> Decode<'r, DB> + Type<DB>,
+        i32: for<'r> Decode<'r, DB> + Type<DB>,
+        i64: for<'r> Decode<'r, DB> + Type<DB>,
+        f32: for<'r> Decode<'r, DB> + Type<DB>,
+        f64: for<'r> Decode<'r, DB> + Type<DB>,
+    {
+        self.register::<i64, i64>()
+            .register::<i32, i64>()
+            .register::<i16, i64>()
+            .register::<i32, i32>()
+            .register::<i16, i32>()
+            .register::<f64, f64>()
+            .register::<f32, f64>()
+    }
+
+    /// Returns `true` if any conversion to `T` has been registered.
+    pub fn contains<T: 'static>(&self) -> bool {
+        self.converters.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Get the value of a column as `T` using the registered conversions.
+    ///
+    /// Returns [`Error::ColumnDecode`] if the column is `NULL`, if no registered conversion to
+    /// `T` accepts the SQL type of the column, or if the conversion fails.
+    pub fn try_get<T, I>(&self, row: &DB::Row, index: I) -> Result<T, Error>
+    where
+        T: 'static,
+        I: ColumnIndex<DB::Row>,
+    {
+        self.try_get_optional(row, &index)?
+            .ok_or_else(|| Error::ColumnDecode {
+                index: format!("{:?}", index),
+                source: UnexpectedNullError.into(),
+            })
+    }
+
+    /// Get the value of a column as `T` using the registered conversions, or `None` if the
+    /// column is `NULL`.
+    pub fn try_get_optional<T, I>(&self, row: &DB::Row, index: I) -> Result<Option<T>, Error>
+    where
+        T: 'static,
+        I: ColumnIndex<DB::Row>,
+    {
+        let column = index.index(row)?;
+        let value = row.try_get_raw(column)?;
+
+        if value.is_null() {
+            return Ok(None);
+        }
+
+        let decode_error = |source| Error::ColumnDecode {
+            index: format!("{:?}", index),
+            source,
+        };
+
+        let converters = self
+            .converters
+            .get(&TypeId::of::<T>())
+            .map_or(&[][..], Vec::as_slice);
+
+        for converter in converters {
+            if let Some(result) = converter(row, column) {
+                let value = result.map_err(decode_error)?;
+
+                // the converters under this key always produce a `T`
+                return Ok(Some(*value.downcast::<T>().unwrap()));
+            }
+        }
+
+        Err(decode_error(
+            format!(
+                "no conversion to Rust type `{}` from SQL type `{}` has been registered",
+                type_name::<T>(),
+                value.type_info().name()
+            )
+            .into(),
+        ))
+    }
+}
+
+impl<DB> Default for Conversions<DB>
+where
+    DB: Database,
+    usize: ColumnIndex<DB::Row>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<DB: Database> Debug for Conversions<DB> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Conversions")
+            .field("types", &self.converters.len())
+            .finish()
+    }
+}
@@ -0,0 +1,259 @@
+//! Propagate a caller-provided deadline to every query issued through an executor.
+
+use std::fmt::Debug;
+use std::future::Future;
+use std::ops::{Deref, DerefMut};
+use std::time::{Duration, Instant};
+
+use either::Either;
+use futures_core::future::BoxFuture;
+use futures_core::stream::BoxStream;
+use futures_util::TryStreamExt;
+
+use crate::database::{Database, HasStatement};
+use crate::describe::Describe;
+use crate::error::Error;
+use crate::executor::{Execute, Executor};
+use crate::pool::Pool;
+
+/// Wraps an executor so that every query executed through it must complete before a fixed
+/// point in time.
+///
+/// A deadline may be attached to a `&mut` connection, a [`Transaction`], a [`PoolConnection`]
+/// or a [`Pool`]. Queries are executed with `&mut deadline` (or `&deadline` for a pool).
+///
+/// Each query is given the time remaining until the deadline as its timeout. Once the
+/// deadline has passed, queries fail with [`Error::QueryTimedOut`] without being sent.
+///
+/// For a pool, the time spent waiting to acquire a connection counts against the deadline.
+///
+/// ```rust,ignore
+/// let mut tx = Deadline::after(pool.begin().await?, Duration::from_secs(2));
+///
+/// sqlx::query("UPDATE accounts SET balance = balance - 10 WHERE id = $1")
+///     .bind(from)
+///     .execute(&mut tx)
+///     .await?;
+///
+/// sqlx::query("UPDATE accounts SET balance = balance + 10 WHERE id = $1")
+///     .bind(to)
+///     .execute(&mut tx)
+///     .await?;
+///
+/// tx.into_inner().commit().await?;
+/// ```
+///
+/// Note that a timed out query is abandoned, not cancelled on the server. The connection
+/// will finish reading the abandoned response before running the next query.
+///
+/// [`Transaction`]: crate::transaction::Transaction
+/// [`PoolConnection`]: crate::pool::PoolConnection
+#[derive(Debug)]
+pub struct Deadline<C> {
+    inner: C,
+    deadline: Instant,
+}
+
+impl<C> Deadline<C> {
+    /// Attach `deadline` to `inner`.
+    pub fn new(inner: C, deadline: Instant) -> Self {
+        Self { inner, deadline }
+    }
+
+    /// Attach a deadline of `timeout` from now to `inner`.
+    pub fn after(inner: C, timeout: Duration) -> Self {
+        Self::new(inner, Instant::now() + timeout)
+    }
+
+    /// Returns the point in time that queries must complete by.
+    pub fn deadline(&self) -> Instant {
+        self.deadline
+    }
+
+    /// Returns the time remaining until the deadline, or `None` if it has passed.
+    pub fn remaining(&self) -> Option<Duration> {
+        self.deadline.checked_duration_since(Instant::now())
+    }
+
+    /// Unwrap the connection, transaction or pool, discarding the deadline.
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+}
+
+impl<C> Deref for Deadline<C> {
+    type Target = C;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<C> DerefMut for Deadline<C> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+impl<'c, C, DB> Executor<'c> for &'c mut Deadline<C>
+where
+    C: DerefMut + Send + Debug,
+    DB: Database,
+    for<'a> &'a mut C::Target: Executor<'a, Database = DB>,
+{
+    type Database = DB;
+
+    fn fetch_many<'e, 'q: 'e, E: 'q>(
+        self,
+        query: E,
+    ) -> BoxStream<'e, Result<Either<DB::Done, DB::Row>, Error>>
+    where
+        'c: 'e,
+        E: Execute<'q, Self::Database>,
+    {
+        stream_before(self.deadline, (&mut *self.inner).fetch_many(query))
+    }
+
+    fn fetch_optional<'e, 'q: 'e, E: 'q>(
+        self,
+        query: E,
+    ) -> BoxFuture<'e, Result<Option<DB::Row>, Error>>
+    where
+        'c: 'e,
+        E: Execute<'q, Self::Database>,
+    {
+        Box::pin(before(
+            self.deadline,
+            (&mut *self.inner).fetch_optional(query),
+        ))
+    }
+
+    fn prepare_with<'e, 'q: 'e>(
+        self,
+        sql: &'q str,
+        parameters: &'e [DB::TypeInfo],
+    ) -> BoxFuture<'e, Result<<DB as HasStatement<'q>>::Statement, Error>>
+    where
+        'c: 'e,
+    {
+        Box::pin(before(
+            self.deadline,
+            (&mut *self.inner).prepare_with(sql, parameters),
+        ))
+    }
+
+    #[doc(hidden)]
+    fn describe<'e, 'q: 'e>(self, sql: &'q str) -> BoxFuture<'e, Result<Describe<DB>, Error>>
+    where
+        'c: 'e,
+    {
+        Box::pin(before(self.deadline, (&mut *self.inner).describe(sql)))
+    }
+}
+
+impl<'p, DB: Database> Executor<'p> for &'_ Deadline<Pool<DB>>
+where
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+{
+    type Database = DB;
+
+    fn fetch_many<'e, 'q: 'e, E: 'q>(
+        self,
+        query: E,
+    ) -> BoxStream<'e, Result<Either<DB::Done, DB::Row>, Error>>
+    where
+        E: Execute<'q, Self::Database>,
+    {
+        stream_before(self.deadline, self.inner.fetch_many(query))
+    }
+
+    fn fetch_optional<'e, 'q: 'e, E: 'q>(
+        self,
+        query: E,
+    ) -> BoxFuture<'e, Result<Option<DB::Row>, Error>>
+    where
+        E: Execute<'q, Self::Database>,
+    {
+        Box::pin(before(self.deadline, self.inner.fetch_optional(query)))
+    }
+
+    fn prepare_with<'e, 'q: 'e>(
+        self,
+        sql: &'q str,
+        parameters: &'e [DB::TypeInfo],
+    ) -> BoxFuture<'e, Result<<DB as HasStatement<'q>>::Statement, Error>> {
+        Box::pin(before(
+            self.deadline,
+            self.inner.prepare_with(sql, parameters),
+        ))
+    }
+
+    #[doc(hidden)]
+    fn describe<'e, 'q: 'e>(self, sql: &'q str) -> BoxFuture<'e, Result<Describe<DB>, Error>> {
+        Box::pin(before(self.deadline, self.inner.describe(sql)))
+    }
+}
+
+// run `future` with the time remaining until `deadline` as its timeout
+async fn before<F, T>(deadline: Instant, future: F) -> Result<T, Error>
+where
+    F: Future<Output = Result<T, Error>>,
+{
+    let remaining = deadline
+        .checked_duration_since(Instant::now())
+        .ok_or(Error::QueryTimedOut)?;
+
+    sqlx_rt::timeout(remaining, future)
+        .await
+        .map_err(|_| Error::QueryTimedOut)?
+}
+
+fn stream_before<'e, T: 'e + Send>(
+    deadline: Instant,
+    mut stream: BoxStream<'e, Result<T, Error>>,
+) -> BoxStream<'e, Result<T, Error>> {
+    Box::pin(try_stream! {
+        while let Some(v) = before(deadline, stream.try_next()).await? {
+            r#yield!(v);
+        }
+
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::before;
+    use crate::error::Error;
+
+    async fn sleep_then(duration: Duration, value: i32) -> Result<i32, Error> {
+        sqlx_rt::sleep(duration).await;
+        Ok(value)
+    }
+
+    #[test]
+    fn it_times_out_past_the_deadline() {
+        let deadline = Instant::now() + Duration::from_millis(20);
+        let result = sqlx_rt::block_on(before(deadline, sleep_then(Duration::from_secs(5), 1)));
+
+        assert!(matches!(result, Err(Error::QueryTimedOut)));
+    }
+
+    #[test]
+    fn it_does_not_start_after_the_deadline() {
+        let deadline = Instant::now() - Duration::from_millis(1);
+        let result = sqlx_rt::block_on(before(deadline, sleep_then(Duration::from_millis(0), 1)));
+
+        assert!(matches!(result, Err(Error::QueryTimedOut)));
+    }
+
+    #[test]
+    fn it_completes_within_the_deadline() {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let result = sqlx_rt::block_on(before(deadline, sleep_then(Duration::from_millis(1), 1)));
+
+        assert!(matches!(result, Ok(1)));
+    }
+}
@@ -6,8 +6,10 @@ use std::error::Error as StdError;
 use std::fmt::Display;
 use std::io;
 use std::result::Result as StdResult;
+use std::sync::Arc;
 
 use crate::database::Database;
+use crate::sqlstate::{self, SqlStateClass};
 use crate::type_info::TypeInfo;
 use crate::types::Type;
 
@@ -85,6 +87,47 @@ pub enum Error {
     #[error("pool timed out while waiting for an open connection")]
     PoolTimedOut,
 
+    /// A query did not complete before the deadline attached to its executor.
+    ///
+    /// See [`Deadline`].
+    ///
+    /// [`Deadline`]: crate::deadline::Deadline
+    #[error("deadline elapsed before the query completed")]
+    QueryTimedOut,
+
+    /// A message from the server was larger than the maximum frame size set on the
+    /// connection options.
+    ///
+    /// The message was read in chunks, handed to the [`FrameSink`] of the connection if it has
+    /// one and discarded, without being held in memory. The connection remains usable.
+    ///
+    /// [`FrameSink`]: crate::frame::FrameSink
+    #[error(
+        "received a message of {size} bytes, larger than the maximum frame size of {max} bytes"
+    )]
+    FrameTooLarge { size: usize, max: usize },
+
+    /// The notification buffer of a `PgListener` overflowed and notifications were dropped.
+    ///
+    /// Returned by the receive following the dropped notifications, with their number.
+    #[error("the notification buffer of the listener is full ({dropped} notifications dropped)")]
+    NotificationBufferFull { dropped: u64 },
+
+    /// A query was run through a `PgListener` while its notification buffer was full.
+    ///
+    /// The query was not sent; receive notifications from the listener to make room.
+    #[error(
+        "the notification buffer of the listener is full ({buffered} notifications to receive)"
+    )]
+    NotificationBufferBlocked { buffered: usize },
+
+    /// A query run through a [`SingleFlight`] failed with the contained error, which is shared
+    /// by every caller the query was coalesced with.
+    ///
+    /// [`SingleFlight`]: crate::pool::SingleFlight
+    #[error("{0}")]
+    Coalesced(Arc<Error>),
+
     /// [`Pool::close`] was called while we were waiting in [`Pool::acquire`].
     ///
     /// [`Pool::acquire`]: crate::pool::Pool::acquire
@@ -114,6 +157,7 @@ impl Error {
     pub fn as_database_error(&self) -> Option<&(dyn DatabaseError + 'static)> {
         match self {
             Error::Database(err) => Some(&**err),
+            Error::Coalesced(err) => err.as_database_error(),
             _ => None,
         }
     }
@@ -163,6 +207,36 @@ pub trait DatabaseError: 'static + Send + Sync + StdError {
 }
 
 impl dyn DatabaseError {
+    /// The class of the [SQLSTATE][crate::sqlstate] code for the error.
+    ///
+    /// Returns `None` if the database did not report a code or the code is not a SQLSTATE
+    /// (e.g. the extended result codes reported by SQLite).
+    pub fn sqlstate_class(&self) -> Option<SqlStateClass> {
+        self.code().and_then(|code| SqlStateClass::from_code(&code))
+    }
+
+    /// Returns `true` if the error is a notice that the server is shutting down or has
+    /// terminated the session, e.g. during an administrative shutdown or a failover. The
+    /// connection that received it cannot be used again.
+    ///
+    /// Recognizes the PostgreSQL `admin_shutdown`, `crash_shutdown` and `cannot_connect_now`
+    /// codes and the MySQL server shutdown and connection killed errors.
+    pub fn is_server_shutdown(&self) -> bool {
+        #[cfg(feature = "mysql")]
+        {
+            if let Some(error) = self.try_downcast_ref::<crate::mysql::MySqlDatabaseError>() {
+                return error.is_server_shutdown();
+            }
+        }
+
+        matches!(
+            self.code().as_deref(),
+            Some(sqlstate::ADMIN_SHUTDOWN)
+                | Some(sqlstate::CRASH_SHUTDOWN)
+                | Some(sqlstate::CANNOT_CONNECT_NOW)
+        )
+    }
+
     /// Downcast a reference to this generic database error to a specific
     /// database error type.
     ///
@@ -6,6 +6,7 @@ use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
 use futures_util::{future, FutureExt, StreamExt, TryFutureExt, TryStreamExt};
 use std::fmt::Debug;
+use std::marker::PhantomData;
 
 /// A type that contains or can provide a database
 /// connection to use for executing queries against the database.
@@ -22,6 +23,20 @@ use std::fmt::Debug;
 ///  * [`&mut PoolConnection`](super::pool::PoolConnection)
 ///  * [`&mut Connection`](super::connection::Connection)
 ///
+/// # Cancellation
+///
+/// The futures and streams returned by an `Executor` may be dropped at any point, as when
+/// they lose a `select!` or hit a timeout. The connection they were using is left usable:
+/// what remains of the interrupted exchange with the server, whether a request that was only
+/// partly written or a response that was only partly read, is completed or discarded by the
+/// next operation on the connection before it starts.
+///
+/// The one exception is an operation dropped in the middle of a response that cannot be
+/// resumed, such as the response to a prepare on MySQL. The connection is then marked as
+/// [broken](crate::connection::Connection::is_broken): its next operations return an error,
+/// and a pool closes it instead of reusing it.
+///
+/// In both cases, the query may or may not have run on the server.
 pub trait Executor<'c>: Send + Debug + Sized {
     type Database: Database;
 
@@ -245,3 +260,43 @@ impl<'q, DB: Database> Execute<'q, DB> for (&'q str, Option<<DB as HasArguments<
         true
     }
 }
+
+// a query whose arguments were taken out, such as to capture them, and are handed back to the
+// executor along with its statement and persistence
+pub(crate) struct Captured<'q, DB: Database, E> {
+    query: E,
+    arguments: Option<<DB as HasArguments<'q>>::Arguments>,
+    database: PhantomData<DB>,
+}
+
+impl<'q, DB: Database, E> Captured<'q, DB, E> {
+    pub(crate) fn new(query: E, arguments: Option<<DB as HasArguments<'q>>::Arguments>) -> Self {
+        Self {
+            query,
+            arguments,
+            database: PhantomData,
+        }
+    }
+}
+
+impl<'q, DB, E> Execute<'q, DB> for Captured<'q, DB, E>
+where
+    DB: Database,
+    E: Execute<'q, DB>,
+{
+    fn sql(&self) -> &'q str {
+        self.query.sql()
+    }
+
+    fn statement(&self) -> Option<&<DB as HasStatement<'q>>::Statement> {
+        self.query.statement()
+    }
+
+    fn take_arguments(&mut self) -> Option<<DB as HasArguments<'q>>::Arguments> {
+        self.arguments.take()
+    }
+
+    fn persistent(&self) -> bool {
+        self.query.persistent()
+    }
+}
@@ -0,0 +1,196 @@
+//! Assertions on the plans the database chooses for queries.
+//!
+//! [`Explain::explain`] runs a query through `EXPLAIN` and returns its [`QueryPlan`], which
+//! describes the steps of the plan in the same terms for every database. Asserting on the plan
+//! of performance-critical queries in tests catches the schema or query changes that make them
+//! stop using an index:
+//!
+//! ```rust,ignore
+//! use sqlx::explain::Explain;
+//!
+//! let plan = conn
+//!     .explain(sqlx::query("SELECT * FROM users WHERE email = $1").bind("ann@example.com"))
+//!     .await?;
+//!
+//! plan.assert_uses_index("users_email_idx");
+//! plan.assert_no_full_scan("users");
+//! ```
+//!
+//! The query is planned but not executed. The plan depends on the data and the statistics of
+//! the database, so assertions are best made against a database populated like the one the
+//! query runs against; a table with only a few rows is often scanned regardless of its indexes.
+
+use std::fmt::{self, Display, Formatter};
+
+use futures_core::future::BoxFuture;
+
+use crate::connection::Connection;
+use crate::error::Error;
+use crate::executor::Execute;
+
+/// A connection that can return the plan of a query.
+///
+/// Implemented for PostgreSQL, MySQL and SQLite.
+pub trait Explain: Connection {
+    /// Return the plan the database would execute `query` with, without executing it.
+    ///
+    /// The arguments bound to the query are sent along, as the plan may depend on them.
+    fn explain<'e, 'q: 'e, E>(&'e mut self, query: E) -> BoxFuture<'e, Result<QueryPlan, Error>>
+    where
+        E: 'q + Execute<'q, Self::Database>;
+}
+
+/// How a step of a plan reads the rows of a table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Access {
+    /// Every row of the table is read: a sequential scan on PostgreSQL, a join type of `ALL`
+    /// or `index` on MySQL or a `SCAN` on SQLite, including one through a covering index.
+    FullScan,
+
+    /// Rows are looked up through an index.
+    Index,
+
+    /// Any other step, such as a join, a sort or an aggregate.
+    Other,
+}
+
+/// A step of a [`QueryPlan`].
+#[derive(Debug, Clone)]
+pub struct PlanStep {
+    pub(crate) access: Access,
+    pub(crate) table: Option<String>,
+    pub(crate) index: Option<String>,
+    pub(crate) detail: String,
+}
+
+impl PlanStep {
+    /// How the step reads its table.
+    pub fn access(&self) -> Access {
+        self.access
+    }
+
+    /// The table the step reads, if any, without its schema.
+    pub fn table(&self) -> Option<&str> {
+        self.table.as_deref()
+    }
+
+    /// The index the step reads, if any.
+    pub fn index(&self) -> Option<&str> {
+        self.index.as_deref()
+    }
+
+    /// The step as described by the database.
+    pub fn detail(&self) -> &str {
+        &self.detail
+    }
+}
+
+/// The plan of a query, as returned by [`Explain::explain`].
+///
+/// Names of tables and indexes are compared case-insensitively.
+#[derive(Debug, Clone)]
+pub struct QueryPlan {
+    steps: Vec<PlanStep>,
+}
+
+impl QueryPlan {
+    pub(crate) fn new(steps: Vec<PlanStep>) -> Self {
+        Self { steps }
+    }
+
+    /// The steps of the plan, in the order the database lists them.
+    pub fn steps(&self) -> &[PlanStep] {
+        &self.steps
+    }
+
+    /// Returns `true` if a step of the plan reads the index `index`.
+    pub fn uses_index(&self, index: &str) -> bool {
+        self.steps
+            .iter()
+            .any(|step| matches!(&step.index, Some(name) if name.eq_ignore_ascii_case(index)))
+    }
+
+    /// Returns `true` if a step of the plan reads every row of the table `table`.
+    pub fn scans_table(&self, table: &str) -> bool {
+        self.steps.iter().any(|step| {
+            step.access == Access::FullScan
+                && matches!(&step.table, Some(name) if name.eq_ignore_ascii_case(table))
+        })
+    }
+
+    /// Asserts that a step of the plan reads the index `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics with the plan if no step reads the index.
+    pub fn assert_uses_index(&self, index: &str) {
+        assert!(
+            self.uses_index(index),
+            "expected the plan to use the index {:?}:\n{}",
+            index,
+            self
+        );
+    }
+
+    /// Asserts that no step of the plan reads every row of the table `table`.
+    ///
+    /// # Panics
+    ///
+    /// Panics with the plan if a step scans the table.
+    pub fn assert_no_full_scan(&self, table: &str) {
+        assert!(
+            !self.scans_table(table),
+            "expected the plan not to scan the table {:?}:\n{}",
+            table,
+            self
+        );
+    }
+}
+
+impl Display for QueryPlan {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for step in &self.steps {
+            writeln!(f, "  {}", step.detail)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Access, PlanStep, QueryPlan};
+
+    fn step(access: Access, table: &str, index: Option<&str>) -> PlanStep {
+        PlanStep {
+            access,
+            table: Some(table.to_owned()),
+            index: index.map(ToOwned::to_owned),
+            detail: format!("{:?} {}", access, table),
+        }
+    }
+
+    #[test]
+    fn it_finds_indexes_and_full_scans() {
+        let plan = QueryPlan::new(vec![
+            step(Access::Index, "users", Some("users_email_idx")),
+            step(Access::FullScan, "Orders", None),
+        ]);
+
+        assert!(plan.uses_index("users_email_idx"));
+        assert!(plan.uses_index("USERS_EMAIL_IDX"));
+        assert!(!plan.uses_index("users_pkey"));
+
+        assert!(plan.scans_table("orders"));
+        assert!(!plan.scans_table("users"));
+
+        plan.assert_uses_index("users_email_idx");
+        plan.assert_no_full_scan("users");
+    }
+
+    #[test]
+    #[should_panic(expected = "expected the plan not to scan the table \"orders\"")]
+    fn it_panics_on_unexpected_full_scans() {
+        QueryPlan::new(vec![step(Access::FullScan, "orders", None)]).assert_no_full_scan("orders");
+    }
+}
@@ -0,0 +1,56 @@
+//! Stream the frames from the server that are too large to be buffered in memory.
+
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+
+/// Receives the frames from the server larger than the maximum frame size of a connection.
+///
+/// A frame larger than the `max_frame_size` set on the connect options, typically a row holding
+/// a huge value, is handed to the sink in chunks as it is read off the connection instead of
+/// being buffered in memory. The query then fails with [`Error::FrameTooLarge`] and the
+/// connection remains usable.
+///
+/// The chunks are the contents of the frame, without the header of the protocol: the body of
+/// a `DataRow` message on PostgreSQL and the payload of a packet on MySQL. They are written from
+/// the task that runs the query; a sink should hand them off rather than block.
+///
+/// ```rust,ignore
+/// let file = Mutex::new(File::create("value.bin")?);
+///
+/// let options = PgConnectOptions::new()
+///     .max_frame_size(16 * 1024 * 1024)
+///     .frame_sink(move |chunk: &[u8]| file.lock().unwrap().write_all(chunk).unwrap());
+/// ```
+///
+/// [`Error::FrameTooLarge`]: crate::error::Error::FrameTooLarge
+pub trait FrameSink: Send + Sync + 'static {
+    /// Called before the first chunk of a frame of `size` bytes.
+    fn begin(&self, size: usize) {
+        let _ = size;
+    }
+
+    /// Called with each chunk of the frame, in order.
+    fn write(&self, chunk: &[u8]);
+
+    /// Called once every chunk of the frame was written.
+    fn end(&self) {}
+}
+
+impl<F> FrameSink for F
+where
+    F: Fn(&[u8]) + Send + Sync + 'static,
+{
+    fn write(&self, chunk: &[u8]) {
+        self(chunk)
+    }
+}
+
+// shared by the connect options and the connections made from them
+#[derive(Clone)]
+pub(crate) struct SharedFrameSink(pub(crate) Arc<dyn FrameSink>);
+
+impl Debug for SharedFrameSink {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FrameSink").finish()
+    }
+}
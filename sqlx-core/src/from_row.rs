@@ -1,3 +1,4 @@
+use crate::column::Column;
 use crate::error::Error;
 use crate::row::Row;
 
@@ -66,6 +67,30 @@ use crate::row::Row;
 /// reason), `lowercase`, `UPPERCASE`, `camelCase`, `PascalCase`, `SCREAMING_SNAKE_CASE` and `kebab-case`.
 /// The styling of each option is intended to be an example of its behavior.
 ///
+/// #### `match_columns`
+/// By default, the name of each field must be equal to the name of its column as reported by the
+/// database, which may not be cased the same from one database to another. Placed at the struct
+/// level, this attribute changes how the names are compared:
+///
+/// ```rust,ignore
+/// #[derive(sqlx::FromRow)]
+/// #[sqlx(match_columns = "snake_case")]
+/// struct UserPost {
+///     // read from any of the columns "user_id", "UserId", "userID" or "USER_ID"
+///     user_id: i32,
+///     contents: String
+/// }
+/// ```
+///
+/// The supported values are `exact` (the default), `snake_case`, which compares the names once
+/// both are converted to snake case, and `case_insensitive`, which ignores the case of ASCII
+/// letters. A column whose name is equal to that of the field is always preferred; otherwise, the
+/// first column matching it is read. The columns are matched once for each set of column names
+/// a struct is read from, rather than for each row.
+///
+/// As `query_as!` does not use `FromRow`, it does not support this attribute: the names of its
+/// columns must be equal to those of the fields, which can be done by aliasing them with `AS`.
+///
 /// #### `default`
 ///
 /// When your struct contains a field that is not present in your query,
@@ -98,6 +123,119 @@ pub trait FromRow<'r, R: Row>: Sized {
     fn from_row(row: &'r R) -> Result<Self, Error>;
 }
 
+// how `#[derive(FromRow)]` compares the names of the columns to those of the fields
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy)]
+pub enum MatchColumns {
+    SnakeCase,
+    CaseInsensitive,
+}
+
+// the indexes of the columns read into the fields of a struct deriving `FromRow` with
+// `match_columns`, matched again only when the names of the columns change
+#[doc(hidden)]
+#[derive(Debug, Default)]
+pub struct ColumnCache {
+    columns: Vec<String>,
+    indexes: Vec<Option<usize>>,
+}
+
+impl ColumnCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // writes the index of the column read into each field of `fields` to `indexes`, or `None`
+    // if there is no such column
+    pub fn resolve<R: Row>(
+        &mut self,
+        row: &R,
+        fields: &[&str],
+        match_columns: MatchColumns,
+        indexes: &mut [Option<usize>],
+    ) {
+        let columns = row.columns();
+
+        let unchanged = self.columns.len() == columns.len()
+            && self
+                .columns
+                .iter()
+                .zip(columns)
+                .all(|(cached, column)| cached == column.name());
+
+        if !unchanged || self.indexes.len() != fields.len() {
+            self.columns = columns
+                .iter()
+                .map(|column| column.name().to_owned())
+                .collect();
+
+            self.indexes = fields
+                .iter()
+                .map(|field| find_column(&self.columns, field, match_columns))
+                .collect();
+        }
+
+        indexes.copy_from_slice(&self.indexes);
+    }
+}
+
+fn find_column(columns: &[String], name: &str, match_columns: MatchColumns) -> Option<usize> {
+    if let Some(index) = columns.iter().position(|column| column == name) {
+        return Some(index);
+    }
+
+    match match_columns {
+        MatchColumns::SnakeCase => {
+            let name = to_snake_case(name);
+
+            columns
+                .iter()
+                .position(|column| to_snake_case(column) == name)
+        }
+
+        MatchColumns::CaseInsensitive => columns
+            .iter()
+            .position(|column| column.eq_ignore_ascii_case(name)),
+    }
+}
+
+// split words on underscores, hyphens and spaces, and before an uppercase letter that follows a
+// lowercase letter or a digit or that starts a word after an acronym (`userID`, `HTTPServer`)
+fn to_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut snake = String::with_capacity(name.len() + 4);
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c == '_' || c == '-' || c == ' ' {
+            if !snake.is_empty() && !snake.ends_with('_') {
+                snake.push('_');
+            }
+
+            continue;
+        }
+
+        if c.is_uppercase() && !snake.is_empty() && !snake.ends_with('_') {
+            let prev = chars[i - 1];
+            let next = chars.get(i + 1).copied();
+
+            if prev.is_lowercase()
+                || prev.is_numeric()
+                || (prev.is_uppercase() && matches!(next, Some(next) if next.is_lowercase()))
+            {
+                snake.push('_');
+            }
+        }
+
+        snake.extend(c.to_lowercase());
+    }
+
+    if snake.ends_with('_') {
+        snake.pop();
+    }
+
+    snake
+}
+
 // implement FromRow for tuples of types that implement Decode
 // up to tuples of 9 values
 
@@ -300,3 +438,39 @@ impl_from_row_for_tuple!(
     (14) -> T15;
     (15) -> T16;
 );
+
+#[test]
+fn test_to_snake_case() {
+    assert_eq!(to_snake_case("user_id"), "user_id");
+    assert_eq!(to_snake_case("UserId"), "user_id");
+    assert_eq!(to_snake_case("userID"), "user_id");
+    assert_eq!(to_snake_case("USER_ID"), "user_id");
+    assert_eq!(to_snake_case("HTTPServer"), "http_server");
+    assert_eq!(to_snake_case("user-name "), "user_name");
+    assert_eq!(to_snake_case("address2Line"), "address2_line");
+}
+
+#[test]
+fn test_find_column() {
+    let columns = ["UserId".to_owned(), "user_id".to_owned(), "NAME".to_owned()];
+
+    // an equal name is preferred
+    assert_eq!(
+        find_column(&columns, "user_id", MatchColumns::SnakeCase),
+        Some(1)
+    );
+
+    assert_eq!(
+        find_column(&columns, "name", MatchColumns::CaseInsensitive),
+        Some(2)
+    );
+
+    assert_eq!(
+        find_column(&columns, "name", MatchColumns::SnakeCase),
+        Some(2)
+    );
+    assert_eq!(
+        find_column(&columns, "email", MatchColumns::SnakeCase),
+        None
+    );
+}
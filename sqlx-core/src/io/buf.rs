@@ -5,18 +5,123 @@ use memchr::memchr;
 
 use crate::error::Error;
 
+// generates a checked counterpart for each fixed-width read of `Buf`
+macro_rules! impl_try_get {
+    ($($name:ident => $get:ident: $ty:ty),* $(,)?) => {
+        $(
+            /// Read a fixed-width integer, returning an error if too few bytes remain.
+            fn $name(&mut self) -> Result<$ty, Error> {
+                self.ensure_remaining(std::mem::size_of::<$ty>())?;
+
+                Ok(self.$get())
+            }
+        )*
+    };
+}
+
+/// Checked reads from a buffer of protocol data.
+///
+/// Every method returns a protocol error instead of panicking when the buffer does not hold
+/// enough data. Decoders should use the `try_get_*` methods in place of the fixed-width reads
+/// of [`Buf`], such as `get_u16_le`, which panic on a truncated buffer.
 pub trait BufExt: Buf {
-    // Read a nul-terminated byte sequence
+    /// Return an error if fewer than `len` bytes remain in the buffer.
+    fn ensure_remaining(&self, len: usize) -> Result<(), Error> {
+        let remaining = self.remaining();
+
+        if remaining < len {
+            return Err(err_protocol!(
+                "expected {} more bytes in message but only {} remain",
+                len,
+                remaining
+            ));
+        }
+
+        Ok(())
+    }
+
+    impl_try_get! {
+        try_get_u8 => get_u8: u8,
+        try_get_i8 => get_i8: i8,
+        try_get_u16 => get_u16: u16,
+        try_get_u16_le => get_u16_le: u16,
+        try_get_i16 => get_i16: i16,
+        try_get_i16_le => get_i16_le: i16,
+        try_get_u32 => get_u32: u32,
+        try_get_u32_le => get_u32_le: u32,
+        try_get_i32 => get_i32: i32,
+        try_get_i32_le => get_i32_le: i32,
+        try_get_u64 => get_u64: u64,
+        try_get_u64_le => get_u64_le: u64,
+        try_get_i64 => get_i64: i64,
+        try_get_i64_le => get_i64_le: i64,
+    }
+
+    /// Checked [`Buf::get_uint`].
+    fn try_get_uint(&mut self, size: usize) -> Result<u64, Error> {
+        self.ensure_remaining(size)?;
+
+        Ok(self.get_uint(size))
+    }
+
+    /// Checked [`Buf::get_uint_le`].
+    fn try_get_uint_le(&mut self, size: usize) -> Result<u64, Error> {
+        self.ensure_remaining(size)?;
+
+        Ok(self.get_uint_le(size))
+    }
+
+    /// Read a nul-terminated byte sequence.
     fn get_bytes_nul(&mut self) -> Result<Bytes, Error>;
 
-    // Read a byte sequence of the exact length
-    fn get_bytes(&mut self, len: usize) -> Bytes;
+    /// Read a byte sequence of the exact length.
+    fn get_bytes(&mut self, len: usize) -> Result<Bytes, Error>;
 
-    // Read a nul-terminated string
+    /// Read a nul-terminated string.
     fn get_str_nul(&mut self) -> Result<String, Error>;
 
-    // Read a string of the exact length
+    /// Read a string of the exact length.
     fn get_str(&mut self, len: usize) -> Result<String, Error>;
+
+    /// Read a byte sequence prefixed with its length as a little-endian unsigned integer
+    /// of `size` bytes.
+    fn get_bytes_prefixed_le(&mut self, size: usize) -> Result<Bytes, Error> {
+        let len = self.try_get_uint_le(size)? as usize;
+        self.get_bytes(len)
+    }
+
+    /// Read a byte sequence prefixed with its length as a big-endian unsigned integer
+    /// of `size` bytes.
+    fn get_bytes_prefixed_be(&mut self, size: usize) -> Result<Bytes, Error> {
+        let len = self.try_get_uint(size)? as usize;
+        self.get_bytes(len)
+    }
+
+    // Read a length-encoded integer.
+    // NOTE: 0xfb or NULL is only returned for binary value encoding to indicate NULL.
+    // NOTE: 0xff is only returned during a result set to indicate ERR.
+    // <https://dev.mysql.com/doc/internals/en/integer.html#packet-Protocol::LengthEncodedInteger>
+    fn get_uint_lenenc(&mut self) -> Result<u64, Error> {
+        Ok(match self.try_get_u8()? {
+            0xfc => u64::from(self.try_get_u16_le()?),
+            0xfd => self.try_get_uint_le(3)?,
+            0xfe => self.try_get_u64_le()?,
+
+            v => u64::from(v),
+        })
+    }
+
+    // Read a length-encoded string.
+    fn get_str_lenenc(&mut self) -> Result<String, Error> {
+        let size = self.get_uint_lenenc()?;
+        self.get_str(size as usize)
+    }
+
+    // Read a length-encoded byte sequence.
+    fn get_bytes_lenenc(&mut self) -> Result<Bytes, Error> {
+        let size = self.get_uint_lenenc()?;
+        self.get_bytes(size as usize)
+    }
 }
 
 impl BufExt for Bytes {
@@ -31,11 +136,10 @@ impl BufExt for Bytes {
         Ok(v)
     }
 
-    fn get_bytes(&mut self, len: usize) -> Bytes {
-        let v = self.slice(..len);
-        self.advance(len);
+    fn get_bytes(&mut self, len: usize) -> Result<Bytes, Error> {
+        self.ensure_remaining(len)?;
 
-        v
+        Ok(self.split_to(len))
     }
 
     fn get_str_nul(&mut self) -> Result<String, Error> {
@@ -47,6 +151,8 @@ impl BufExt for Bytes {
     }
 
     fn get_str(&mut self, len: usize) -> Result<String, Error> {
+        self.ensure_remaining(len)?;
+
         let v = from_utf8(&self[..len])
             .map_err(|err| err_protocol!("{}", err))
             .map(ToOwned::to_owned)?;
@@ -56,3 +162,130 @@ impl BufExt for Bytes {
         Ok(v)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bytes::{Buf, Bytes};
+
+    use super::BufExt;
+
+    #[test]
+    fn it_reads_nul_terminated_strings() {
+        let mut buf = Bytes::from_static(b"hello\0world\0");
+
+        assert_eq!(buf.get_str_nul().unwrap(), "hello");
+        assert_eq!(buf.get_str_nul().unwrap(), "world");
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn it_errors_on_missing_nul() {
+        let mut buf = Bytes::from_static(b"hello");
+
+        assert!(buf.get_bytes_nul().is_err());
+        assert_eq!(&buf[..], b"hello");
+    }
+
+    #[test]
+    fn it_errors_on_truncated_bytes() {
+        let mut buf = Bytes::from_static(b"abc");
+
+        assert!(buf.get_bytes(4).is_err());
+        assert!(buf.get_str(4).is_err());
+        assert_eq!(&buf.get_bytes(3).unwrap()[..], b"abc");
+    }
+
+    #[test]
+    fn it_errors_on_invalid_utf8() {
+        let mut buf = Bytes::from_static(b"\xff\xfe\0");
+
+        assert!(buf.get_str_nul().is_err());
+    }
+
+    #[test]
+    fn it_reads_length_prefixed_bytes() {
+        let mut buf = Bytes::from_static(b"\x03\x00abc\x00\x00\x00\x02de");
+
+        assert_eq!(&buf.get_bytes_prefixed_le(2).unwrap()[..], b"abc");
+        assert_eq!(&buf.get_bytes_prefixed_be(4).unwrap()[..], b"de");
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn it_reads_fixed_width_integers() {
+        let mut buf = Bytes::from_static(b"\x01\x02\x00\x00\x03\x04\x00\x00\x00\xff\xff\xff");
+
+        assert_eq!(buf.try_get_u8().unwrap(), 1);
+        assert_eq!(buf.try_get_u16_le().unwrap(), 2);
+        assert_eq!(buf.try_get_u16().unwrap(), 3);
+        assert_eq!(buf.try_get_u32_le().unwrap(), 4);
+        assert_eq!(buf.try_get_uint_le(3).unwrap(), 0xFF_FF_FF);
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn it_errors_on_truncated_fixed_width_integers() {
+        let mut buf = Bytes::from_static(b"\x01\x02\x03");
+
+        assert!(buf.try_get_u32().is_err());
+        assert!(buf.try_get_i32_le().is_err());
+        assert!(buf.try_get_u64_le().is_err());
+        assert!(buf.try_get_uint_le(4).is_err());
+        assert_eq!(buf.remaining(), 3);
+
+        assert_eq!(buf.try_get_u16().unwrap(), 0x0102);
+        assert!(buf.try_get_i16_le().is_err());
+        assert_eq!(buf.try_get_i8().unwrap(), 3);
+        assert!(buf.try_get_u8().is_err());
+    }
+
+    #[test]
+    fn it_errors_on_truncated_length_prefix() {
+        assert!(Bytes::from_static(b"\x03")
+            .get_bytes_prefixed_le(2)
+            .is_err());
+        assert!(Bytes::from_static(b"\x05abc")
+            .get_bytes_prefixed_le(1)
+            .is_err());
+    }
+
+    #[test]
+    fn it_reads_lenenc_integers() {
+        let mut buf = Bytes::from_static(
+            b"\xFA\xFC\xFF\xFF\xFD\xFF\xFF\xFF\xFE\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF",
+        );
+
+        assert_eq!(buf.get_uint_lenenc().unwrap(), 0xFA);
+        assert_eq!(buf.get_uint_lenenc().unwrap(), 0xFF_FF);
+        assert_eq!(buf.get_uint_lenenc().unwrap(), 0xFF_FF_FF);
+        assert_eq!(buf.get_uint_lenenc().unwrap(), u64::MAX);
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn it_errors_on_truncated_lenenc_integers() {
+        assert!(Bytes::new().get_uint_lenenc().is_err());
+        assert!(Bytes::from_static(b"\xFC\xFF").get_uint_lenenc().is_err());
+        assert!(Bytes::from_static(b"\xFD\xFF\xFF")
+            .get_uint_lenenc()
+            .is_err());
+        assert!(Bytes::from_static(b"\xFE\xFF\xFF\xFF")
+            .get_uint_lenenc()
+            .is_err());
+    }
+
+    #[test]
+    fn it_reads_lenenc_strings() {
+        let mut buf = Bytes::from_static(b"\x0Drandom_string");
+
+        assert_eq!(buf.get_str_lenenc().unwrap(), "random_string");
+    }
+
+    #[test]
+    fn it_errors_on_truncated_lenenc_strings() {
+        assert!(Bytes::from_static(b"\x0Drandom").get_str_lenenc().is_err());
+        assert!(Bytes::from_static(b"\x0Drandom")
+            .get_bytes_lenenc()
+            .is_err());
+    }
+}
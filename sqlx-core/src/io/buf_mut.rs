@@ -1,7 +1,44 @@
 use bytes::BufMut;
 
+use crate::error::Error;
+
 pub trait BufMutExt: BufMut {
+    /// Write a nul-terminated string.
     fn put_str_nul(&mut self, s: &str);
+
+    /// Write a byte sequence prefixed with its length as a little-endian unsigned integer
+    /// of `size` bytes.
+    ///
+    /// Returns an error, without writing anything, if the length does not fit in `size` bytes.
+    fn put_bytes_prefixed_le(&mut self, size: usize, v: &[u8]) -> Result<(), Error>;
+
+    /// Write a byte sequence prefixed with its length as a big-endian unsigned integer
+    /// of `size` bytes.
+    ///
+    /// Returns an error, without writing anything, if the length does not fit in `size` bytes.
+    fn put_bytes_prefixed_be(&mut self, size: usize, v: &[u8]) -> Result<(), Error>;
+
+    // Write a length-encoded integer.
+    // <https://dev.mysql.com/doc/internals/en/integer.html#packet-Protocol::LengthEncodedInteger>
+    fn put_uint_lenenc(&mut self, v: u64);
+
+    // Write a length-encoded string.
+    fn put_str_lenenc(&mut self, v: &str);
+
+    // Write a length-encoded byte sequence.
+    fn put_bytes_lenenc(&mut self, v: &[u8]);
+}
+
+fn check_prefix_size(size: usize, len: usize) -> Result<(), Error> {
+    if size == 0 || size > 8 || (size < 8 && (len as u64) >> (size * 8) != 0) {
+        return Err(err_protocol!(
+            "byte sequence of length {} does not fit a {}-byte length prefix",
+            len,
+            size
+        ));
+    }
+
+    Ok(())
 }
 
 impl BufMutExt for Vec<u8> {
@@ -9,4 +46,154 @@ impl BufMutExt for Vec<u8> {
         self.extend(s.as_bytes());
         self.push(0);
     }
+
+    fn put_bytes_prefixed_le(&mut self, size: usize, v: &[u8]) -> Result<(), Error> {
+        check_prefix_size(size, v.len())?;
+
+        self.put_uint_le(v.len() as u64, size);
+        self.extend(v);
+
+        Ok(())
+    }
+
+    fn put_bytes_prefixed_be(&mut self, size: usize, v: &[u8]) -> Result<(), Error> {
+        check_prefix_size(size, v.len())?;
+
+        self.put_uint(v.len() as u64, size);
+        self.extend(v);
+
+        Ok(())
+    }
+
+    fn put_uint_lenenc(&mut self, v: u64) {
+        // https://dev.mysql.com/doc/internals/en/integer.html
+        // https://mariadb.com/kb/en/library/protocol-data-types/#length-encoded-integers
+
+        if v < 251 {
+            self.push(v as u8);
+        } else if v < 0x1_00_00 {
+            self.push(0xfc);
+            self.extend(&(v as u16).to_le_bytes());
+        } else if v < 0x1_00_00_00 {
+            self.push(0xfd);
+            self.extend(&(v as u32).to_le_bytes()[..3]);
+        } else {
+            self.push(0xfe);
+            self.extend(&v.to_le_bytes());
+        }
+    }
+
+    fn put_str_lenenc(&mut self, v: &str) {
+        self.put_bytes_lenenc(v.as_bytes());
+    }
+
+    fn put_bytes_lenenc(&mut self, v: &[u8]) {
+        self.put_uint_lenenc(v.len() as u64);
+        self.extend(v);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use super::BufMutExt;
+    use crate::io::BufExt;
+
+    // every prefix of an encoded frame, short of the whole frame, must fail to decode
+    fn assert_truncations_fail<T>(
+        frame: &[u8],
+        decode: impl Fn(&mut Bytes) -> Result<T, crate::error::Error>,
+    ) {
+        for len in 0..frame.len() {
+            let mut buf = Bytes::copy_from_slice(&frame[..len]);
+
+            assert!(
+                decode(&mut buf).is_err(),
+                "decoded a frame truncated to {} bytes",
+                len
+            );
+        }
+    }
+
+    #[test]
+    fn it_writes_nul_terminated_strings() {
+        let mut buf = Vec::new();
+        buf.put_str_nul("hello");
+
+        assert_eq!(&buf[..], b"hello\0");
+        assert_truncations_fail(&buf, |buf| buf.get_str_nul());
+    }
+
+    #[test]
+    fn it_writes_length_prefixed_bytes_le() {
+        let mut buf = Vec::new();
+        buf.put_bytes_prefixed_le(2, b"abc").unwrap();
+
+        assert_eq!(&buf[..], b"\x03\x00abc");
+        assert_truncations_fail(&buf, |buf| buf.get_bytes_prefixed_le(2));
+    }
+
+    #[test]
+    fn it_writes_length_prefixed_bytes_be() {
+        let mut buf = Vec::new();
+        buf.put_bytes_prefixed_be(4, b"de").unwrap();
+
+        assert_eq!(&buf[..], b"\x00\x00\x00\x02de");
+        assert_truncations_fail(&buf, |buf| buf.get_bytes_prefixed_be(4));
+    }
+
+    #[test]
+    fn it_rejects_lengths_that_overflow_the_prefix() {
+        let mut buf = Vec::new();
+
+        assert!(buf.put_bytes_prefixed_le(1, &[0; 256]).is_err());
+        assert!(buf.put_bytes_prefixed_be(0, b"").is_err());
+        assert!(buf.is_empty());
+
+        buf.put_bytes_prefixed_be(1, &[0; 255]).unwrap();
+        assert_eq!(buf.len(), 256);
+    }
+
+    #[test]
+    fn it_writes_lenenc_integers() {
+        let cases: &[(u64, &[u8])] = &[
+            (0xFA, b"\xFA"),
+            (0xFB, b"\xFC\xFB\x00"),
+            (0xFC, b"\xFC\xFC\x00"),
+            (0xFD, b"\xFC\xFD\x00"),
+            (0xFE, b"\xFC\xFE\x00"),
+            (0xFF, b"\xFC\xFF\x00"),
+            (u64::from(u16::MAX), b"\xFC\xFF\xFF"),
+            (0xFF_FF_FF, b"\xFD\xFF\xFF\xFF"),
+            (u64::MAX, b"\xFE\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF"),
+        ];
+
+        for (v, expected) in cases {
+            let mut buf = Vec::new();
+            buf.put_uint_lenenc(*v);
+
+            assert_eq!(&buf[..], *expected);
+            assert_eq!(Bytes::from(buf.clone()).get_uint_lenenc().unwrap(), *v);
+            assert_truncations_fail(&buf, |buf| buf.get_uint_lenenc());
+        }
+    }
+
+    #[test]
+    fn it_writes_lenenc_strings() {
+        let mut buf = Vec::new();
+        buf.put_str_lenenc("random_string");
+
+        assert_eq!(&buf[..], b"\x0Drandom_string");
+        assert_truncations_fail(&buf, |buf| buf.get_str_lenenc());
+    }
+
+    #[test]
+    fn it_writes_lenenc_bytes() {
+        let mut buf = Vec::new();
+        buf.put_bytes_lenenc(b"random_string");
+
+        assert_eq!(&buf[..], b"\x0Drandom_string");
+        assert_truncations_fail(&buf, |buf| buf.get_bytes_lenenc());
+    }
 }
@@ -3,13 +3,15 @@
 use std::io;
 use std::ops::{Deref, DerefMut};
 
-use bytes::BytesMut;
+use bytes::{Buf, BytesMut};
 use sqlx_rt::{AsyncRead, AsyncReadExt, AsyncWrite};
 
 use crate::error::Error;
+use crate::frame::FrameSink;
 use crate::io::write_and_flush::WriteAndFlush;
 use crate::io::{decode::Decode, encode::Encode};
 use std::io::Cursor;
+use std::sync::Arc;
 
 pub struct BufStream<S>
 where
@@ -23,6 +25,12 @@ where
 
     // we read into the read buffer using 100% safe code
     rbuf: BytesMut,
+
+    // bytes still to be discarded by a `skip` that was interrupted
+    pending_skip: usize,
+
+    // receives the bytes discarded by `skip_frame`, if any
+    skip_sink: Option<Arc<dyn FrameSink>>,
 }
 
 impl<S> BufStream<S>
@@ -34,6 +42,8 @@ where
             stream,
             wbuf: Vec::with_capacity(512),
             rbuf: BytesMut::with_capacity(4096),
+            pending_skip: 0,
+            skip_sink: None,
         }
     }
 
@@ -55,6 +65,7 @@ where
         WriteAndFlush {
             stream: &mut self.stream,
             buf: Cursor::new(&mut self.wbuf),
+            finished: false,
         }
     }
 
@@ -73,14 +84,86 @@ where
     }
 
     pub async fn read_raw(&mut self, cnt: usize) -> Result<BytesMut, Error> {
-        read_raw_into(&mut self.stream, &mut self.rbuf, cnt).await?;
-        let buf = self.rbuf.split_to(cnt);
+        self.fill(cnt).await?;
+
+        Ok(self.rbuf.split_to(cnt))
+    }
+
+    // returns the next `cnt` bytes of the stream without consuming them
+    pub async fn peek(&mut self, cnt: usize) -> Result<&[u8], Error> {
+        self.fill(cnt).await?;
+
+        Ok(&self.rbuf[..cnt])
+    }
+
+    pub fn rbuf_capacity(&self) -> usize {
+        self.rbuf.capacity()
+    }
+
+    // read and discard `cnt` bytes, holding no more than a small chunk in memory at a time
+    pub async fn skip(&mut self, cnt: usize) -> Result<(), Error> {
+        self.pending_skip += cnt;
+        self.finish_skip().await
+    }
+
+    // discard a frame of `size` bytes after a `header` that was already peeked, handing the
+    // frame to `sink` in small chunks; the header is consumed right away so that a cancelled
+    // skip resumes within the frame
+    pub async fn skip_frame(
+        &mut self,
+        header: usize,
+        size: usize,
+        sink: Option<Arc<dyn FrameSink>>,
+    ) -> Result<(), Error> {
+        debug_assert!(self.pending_skip == 0 && self.rbuf.len() >= header);
+
+        self.rbuf.advance(header);
+        self.pending_skip = size;
+
+        if let Some(sink) = sink {
+            sink.begin(size);
+            self.skip_sink = Some(sink);
+        }
 
-        Ok(buf)
+        self.finish_skip().await
     }
 
-    pub async fn read_raw_into(&mut self, buf: &mut BytesMut, cnt: usize) -> Result<(), Error> {
-        read_raw_into(&mut self.stream, buf, cnt).await
+    // reads until the read buffer holds at least `cnt` bytes
+    //
+    // the reads of this, and so of every other read method, are kept in the read buffer as
+    // they complete; if the future is dropped, no byte of the stream is lost and the next read
+    // resumes where this one stopped
+    async fn fill(&mut self, cnt: usize) -> Result<(), Error> {
+        self.finish_skip().await?;
+
+        fill(&mut self.stream, &mut self.rbuf, cnt).await
+    }
+
+    async fn finish_skip(&mut self) -> Result<(), Error> {
+        const CHUNK_SIZE: usize = 8 * 1024;
+
+        while self.pending_skip > 0 {
+            if self.rbuf.is_empty() {
+                let n = self.pending_skip.min(CHUNK_SIZE);
+
+                fill(&mut self.stream, &mut self.rbuf, n).await?;
+            }
+
+            let n = self.rbuf.len().min(self.pending_skip);
+
+            if let Some(sink) = &self.skip_sink {
+                sink.write(&self.rbuf[..n]);
+            }
+
+            self.rbuf.advance(n);
+            self.pending_skip -= n;
+        }
+
+        if let Some(sink) = self.skip_sink.take() {
+            sink.end();
+        }
+
+        Ok(())
     }
 }
 
@@ -104,21 +187,27 @@ where
     }
 }
 
-async fn read_raw_into<S: AsyncRead + Unpin>(
+async fn fill<S: AsyncRead + Unpin>(
     stream: &mut S,
     buf: &mut BytesMut,
     cnt: usize,
 ) -> Result<(), Error> {
-    let offset = buf.len();
+    if buf.len() >= cnt {
+        return Ok(());
+    }
+
+    let mut filled = Filled {
+        len: buf.len(),
+        buf,
+    };
 
     // zero-fills the space in the read buffer
-    buf.resize(offset + cnt, 0);
+    filled.buf.resize(cnt, 0);
 
-    let mut read = offset;
-    while (offset + cnt) > read {
+    while cnt > filled.len {
         // read in bytes from the stream into the read buffer starting
         // from the offset we last read from
-        let n = stream.read(&mut buf[read..]).await?;
+        let n = stream.read(&mut filled.buf[filled.len..]).await?;
 
         if n == 0 {
             // a zero read when we had space in the read buffer
@@ -129,8 +218,162 @@ async fn read_raw_into<S: AsyncRead + Unpin>(
             return Err(io::Error::from(io::ErrorKind::ConnectionAborted).into());
         }
 
-        read += n;
+        filled.len += n;
     }
 
     Ok(())
 }
+
+// trims the zero-filled space that was not read into from the read buffer, whether the read
+// completed, failed or was cancelled
+struct Filled<'a> {
+    buf: &'a mut BytesMut,
+    len: usize,
+}
+
+impl Drop for Filled<'_> {
+    fn drop(&mut self) {
+        self.buf.truncate(self.len);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::Duration;
+
+    use sqlx_rt::{timeout, TcpStream};
+
+    use super::BufStream;
+    use crate::error::Error;
+    use crate::frame::FrameSink;
+
+    // connects a buffered stream to a blocking socket standing in for the server
+    async fn connect() -> Result<(BufStream<TcpStream>, std::net::TcpStream), Error> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let stream = TcpStream::connect(listener.local_addr()?).await?;
+        let (server, _) = listener.accept()?;
+
+        Ok((BufStream::new(stream), server))
+    }
+
+    #[test]
+    fn it_resumes_a_cancelled_read() -> Result<(), Error> {
+        sqlx_rt::block_on(async {
+            let (mut stream, mut server) = connect().await?;
+
+            server.write_all(b"hello")?;
+
+            // only half of the bytes are there when the read is dropped
+            assert!(timeout(Duration::from_millis(100), stream.read_raw(10))
+                .await
+                .is_err());
+
+            server.write_all(b"world")?;
+
+            assert_eq!(stream.peek(5).await?, b"hello");
+            assert_eq!(&stream.read_raw(10).await?[..], b"helloworld");
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn it_resumes_a_cancelled_skip() -> Result<(), Error> {
+        sqlx_rt::block_on(async {
+            let (mut stream, mut server) = connect().await?;
+
+            server.write_all(&[0; 5])?;
+
+            assert!(timeout(Duration::from_millis(100), stream.skip(10))
+                .await
+                .is_err());
+
+            server.write_all(&[0; 5])?;
+            server.write_all(b"next")?;
+
+            assert_eq!(&stream.read_raw(4).await?[..], b"next");
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn it_streams_a_skipped_frame_to_the_sink() -> Result<(), Error> {
+        struct Collect(Mutex<(usize, Vec<u8>, bool)>);
+
+        impl FrameSink for Collect {
+            fn begin(&self, size: usize) {
+                self.0.lock().unwrap().0 = size;
+            }
+
+            fn write(&self, chunk: &[u8]) {
+                self.0.lock().unwrap().1.extend_from_slice(chunk);
+            }
+
+            fn end(&self) {
+                self.0.lock().unwrap().2 = true;
+            }
+        }
+
+        sqlx_rt::block_on(async {
+            let (mut stream, mut server) = connect().await?;
+            let sink = Arc::new(Collect(Mutex::new((0, Vec::new(), false))));
+
+            server.write_all(b"HDRhello")?;
+            stream.peek(3).await?;
+
+            // the frame is still streamed to the sink once the cancelled skip resumes
+            assert!(timeout(
+                Duration::from_millis(100),
+                stream.skip_frame(3, 10, Some(sink.clone()))
+            )
+            .await
+            .is_err());
+
+            assert!(!sink.0.lock().unwrap().2);
+
+            server.write_all(b"worldnext")?;
+
+            assert_eq!(&stream.read_raw(4).await?[..], b"next");
+            assert_eq!(*sink.0.lock().unwrap(), (10, b"helloworld".to_vec(), true));
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn it_keeps_the_rest_of_a_cancelled_write() -> Result<(), Error> {
+        // more than the socket buffers hold while the server is not reading
+        const LEN: usize = 32 * 1024 * 1024;
+
+        sqlx_rt::block_on(async {
+            let (mut stream, mut server) = connect().await?;
+
+            let message: Vec<u8> = (0..LEN).map(|i| i as u8).collect();
+            stream.wbuf.extend_from_slice(&message);
+
+            assert!(timeout(Duration::from_millis(100), stream.flush())
+                .await
+                .is_err());
+
+            assert!(!stream.wbuf.is_empty());
+            assert!(stream.wbuf.len() < LEN);
+
+            let reader = thread::spawn(move || {
+                let mut received = vec![0; LEN];
+                server.read_exact(&mut received).map(|_| received)
+            });
+
+            stream.flush().await?;
+
+            assert!(stream.wbuf.is_empty());
+            assert!(reader.join().unwrap()? == message);
+
+            Ok(())
+        })
+    }
+}
@@ -1,3 +1,12 @@
+//! Checked helpers for reading and writing the byte buffers of wire protocols.
+//!
+//! These are shared by the database drivers for the framing common to their protocols:
+//! fixed-width integers, length-encoded integers, nul-terminated strings, length-prefixed byte
+//! sequences and fixed-length strings. Reads return
+//! [`Error::Protocol`][crate::error::Error::Protocol] on truncated or malformed input instead of
+//! panicking. Decoders read fixed-width fields with the `try_get_*` methods of [`BufExt`] rather
+//! than the panicking reads of [`bytes::Buf`].
+
 mod buf;
 mod buf_mut;
 mod buf_stream;
@@ -7,6 +16,6 @@ mod write_and_flush;
 
 pub use buf::BufExt;
 pub use buf_mut::BufMutExt;
-pub use buf_stream::BufStream;
-pub use decode::Decode;
-pub use encode::Encode;
+pub(crate) use buf_stream::BufStream;
+pub(crate) use decode::Decode;
+pub(crate) use encode::Encode;
@@ -8,18 +8,37 @@ use std::task::{Context, Poll};
 
 // Atomic operation that writes the full buffer to the stream, flushes the stream, and then
 // clears the buffer (even if either of the two previous operations failed).
+//
+// If the future is dropped before it completes, only the bytes that were written are removed
+// from the buffer; the rest is written by the next flush, so that the server never receives
+// part of a message followed by the start of another.
 pub struct WriteAndFlush<'a, S> {
     pub(super) stream: &'a mut S,
     pub(super) buf: Cursor<&'a mut Vec<u8>>,
+    pub(super) finished: bool,
 }
 
 impl<S: AsyncWrite + Unpin> Future for WriteAndFlush<'_, S> {
     type Output = Result<(), Error>;
 
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let result = ready!(self.as_mut().poll_write_and_flush(cx));
+
+        self.finished = true;
+
+        Poll::Ready(result)
+    }
+}
+
+impl<S: AsyncWrite + Unpin> WriteAndFlush<'_, S> {
+    fn poll_write_and_flush(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Error>> {
         let Self {
             ref mut stream,
             ref mut buf,
+            ..
         } = *self;
 
         loop {
@@ -39,7 +58,13 @@ impl<S: AsyncWrite + Unpin> Future for WriteAndFlush<'_, S> {
 
 impl<'a, S> Drop for WriteAndFlush<'a, S> {
     fn drop(&mut self) {
-        // clear the buffer regardless of whether the flush succeeded or not
-        self.buf.get_mut().clear();
+        if self.finished {
+            // clear the buffer regardless of whether the flush succeeded or not
+            self.buf.get_mut().clear();
+        } else {
+            // keep what is left to write of an interrupted flush
+            let written = self.buf.position() as usize;
+            self.buf.get_mut().drain(..written);
+        }
     }
 }
@@ -30,6 +30,7 @@ pub mod arguments;
 pub mod pool;
 
 pub mod connection;
+pub mod convert;
 
 #[macro_use]
 pub mod transaction;
@@ -55,19 +56,36 @@ pub mod column;
 #[macro_use]
 pub mod statement;
 
+pub mod arena;
+pub mod audit;
+pub mod bulk_update;
 mod common;
 pub mod database;
+pub mod deadline;
 pub mod describe;
 pub mod done;
 pub mod executor;
+pub mod explain;
+pub mod frame;
 pub mod from_row;
-mod io;
+pub mod io;
 mod logger;
 mod net;
+pub mod offload;
 pub mod query_as;
+pub mod query_builder;
+#[doc(hidden)]
+pub mod query_data;
 pub mod query_scalar;
+pub mod returning;
 pub mod row;
+pub mod script;
+pub mod snapshot;
+pub mod sqlstate;
+pub mod testing;
+pub mod two_phase;
 pub mod type_info;
+pub mod type_overrides;
 pub mod value;
 
 #[cfg(feature = "migrate")]
@@ -1,3 +1,5 @@
+use std::fmt::{self, Write};
+
 use crate::arguments::Arguments;
 use crate::encode::Encode;
 use crate::mssql::database::Mssql;
@@ -115,4 +117,8 @@ impl<'q> Arguments<'q> for MssqlArguments {
     {
         self.add(value)
     }
+
+    fn format_placeholder<W: Write>(&self, writer: &mut W) -> fmt::Result {
+        write!(writer, "@p{}", self.ordinal)
+    }
 }
@@ -82,6 +82,8 @@ impl MssqlConnection {
         Ok(Self {
             stream,
             cache_statement: StatementCache::new(1024),
+            last_sql: String::new(),
+            type_overrides: None,
             log_settings: options.log_settings.clone(),
         })
     }
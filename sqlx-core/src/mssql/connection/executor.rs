@@ -80,6 +80,9 @@ impl<'c> Executor<'c> for &'c mut MssqlConnection {
         let arguments = query.take_arguments();
         let mut logger = QueryLogger::new(sql, self.log_settings.clone());
 
+        self.last_sql.clear();
+        self.last_sql.push_str(sql);
+
         Box::pin(try_stream! {
             self.run(sql, arguments).await?;
 
@@ -90,10 +93,11 @@ impl<'c> Executor<'c> for &'c mut MssqlConnection {
                     Message::Row(row) => {
                         let columns = Arc::clone(&self.stream.columns);
                         let column_names = Arc::clone(&self.stream.column_names);
+                        let type_overrides = self.type_overrides.clone();
 
                         logger.increment_rows();
 
-                        r#yield!(Either::Right(MssqlRow { row, column_names, columns }));
+                        r#yield!(Either::Right(MssqlRow { row, column_names, columns, type_overrides }));
                     }
 
                     Message::Done(done) | Message::DoneProc(done) => {
@@ -1,11 +1,12 @@
 use crate::common::StatementCache;
-use crate::connection::{Connection, LogSettings};
+use crate::connection::{Connection, ConnectionState, LogSettings};
 use crate::error::Error;
 use crate::executor::Executor;
 use crate::mssql::connection::stream::MssqlStream;
 use crate::mssql::statement::MssqlStatementMetadata;
 use crate::mssql::{Mssql, MssqlConnectOptions};
 use crate::transaction::Transaction;
+use crate::type_overrides::TypeOverrides;
 use futures_core::future::BoxFuture;
 use futures_util::{future::ready, FutureExt, TryFutureExt};
 use std::fmt::{self, Debug, Formatter};
@@ -20,6 +21,10 @@ mod stream;
 pub struct MssqlConnection {
     pub(crate) stream: MssqlStream,
     pub(crate) cache_statement: StatementCache<Arc<MssqlStatementMetadata>>,
+    // most recently executed query, kept for diagnostics
+    pub(crate) last_sql: String,
+    // shared with the rows returned by this connection
+    type_overrides: Option<Arc<TypeOverrides<Mssql>>>,
     log_settings: LogSettings,
 }
 
@@ -44,6 +49,28 @@ impl Connection for MssqlConnection {
         self.execute("/* SQLx ping */").map_ok(|_| ()).boxed()
     }
 
+    fn reset(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move {
+            self.stream.wait_until_ready().await?;
+
+            // the batch carrying the reset must not refer to a transaction the reset ends
+            if self.stream.transaction_descriptor != 0 {
+                self.execute("IF @@TRANCOUNT > 0 ROLLBACK TRAN").await?;
+            }
+
+            // RESETCONNECTION is set on the packet of this batch; the server resets the session
+            // as if by `sp_reset_connection` before running it
+            self.stream.reset_connection = true;
+            self.execute("/* SQLx reset */").await?;
+
+            // the server unprepared every statement
+            self.cache_statement.clear();
+            self.stream.transaction_depth = 0;
+
+            Ok(())
+        })
+    }
+
     fn begin(&mut self) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
     where
         Self: Sized,
@@ -51,6 +78,21 @@ impl Connection for MssqlConnection {
         Transaction::begin(self)
     }
 
+    fn set_type_overrides(&mut self, overrides: TypeOverrides<Mssql>) {
+        self.type_overrides = overrides.into_shared();
+    }
+
+    fn dump_state(&self) -> ConnectionState {
+        ConnectionState {
+            transaction_depth: self.stream.transaction_depth,
+            cached_statements: self.cache_statement.len(),
+            read_buffer_capacity: self.stream.rbuf_capacity(),
+            write_buffer_capacity: self.stream.wbuf.capacity(),
+            last_sql: Some(self.last_sql.clone()).filter(|sql| !sql.is_empty()),
+            ..ConnectionState::default()
+        }
+    }
+
     #[doc(hidden)]
     fn flush(&mut self) -> BoxFuture<'_, Result<(), Error>> {
         self.stream.wait_until_ready().boxed()
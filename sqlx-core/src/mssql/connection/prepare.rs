@@ -88,6 +88,7 @@ pub(crate) async fn prepare(
                 id = <i32 as Decode<Mssql>>::decode(MssqlValueRef {
                     data: rv.value.as_ref(),
                     type_info: MssqlTypeInfo(rv.type_info),
+                    type_overrides: None,
                 })
                 .ok();
             }
@@ -1,3 +1,4 @@
+use std::mem;
 use std::ops::{Deref, DerefMut};
 
 use bytes::{Bytes, BytesMut};
@@ -5,7 +6,7 @@ use sqlx_rt::TcpStream;
 
 use crate::error::Error;
 use crate::ext::ustr::UStr;
-use crate::io::{BufStream, Encode};
+use crate::io::{BufStream, Decode, Encode};
 use crate::mssql::protocol::col_meta_data::ColMetaData;
 use crate::mssql::protocol::done::{Done, Status as DoneStatus};
 use crate::mssql::protocol::env_change::EnvChange;
@@ -34,6 +35,9 @@ pub(crate) struct MssqlStream {
     pub(crate) transaction_descriptor: u64,
     pub(crate) transaction_depth: usize,
 
+    // set by `Connection::reset`; the server resets the session before it runs the next request
+    pub(crate) reset_connection: bool,
+
     // current TabularResult from the server that we are iterating over
     response: Option<(PacketHeader, Bytes)>,
 
@@ -57,6 +61,7 @@ impl MssqlStream {
             pending_done_count: 0,
             transaction_descriptor: 0,
             transaction_depth: 0,
+            reset_connection: false,
         })
     }
 
@@ -70,10 +75,16 @@ impl MssqlStream {
 
         let mut len_offset = 0;
 
+        let mut status = Status::END_OF_MESSAGE;
+
+        if mem::take(&mut self.reset_connection) {
+            status |= Status::RESET_CONN;
+        }
+
         self.inner.write_with(
             PacketHeader {
                 r#type: ty,
-                status: Status::END_OF_MESSAGE,
+                status,
                 length: 0,
                 server_process_id: 0,
                 packet_id: 1,
@@ -92,29 +103,47 @@ impl MssqlStream {
     // receive the next packet from the database
     // blocks until a packet is available
     pub(super) async fn recv_packet(&mut self) -> Result<(PacketHeader, Bytes), Error> {
-        let mut header: PacketHeader = self.inner.read(8).await?;
-
-        // NOTE: From what I can tell, the response type from the server should ~always~
-        //       be TabularResult. Here we expect that and die otherwise.
-        if !matches!(header.r#type, PacketType::TabularResult) {
-            return Err(err_protocol!(
-                "received unexpected packet: {:?}",
-                header.r#type
-            ));
-        }
+        // a message may span several packets; they are all read into the buffer of the stream
+        // before any is consumed, so that a cancelled receive leaves the stream at the start
+        // of the message
+        let mut len = 0;
+
+        let header = loop {
+            let header = PacketHeader::decode(Bytes::copy_from_slice(
+                &self.inner.peek(len + 8).await?[len..],
+            ))?;
+
+            // NOTE: From what I can tell, the response type from the server should ~always~
+            //       be TabularResult. Here we expect that and die otherwise.
+            if !matches!(header.r#type, PacketType::TabularResult) {
+                return Err(err_protocol!(
+                    "received unexpected packet: {:?}",
+                    header.r#type
+                ));
+            }
 
-        let mut payload = BytesMut::new();
+            if header.length < 8 {
+                return Err(err_protocol!(
+                    "received packet with invalid length: {}",
+                    header.length
+                ));
+            }
 
-        loop {
-            self.inner
-                .read_raw_into(&mut payload, (header.length - 8) as usize)
-                .await?;
+            len += header.length as usize;
+            self.inner.peek(len).await?;
 
             if header.status.contains(Status::END_OF_MESSAGE) {
-                break;
+                break header;
             }
+        };
+
+        let mut packets = self.inner.read_raw(len).await?;
+        let mut payload = BytesMut::with_capacity(len);
+
+        while !packets.is_empty() {
+            let length = u16::from_be_bytes([packets[2], packets[3]]) as usize;
 
-            header = self.inner.read(8).await?;
+            payload.extend_from_slice(&packets.split_to(length)[8..]);
         }
 
         Ok((header, payload.freeze()))
@@ -10,15 +10,17 @@ pub trait MssqlBufExt: Buf {
 
     fn get_us_varchar(&mut self) -> Result<String, Error>;
 
-    fn get_b_varbyte(&mut self) -> Bytes;
+    fn get_b_varbyte(&mut self) -> Result<Bytes, Error>;
 }
 
 impl MssqlBufExt for Bytes {
     fn get_utf16_str(&mut self, mut n: usize) -> Result<String, Error> {
+        self.ensure_remaining(n * 2)?;
+
         let mut raw = Vec::with_capacity(n * 2);
 
         while n > 0 {
-            let ch = self.get_u16_le();
+            let ch = self.try_get_u16_le()?;
             raw.push(ch);
             n -= 1;
         }
@@ -27,17 +29,16 @@ impl MssqlBufExt for Bytes {
     }
 
     fn get_b_varchar(&mut self) -> Result<String, Error> {
-        let size = self.get_u8();
+        let size = self.try_get_u8()?;
         self.get_utf16_str(size as usize)
     }
 
     fn get_us_varchar(&mut self) -> Result<String, Error> {
-        let size = self.get_u16_le();
+        let size = self.try_get_u16_le()?;
         self.get_utf16_str(size as usize)
     }
 
-    fn get_b_varbyte(&mut self) -> Bytes {
-        let size = self.get_u8();
-        self.get_bytes(size as usize)
+    fn get_b_varbyte(&mut self) -> Result<Bytes, Error> {
+        self.get_bytes_prefixed_le(1)
     }
 }
@@ -1,8 +1,9 @@
 use bitflags::bitflags;
-use bytes::{Buf, Bytes};
+use bytes::Bytes;
 
 use crate::error::Error;
 use crate::ext::ustr::UStr;
+use crate::io::BufExt;
 use crate::mssql::io::MssqlBufExt;
 use crate::mssql::protocol::type_info::TypeInfo;
 use crate::mssql::MssqlColumn;
@@ -85,7 +86,7 @@ impl ColMetaData {
         columns.clear();
         column_names.clear();
 
-        let mut count = buf.get_u16_le();
+        let mut count = buf.try_get_u16_le()?;
         let mut ordinal = 0;
 
         if count == 0xffff {
@@ -113,8 +114,8 @@ impl ColMetaData {
 
 impl ColumnData {
     fn get(buf: &mut Bytes) -> Result<Self, Error> {
-        let user_type = buf.get_u32_le();
-        let flags = Flags::from_bits_truncate(buf.get_u16_le());
+        let user_type = buf.try_get_u32_le()?;
+        let flags = Flags::from_bits_truncate(buf.try_get_u16_le()?);
         let type_info = TypeInfo::get(buf)?;
 
         // TODO: table_name
@@ -1,6 +1,7 @@
-use bytes::{Buf, Bytes};
+use bytes::Bytes;
 
 use crate::error::Error;
+use crate::io::BufExt;
 use crate::mssql::io::MssqlBufExt;
 
 #[derive(Debug)]
@@ -33,9 +34,15 @@ pub(crate) enum EnvChange {
 
 impl EnvChange {
     pub(crate) fn get(buf: &mut Bytes) -> Result<Self, Error> {
-        let len = buf.get_u16_le();
-        let ty = buf.get_u8();
-        let mut data = buf.split_to((len - 1) as usize);
+        let len = buf.try_get_u16_le()? as usize;
+        let ty = buf.try_get_u8()?;
+
+        // the length includes the type
+        let len = len
+            .checked_sub(1)
+            .ok_or_else(|| err_protocol!("ENVCHANGE length must include its type"))?;
+
+        let mut data = buf.get_bytes(len)?;
 
         Ok(match ty {
             1 => EnvChange::Database(data.get_b_varchar()?),
@@ -44,22 +51,71 @@ impl EnvChange {
             4 => EnvChange::PacketSize(data.get_b_varchar()?),
             5 => EnvChange::UnicodeDataSortingLocalId(data.get_b_varchar()?),
             6 => EnvChange::UnicodeDataSortingComparisonFlags(data.get_b_varchar()?),
-            7 => EnvChange::SqlCollation(data.get_b_varbyte()),
-            8 => EnvChange::BeginTransaction(data.get_b_varbyte().get_u64_le()),
+            7 => EnvChange::SqlCollation(data.get_b_varbyte()?),
+            8 => {
+                let mut descriptor = data.get_b_varbyte()?;
+                EnvChange::BeginTransaction(descriptor.try_get_u64_le()?)
+            }
 
             9 => {
-                let _ = data.get_u8();
-                EnvChange::CommitTransaction(data.get_u64_le())
+                let _ = data.try_get_u8()?;
+                EnvChange::CommitTransaction(data.try_get_u64_le()?)
             }
 
             10 => {
-                let _ = data.get_u8();
-                EnvChange::RollbackTransaction(data.get_u64_le())
+                let _ = data.try_get_u8()?;
+                EnvChange::RollbackTransaction(data.try_get_u64_le()?)
             }
 
+            18 => EnvChange::ResetConnectionCompletionAck,
+
             _ => {
                 return Err(err_protocol!("unexpected value {} for ENVCHANGE Type", ty));
             }
         })
     }
 }
+
+#[test]
+fn test_get_env_change() {
+    // the database changed to `db` from nothing
+    let mut buf = Bytes::from_static(b"\x07\x00\x01\x02d\x00b\x00\x00");
+
+    assert!(matches!(
+        EnvChange::get(&mut buf),
+        Ok(EnvChange::Database(name)) if name == "db"
+    ));
+
+    assert!(buf.is_empty());
+}
+
+#[test]
+fn test_get_truncated_env_change() {
+    const DATABASE: &[u8] = b"\x07\x00\x01\x02d\x00b\x00\x00";
+    const COMMIT: &[u8] = b"\x0a\x00\x09\x00\x01\x02\x03\x04\x05\x06\x07\x08";
+
+    for data in &[DATABASE, COMMIT] {
+        for len in 0..data.len() {
+            assert!(EnvChange::get(&mut Bytes::copy_from_slice(&data[..len])).is_err());
+        }
+    }
+
+    // a length that does not cover the type
+    assert!(EnvChange::get(&mut Bytes::from_static(b"\x00\x00\x01")).is_err());
+
+    // a transaction descriptor shorter than 8 bytes
+    assert!(EnvChange::get(&mut Bytes::from_static(b"\x03\x00\x08\x01\x00")).is_err());
+}
+
+#[test]
+fn test_get_reset_connection_env_change() {
+    // sent in reply to a request with the RESETCONNECTION status flag
+    let mut buf = Bytes::from_static(b"\x03\x00\x12\x00\x00");
+
+    assert!(matches!(
+        EnvChange::get(&mut buf),
+        Ok(EnvChange::ResetConnectionCompletionAck)
+    ));
+
+    assert!(buf.is_empty());
+}
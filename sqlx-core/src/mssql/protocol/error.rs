@@ -1,5 +1,6 @@
+use crate::io::BufExt;
 use crate::mssql::io::MssqlBufExt;
-use bytes::{Buf, Bytes};
+use bytes::Bytes;
 
 #[derive(Debug)]
 pub(crate) struct Error {
@@ -30,16 +31,16 @@ pub(crate) struct Error {
 
 impl Error {
     pub(crate) fn get(buf: &mut Bytes) -> Result<Self, crate::error::Error> {
-        let len = buf.get_u16_le();
-        let mut data = buf.split_to(len as usize);
+        let len = buf.try_get_u16_le()?;
+        let mut data = buf.get_bytes(len as usize)?;
 
-        let number = data.get_i32_le();
-        let state = data.get_u8();
-        let class = data.get_u8();
+        let number = data.try_get_i32_le()?;
+        let state = data.try_get_u8()?;
+        let class = data.try_get_u8()?;
         let message = data.get_us_varchar()?;
         let server = data.get_b_varchar()?;
         let procedure = data.get_b_varchar()?;
-        let line = data.get_i32_le();
+        let line = data.try_get_i32_le()?;
 
         Ok(Self {
             number,
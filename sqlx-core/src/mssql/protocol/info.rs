@@ -1,6 +1,7 @@
-use bytes::{Buf, Bytes};
+use bytes::Bytes;
 
 use crate::error::Error;
+use crate::io::BufExt;
 use crate::mssql::io::MssqlBufExt;
 
 #[derive(Debug)]
@@ -16,16 +17,16 @@ pub(crate) struct Info {
 
 impl Info {
     pub(crate) fn get(buf: &mut Bytes) -> Result<Self, Error> {
-        let len = buf.get_u16_le();
-        let mut data = buf.split_to(len as usize);
+        let len = buf.try_get_u16_le()?;
+        let mut data = buf.get_bytes(len as usize)?;
 
-        let number = data.get_u32_le();
-        let state = data.get_u8();
-        let class = data.get_u8();
+        let number = data.try_get_u32_le()?;
+        let state = data.try_get_u8()?;
+        let class = data.try_get_u8()?;
         let message = data.get_us_varchar()?;
         let server = data.get_b_varchar()?;
         let procedure = data.get_b_varchar()?;
-        let line = data.get_u32_le();
+        let line = data.try_get_u32_le()?;
 
         Ok(Self {
             number,
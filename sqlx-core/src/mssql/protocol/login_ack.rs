@@ -1,6 +1,7 @@
-use bytes::{Buf, Bytes};
+use bytes::Bytes;
 
 use crate::error::Error;
+use crate::io::BufExt;
 use crate::mssql::io::MssqlBufExt;
 use crate::mssql::protocol::pre_login::Version;
 
@@ -14,15 +15,15 @@ pub(crate) struct LoginAck {
 
 impl LoginAck {
     pub(crate) fn get(buf: &mut Bytes) -> Result<Self, Error> {
-        let len = buf.get_u16_le();
-        let mut data = buf.split_to(len as usize);
+        let len = buf.try_get_u16_le()?;
+        let mut data = buf.get_bytes(len as usize)?;
 
-        let interface = data.get_u8();
-        let tds_version = data.get_u32_le();
+        let interface = data.try_get_u8()?;
+        let tds_version = data.try_get_u32_le()?;
         let program_name = data.get_b_varchar()?;
-        let program_version_major = data.get_u8();
-        let program_version_minor = data.get_u8();
-        let program_version_build = data.get_u16();
+        let program_version_major = data.try_get_u8()?;
+        let program_version_minor = data.try_get_u8()?;
+        let program_version_build = data.try_get_u16()?;
 
         Ok(Self {
             interface,
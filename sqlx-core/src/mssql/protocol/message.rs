@@ -1,5 +1,6 @@
-use bytes::{Buf, Bytes};
+use bytes::Bytes;
 
+use crate::io::BufExt;
 use crate::mssql::protocol::done::Done;
 use crate::mssql::protocol::login_ack::LoginAck;
 use crate::mssql::protocol::order::Order;
@@ -38,7 +39,7 @@ pub(crate) enum MessageType {
 
 impl MessageType {
     pub(crate) fn get(buf: &mut Bytes) -> Result<Self, crate::error::Error> {
-        Ok(match buf.get_u8() {
+        Ok(match buf.try_get_u8()? {
             0x81 => MessageType::ColMetaData,
             0xaa => MessageType::Error,
             0xab => MessageType::Info,
@@ -1,8 +1,8 @@
 use bitflags::bitflags;
-use bytes::{Buf, Bytes};
+use bytes::Bytes;
 
 use crate::error::Error;
-use crate::io::{Decode, Encode};
+use crate::io::{BufExt, Decode, Encode};
 
 #[derive(Debug)]
 pub(crate) struct PacketHeader {
@@ -44,11 +44,11 @@ impl<'s> Encode<'s, &'s mut usize> for PacketHeader {
 impl Decode<'_> for PacketHeader {
     fn decode_with(mut buf: Bytes, _: ()) -> Result<Self, Error> {
         Ok(Self {
-            r#type: PacketType::get(buf.get_u8())?,
-            status: Status::from_bits_truncate(buf.get_u8()),
-            length: buf.get_u16(),
-            server_process_id: buf.get_u16(),
-            packet_id: buf.get_u8(),
+            r#type: PacketType::get(buf.try_get_u8()?)?,
+            status: Status::from_bits_truncate(buf.try_get_u8()?),
+            length: buf.try_get_u16()?,
+            server_process_id: buf.try_get_u16()?,
+            packet_id: buf.try_get_u8()?,
         })
     }
 }
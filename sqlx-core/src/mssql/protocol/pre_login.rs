@@ -1,11 +1,11 @@
 use std::fmt::{self, Display, Formatter};
 
 use bitflags::bitflags;
-use bytes::{Buf, Bytes};
+use bytes::Bytes;
 use uuid::Uuid;
 
 use crate::error::Error;
-use crate::io::{Decode, Encode};
+use crate::io::{BufExt, Decode, Encode};
 
 /// A message sent by the client to set up context for login. The server responds to a client
 /// `PRELOGIN` message with a message of packet header type `0x04` and the packet data
@@ -34,20 +34,23 @@ impl<'de> Decode<'de> for PreLogin<'de> {
         let mut offsets = buf.clone();
 
         loop {
-            let token = offsets.get_u8();
+            let token = offsets.try_get_u8()?;
 
             match PreLoginOptionToken::get(token) {
                 Some(token) => {
-                    let offset = offsets.get_u16() as usize;
-                    let size = offsets.get_u16() as usize;
-                    let mut data = &buf[offset..offset + size];
+                    let offset = offsets.try_get_u16()? as usize;
+                    let size = offsets.try_get_u16()? as usize;
+                    let mut data = buf
+                        .get(offset..offset + size)
+                        .map(|data| buf.slice_ref(data))
+                        .ok_or_else(|| err_protocol!("PRELOGIN: option data out of bounds"))?;
 
                     match token {
                         PreLoginOptionToken::Version => {
-                            let major = data.get_u8();
-                            let minor = data.get_u8();
-                            let build = data.get_u16();
-                            let sub_build = data.get_u16();
+                            let major = data.try_get_u8()?;
+                            let minor = data.try_get_u8()?;
+                            let build = data.try_get_u16()?;
+                            let sub_build = data.try_get_u16()?;
 
                             version = Some(Version {
                                 major,
@@ -58,7 +61,7 @@ impl<'de> Decode<'de> for PreLogin<'de> {
                         }
 
                         PreLoginOptionToken::Encryption => {
-                            encryption = Some(Encrypt::from_bits_truncate(data.get_u8()));
+                            encryption = Some(Encrypt::from_bits_truncate(data.try_get_u8()?));
                         }
 
                         tok => todo!("{:?}", tok),
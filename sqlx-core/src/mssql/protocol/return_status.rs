@@ -1,6 +1,7 @@
-use bytes::{Buf, Bytes};
+use bytes::Bytes;
 
 use crate::error::Error;
+use crate::io::BufExt;
 
 #[derive(Debug)]
 pub(crate) struct ReturnStatus {
@@ -9,7 +10,7 @@ pub(crate) struct ReturnStatus {
 
 impl ReturnStatus {
     pub(crate) fn get(buf: &mut Bytes) -> Result<Self, Error> {
-        let value = buf.get_i32_le();
+        let value = buf.try_get_i32_le()?;
 
         Ok(Self { value })
     }
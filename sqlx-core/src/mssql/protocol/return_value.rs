@@ -1,7 +1,8 @@
 use bitflags::bitflags;
-use bytes::{Buf, Bytes};
+use bytes::Bytes;
 
 use crate::error::Error;
+use crate::io::BufExt;
 use crate::mssql::io::MssqlBufExt;
 use crate::mssql::protocol::col_meta_data::Flags;
 use crate::mssql::protocol::type_info::TypeInfo;
@@ -29,13 +30,13 @@ bitflags! {
 
 impl ReturnValue {
     pub(crate) fn get(buf: &mut Bytes) -> Result<Self, Error> {
-        let ordinal = buf.get_u16_le();
+        let ordinal = buf.try_get_u16_le()?;
         let name = buf.get_b_varchar()?;
-        let status = ReturnValueStatus::from_bits_truncate(buf.get_u8());
-        let user_type = buf.get_u32_le();
-        let flags = Flags::from_bits_truncate(buf.get_u16_le());
+        let status = ReturnValueStatus::from_bits_truncate(buf.try_get_u8()?);
+        let user_type = buf.try_get_u32_le()?;
+        let flags = Flags::from_bits_truncate(buf.try_get_u16_le()?);
         let type_info = TypeInfo::get(buf)?;
-        let value = type_info.get_value(buf);
+        let value = type_info.get_value(buf)?;
 
         Ok(Self {
             param_ordinal: ordinal,
@@ -20,7 +20,7 @@ impl Row {
         let mut column_types = Vec::with_capacity(columns.len());
 
         let nulls = if nullable {
-            buf.get_bytes((columns.len() + 7) / 8)
+            buf.get_bytes((columns.len() + 7) / 8)?
         } else {
             Bytes::from_static(b"")
         };
@@ -30,7 +30,7 @@ impl Row {
 
             if !(column.type_info.0.is_null() || (nullable && (nulls[i / 8] & (1 << (i % 8))) != 0))
             {
-                values.push(column.type_info.0.get_value(buf));
+                values.push(column.type_info.0.get_value(buf)?);
             } else {
                 values.push(None);
             }
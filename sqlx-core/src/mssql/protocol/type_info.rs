@@ -1,9 +1,10 @@
 use bitflags::bitflags;
-use bytes::{Buf, Bytes};
+use bytes::Bytes;
 use encoding_rs::Encoding;
 
 use crate::encode::{Encode, IsNull};
 use crate::error::Error;
+use crate::io::BufExt;
 use crate::mssql::Mssql;
 
 bitflags! {
@@ -155,7 +156,7 @@ impl TypeInfo {
             DataType::DateN => Self::new(ty, 3),
 
             DataType::TimeN | DataType::DateTime2N | DataType::DateTimeOffsetN => {
-                let scale = buf.get_u8();
+                let scale = buf.try_get_u8()?;
 
                 let mut size = match scale {
                     0 | 1 | 2 => 3,
@@ -197,12 +198,12 @@ impl TypeInfo {
             | DataType::Char
             | DataType::VarChar
             | DataType::Binary
-            | DataType::VarBinary => Self::new(ty, buf.get_u8() as u32),
+            | DataType::VarBinary => Self::new(ty, buf.try_get_u8()? as u32),
 
             DataType::Decimal | DataType::Numeric | DataType::DecimalN | DataType::NumericN => {
-                let size = buf.get_u8() as u32;
-                let precision = buf.get_u8();
-                let scale = buf.get_u8();
+                let size = buf.try_get_u8()? as u32;
+                let precision = buf.try_get_u8()?;
+                let scale = buf.try_get_u8()?;
 
                 Self {
                     size,
@@ -213,11 +214,13 @@ impl TypeInfo {
                 }
             }
 
-            DataType::BigVarBinary | DataType::BigBinary => Self::new(ty, buf.get_u16_le() as u32),
+            DataType::BigVarBinary | DataType::BigBinary => {
+                Self::new(ty, buf.try_get_u16_le()? as u32)
+            }
 
             DataType::BigVarChar | DataType::BigChar | DataType::NVarChar | DataType::NChar => {
-                let size = buf.get_u16_le() as u32;
-                let collation = Collation::get(buf);
+                let size = buf.try_get_u16_le()? as u32;
+                let collation = Collation::get(buf)?;
 
                 Self {
                     ty,
@@ -303,8 +306,8 @@ impl TypeInfo {
         matches!(self.ty, DataType::Null)
     }
 
-    pub(crate) fn get_value(&self, buf: &mut Bytes) -> Option<Bytes> {
-        match self.ty {
+    pub(crate) fn get_value(&self, buf: &mut Bytes) -> Result<Option<Bytes>, Error> {
+        Ok(match self.ty {
             DataType::Null
             | DataType::TinyInt
             | DataType::Bit
@@ -316,7 +319,7 @@ impl TypeInfo {
             | DataType::DateTime
             | DataType::Float
             | DataType::SmallMoney
-            | DataType::BigInt => Some(buf.split_to(self.size as usize)),
+            | DataType::BigInt => Some(buf.get_bytes(self.size as usize)?),
 
             DataType::Guid
             | DataType::IntN
@@ -332,22 +335,22 @@ impl TypeInfo {
             | DataType::TimeN
             | DataType::DateTime2N
             | DataType::DateTimeOffsetN => {
-                let size = buf.get_u8();
+                let size = buf.try_get_u8()?;
 
                 if size == 0 || size == 0xFF {
                     None
                 } else {
-                    Some(buf.split_to(size as usize))
+                    Some(buf.get_bytes(size as usize)?)
                 }
             }
 
             DataType::Char | DataType::VarChar | DataType::Binary | DataType::VarBinary => {
-                let size = buf.get_u8();
+                let size = buf.try_get_u8()?;
 
                 if size == 0xFF {
                     None
                 } else {
-                    Some(buf.split_to(size as usize))
+                    Some(buf.get_bytes(size as usize)?)
                 }
             }
 
@@ -359,25 +362,25 @@ impl TypeInfo {
             | DataType::NChar
             | DataType::Xml
             | DataType::UserDefined => {
-                let size = buf.get_u16_le();
+                let size = buf.try_get_u16_le()?;
 
                 if size == 0xFF_FF {
                     None
                 } else {
-                    Some(buf.split_to(size as usize))
+                    Some(buf.get_bytes(size as usize)?)
                 }
             }
 
             DataType::Text | DataType::Image | DataType::NText | DataType::Variant => {
-                let size = buf.get_u32_le();
+                let size = buf.try_get_u32_le()?;
 
                 if size == 0xFFFF_FFFF {
                     None
                 } else {
-                    Some(buf.split_to(size as usize))
+                    Some(buf.get_bytes(size as usize)?)
                 }
             }
-        }
+        })
     }
 
     pub(crate) fn put_value<'q, T: Encode<'q, Mssql>>(&self, buf: &mut Vec<u8>, value: T) {
@@ -585,7 +588,7 @@ impl TypeInfo {
 
 impl DataType {
     pub(crate) fn get(buf: &mut Bytes) -> Result<Self, Error> {
-        Ok(match buf.get_u8() {
+        Ok(match buf.try_get_u8()? {
             0x1f => DataType::Null,
             0x30 => DataType::TinyInt,
             0x32 => DataType::Bit,
@@ -637,19 +640,19 @@ impl DataType {
 }
 
 impl Collation {
-    pub(crate) fn get(buf: &mut Bytes) -> Collation {
-        let locale_sort_version = buf.get_u32_le();
+    pub(crate) fn get(buf: &mut Bytes) -> Result<Collation, Error> {
+        let locale_sort_version = buf.try_get_u32_le()?;
         let locale = locale_sort_version & 0xfffff;
         let flags = CollationFlags::from_bits_truncate(((locale_sort_version >> 20) & 0xFF) as u8);
         let version = (locale_sort_version >> 28) as u8;
-        let sort = buf.get_u8();
+        let sort = buf.try_get_u8()?;
 
-        Collation {
+        Ok(Collation {
             locale,
             flags,
             sort,
             version,
-        }
+        })
     }
 
     pub(crate) fn put(&self, buf: &mut Vec<u8>) {
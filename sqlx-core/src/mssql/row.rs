@@ -4,6 +4,7 @@ use crate::ext::ustr::UStr;
 use crate::mssql::protocol::row::Row as ProtocolRow;
 use crate::mssql::{Mssql, MssqlColumn, MssqlValueRef};
 use crate::row::Row;
+use crate::type_overrides::TypeOverrides;
 use crate::HashMap;
 use std::sync::Arc;
 
@@ -11,9 +12,19 @@ pub struct MssqlRow {
     pub(crate) row: ProtocolRow,
     pub(crate) columns: Arc<Vec<MssqlColumn>>,
     pub(crate) column_names: Arc<HashMap<UStr, usize>>,
+    pub(crate) type_overrides: Option<Arc<TypeOverrides<Mssql>>>,
 }
 
-impl crate::row::private_row::Sealed for MssqlRow {}
+impl crate::row::private_row::Sealed for MssqlRow {
+    fn data_len(&self) -> usize {
+        self.row
+            .values
+            .iter()
+            .flatten()
+            .map(|value| value.len())
+            .sum()
+    }
+}
 
 impl Row for MssqlRow {
     type Database = Mssql;
@@ -30,10 +41,15 @@ impl Row for MssqlRow {
         let value = MssqlValueRef {
             data: self.row.values[index].as_ref(),
             type_info: self.row.column_types[index].clone(),
+            type_overrides: self.type_overrides.as_ref(),
         };
 
         Ok(value)
     }
+
+    fn type_overrides(&self) -> Option<&TypeOverrides<Mssql>> {
+        self.type_overrides.as_deref()
+    }
 }
 
 impl ColumnIndex<MssqlRow> for &'_ str {
@@ -52,6 +68,7 @@ impl From<MssqlRow> for crate::any::AnyRow {
         crate::any::AnyRow {
             columns: row.columns.iter().map(|col| col.clone().into()).collect(),
             kind: crate::any::row::AnyRowKind::Mssql(row),
+            type_overrides: None,
         }
     }
 }
@@ -1,14 +1,17 @@
 use crate::error::{BoxDynError, UnexpectedNullError};
 use crate::mssql::{Mssql, MssqlTypeInfo};
+use crate::type_overrides::TypeOverrides;
 use crate::value::{Value, ValueRef};
 use bytes::Bytes;
 use std::borrow::Cow;
+use std::sync::Arc;
 
 /// Implementation of [`ValueRef`] for MSSQL.
 #[derive(Clone)]
 pub struct MssqlValueRef<'r> {
     pub(crate) type_info: MssqlTypeInfo,
     pub(crate) data: Option<&'r Bytes>,
+    pub(crate) type_overrides: Option<&'r Arc<TypeOverrides<Mssql>>>,
 }
 
 impl<'r> MssqlValueRef<'r> {
@@ -27,6 +30,7 @@ impl ValueRef<'_> for MssqlValueRef<'_> {
         MssqlValue {
             data: self.data.cloned(),
             type_info: self.type_info.clone(),
+            type_overrides: self.type_overrides.cloned(),
         }
     }
 
@@ -46,6 +50,7 @@ impl<'r> From<MssqlValueRef<'r>> for crate::any::AnyValueRef<'r> {
         crate::any::AnyValueRef {
             type_info: value.type_info.clone().into(),
             kind: crate::any::value::AnyValueRefKind::Mssql(value),
+            type_overrides: None,
         }
     }
 }
@@ -55,6 +60,7 @@ impl<'r> From<MssqlValueRef<'r>> for crate::any::AnyValueRef<'r> {
 pub struct MssqlValue {
     pub(crate) type_info: MssqlTypeInfo,
     pub(crate) data: Option<Bytes>,
+    pub(crate) type_overrides: Option<Arc<TypeOverrides<Mssql>>>,
 }
 
 impl Value for MssqlValue {
@@ -64,6 +70,7 @@ impl Value for MssqlValue {
         MssqlValueRef {
             data: self.data.as_ref(),
             type_info: self.type_info.clone(),
+            type_overrides: self.type_overrides.as_ref(),
         }
     }
 
@@ -74,6 +81,10 @@ impl Value for MssqlValue {
     fn is_null(&self) -> bool {
         self.data.is_none() || self.type_info.0.is_null()
     }
+
+    fn type_overrides(&self) -> Option<&TypeOverrides<Mssql>> {
+        self.type_overrides.as_deref()
+    }
 }
 
 #[cfg(feature = "any")]
@@ -83,6 +94,7 @@ impl From<MssqlValue> for crate::any::AnyValue {
         crate::any::AnyValue {
             type_info: value.type_info.clone().into(),
             kind: crate::any::value::AnyValueKind::Mssql(value),
+            type_overrides: None,
         }
     }
 }
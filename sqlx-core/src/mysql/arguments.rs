@@ -1,6 +1,15 @@
+use std::convert::TryFrom;
+
+use bytes::{Buf, Bytes};
+
 use crate::arguments::Arguments;
 use crate::encode::{Encode, IsNull};
+use crate::error::Error;
+use crate::io::BufExt;
+use crate::mysql::protocol::text::{ColumnFlags, ColumnType};
 use crate::mysql::{MySql, MySqlTypeInfo};
+use crate::snapshot::SnapshotValue;
+use crate::type_info::TypeInfo;
 use crate::types::Type;
 
 /// Implementation of [`Arguments`] for MySQL.
@@ -26,6 +35,17 @@ impl MySqlArguments {
             self.null_bitmap[index / 8] |= (1 << (index % 8)) as u8;
         }
     }
+
+    // add a date or time already encoded in the binary protocol
+    fn add_temporal(&mut self, ty: ColumnType, value: &[u8]) {
+        let index = self.types.len();
+
+        self.types.push(MySqlTypeInfo::binary(ty));
+        self.null_bitmap.resize((index / 8) + 1, 0);
+
+        self.values.push(value.len() as u8);
+        self.values.extend_from_slice(value);
+    }
 }
 
 impl<'q> Arguments<'q> for MySqlArguments {
@@ -42,4 +62,302 @@ impl<'q> Arguments<'q> for MySqlArguments {
     {
         self.add(value)
     }
+
+    fn snapshot(&self) -> Option<Vec<SnapshotValue>> {
+        let mut buf = Bytes::copy_from_slice(&self.values);
+        let mut values = Vec::with_capacity(self.types.len());
+
+        for (index, ty) in self.types.iter().enumerate() {
+            // NULL values are only marked in the bitmap and take no space in the buffer
+            if self.null_bitmap[index / 8] & (1 << (index % 8)) != 0 {
+                values.push(SnapshotValue::Null);
+                continue;
+            }
+
+            values.push(snapshot_value(ty, &mut buf).ok()?);
+        }
+
+        Some(values)
+    }
+
+    fn add_snapshot_value(&mut self, value: &SnapshotValue) {
+        if let SnapshotValue::Typed { type_name, text } = value {
+            let ty = match &**type_name {
+                "DATE" => ColumnType::Date,
+                "TIME" => ColumnType::Time,
+                "DATETIME" => ColumnType::Datetime,
+                "TIMESTAMP" => ColumnType::Timestamp,
+
+                _ => return self.add(text.clone()),
+            };
+
+            if let Some(encoded) = parse_temporal(ty, text) {
+                return self.add_temporal(ty, &encoded);
+            }
+        }
+
+        self.add(value.clone())
+    }
+}
+
+// read a value in the binary protocol encoding of its type
+fn snapshot_value(ty: &MySqlTypeInfo, buf: &mut Bytes) -> Result<SnapshotValue, Error> {
+    let size = match ty.r#type {
+        ColumnType::Null => return Ok(SnapshotValue::Null),
+
+        ColumnType::Tiny => 1,
+        ColumnType::Short | ColumnType::Year => 2,
+        ColumnType::Long | ColumnType::Int24 | ColumnType::Float => 4,
+        ColumnType::LongLong | ColumnType::Double => 8,
+
+        ColumnType::Date | ColumnType::Time | ColumnType::Datetime | ColumnType::Timestamp => {
+            let value = buf.get_bytes_prefixed_le(1)?;
+
+            return Ok(match render_temporal(ty.r#type, &value) {
+                Some(text) => SnapshotValue::Typed {
+                    type_name: ty.name().to_owned(),
+                    text,
+                },
+
+                None => SnapshotValue::Bytes(value.to_vec()),
+            });
+        }
+
+        _ => {
+            let value = buf.get_bytes_lenenc()?;
+
+            // the binary character set
+            return Ok(if ty.char_set == 63 {
+                SnapshotValue::Bytes(value.to_vec())
+            } else {
+                SnapshotValue::Text(String::from_utf8_lossy(&value).into_owned())
+            });
+        }
+    };
+
+    buf.ensure_remaining(size)?;
+
+    Ok(match ty.r#type {
+        ColumnType::Float => SnapshotValue::Float(buf.get_f32_le().into()),
+        ColumnType::Double => SnapshotValue::Float(buf.get_f64_le()),
+
+        _ if ty.flags.contains(ColumnFlags::UNSIGNED) => {
+            let value = buf.get_uint_le(size);
+
+            i64::try_from(value).map_or_else(
+                |_| SnapshotValue::Text(value.to_string()),
+                SnapshotValue::Int,
+            )
+        }
+
+        ColumnType::Tiny => SnapshotValue::Int(buf.get_i8().into()),
+        ColumnType::Short | ColumnType::Year => SnapshotValue::Int(buf.get_i16_le().into()),
+        ColumnType::LongLong => SnapshotValue::Int(buf.get_i64_le()),
+        _ => SnapshotValue::Int(buf.get_i32_le().into()),
+    })
+}
+
+// render a date or time in the binary protocol as the text MySQL would accept for it
+fn render_temporal(ty: ColumnType, mut value: &[u8]) -> Option<String> {
+    if let ColumnType::Time = ty {
+        // a zero-length value is the zero time
+        let (negative, days, hour, minute, second) = match value.len() {
+            0 => (false, 0, 0, 0, 0),
+            8 | 12 => (
+                value.get_u8() != 0,
+                value.get_u32_le(),
+                value.get_u8(),
+                value.get_u8(),
+                value.get_u8(),
+            ),
+
+            _ => return None,
+        };
+
+        let sign = if negative { "-" } else { "" };
+        let hours = u64::from(days) * 24 + u64::from(hour);
+        let text = format!("{}{:02}:{:02}:{:02}", sign, hours, minute, second);
+
+        return Some(with_micros(text, value));
+    }
+
+    let (year, month, day) = match value.len() {
+        0 => (0, 0, 0),
+        4 | 7 | 11 => (value.get_u16_le(), value.get_u8(), value.get_u8()),
+
+        _ => return None,
+    };
+
+    let date = format!("{:04}-{:02}-{:02}", year, month, day);
+
+    if let ColumnType::Date = ty {
+        return Some(date);
+    }
+
+    let (hour, minute, second) = if value.is_empty() {
+        (0, 0, 0)
+    } else {
+        (value.get_u8(), value.get_u8(), value.get_u8())
+    };
+
+    let text = format!("{} {:02}:{:02}:{:02}", date, hour, minute, second);
+
+    Some(with_micros(text, value))
+}
+
+// append the fractional seconds left in `value`, if any
+fn with_micros(text: String, mut value: &[u8]) -> String {
+    if value.len() == 4 {
+        format!("{}.{:06}", text, value.get_u32_le())
+    } else {
+        text
+    }
+}
+
+// encode a date or time rendered by `render_temporal` back into the binary protocol
+fn parse_temporal(ty: ColumnType, text: &str) -> Option<Vec<u8>> {
+    let (negative, text) = match text.strip_prefix('-') {
+        Some(text) => (true, text),
+        None => (false, text),
+    };
+
+    let (text, micros) = match text.find('.') {
+        Some(index) => {
+            let fraction = &text[index + 1..];
+
+            if fraction.is_empty() || fraction.len() > 6 {
+                return None;
+            }
+
+            let scale = 10_u32.pow(6 - fraction.len() as u32);
+
+            (&text[..index], fraction.parse::<u32>().ok()? * scale)
+        }
+
+        None => (text, 0),
+    };
+
+    let fields = text
+        .split(&['-', ':', ' '][..])
+        .map(|field| field.parse::<u32>().ok())
+        .collect::<Option<Vec<_>>>()?;
+
+    let mut encoded = Vec::with_capacity(12);
+
+    match (ty, &*fields) {
+        (ColumnType::Time, &[hours, minute, second]) => {
+            encoded.push(negative as u8);
+            encoded.extend_from_slice(&(hours / 24).to_le_bytes());
+            encoded.push((hours % 24) as u8);
+            encoded.push(u8::try_from(minute).ok()?);
+            encoded.push(u8::try_from(second).ok()?);
+        }
+
+        (ColumnType::Date, &[year, month, day]) if !negative => {
+            encoded.extend_from_slice(&u16::try_from(year).ok()?.to_le_bytes());
+            encoded.push(u8::try_from(month).ok()?);
+            encoded.push(u8::try_from(day).ok()?);
+
+            return Some(encoded);
+        }
+
+        (ColumnType::Datetime, &[year, month, day, hour, minute, second])
+        | (ColumnType::Timestamp, &[year, month, day, hour, minute, second])
+            if !negative =>
+        {
+            encoded.extend_from_slice(&u16::try_from(year).ok()?.to_le_bytes());
+
+            for field in &[month, day, hour, minute, second] {
+                encoded.push(u8::try_from(*field).ok()?);
+            }
+        }
+
+        _ => return None,
+    }
+
+    encoded.extend_from_slice(&micros.to_le_bytes());
+
+    Some(encoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MySqlArguments;
+    use crate::arguments::Arguments;
+    use crate::mysql::protocol::text::ColumnType;
+    use crate::snapshot::SnapshotValue;
+
+    fn typed(type_name: &str, text: &str) -> SnapshotValue {
+        SnapshotValue::Typed {
+            type_name: type_name.into(),
+            text: text.into(),
+        }
+    }
+
+    #[test]
+    fn it_snapshots_arguments() {
+        let mut arguments = MySqlArguments::default();
+
+        arguments.add(true);
+        arguments.add(-5_i16);
+        arguments.add(u64::MAX);
+        arguments.add(1.5_f32);
+        arguments.add("alice");
+        arguments.add(None::<i32>);
+        arguments.add(&b"\x00\x01"[..]);
+
+        assert_eq!(
+            arguments.snapshot().unwrap(),
+            vec![
+                SnapshotValue::Int(1),
+                SnapshotValue::Int(-5),
+                SnapshotValue::Text(u64::MAX.to_string()),
+                SnapshotValue::Float(1.5),
+                SnapshotValue::Text("alice".into()),
+                SnapshotValue::Null,
+                SnapshotValue::Bytes(vec![0, 1]),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_snapshots_typed_arguments() {
+        let mut arguments = MySqlArguments::default();
+
+        arguments.add_temporal(ColumnType::Date, &[0xe4, 0x07, 2, 29]);
+        arguments.add_temporal(ColumnType::Datetime, &[0xe4, 0x07, 2, 29, 13, 45, 10]);
+        arguments.add_temporal(ColumnType::Timestamp, &[]);
+        arguments.add_temporal(ColumnType::Time, &[1, 1, 0, 0, 0, 3, 0, 1, 6, 0, 0, 0]);
+
+        let values = vec![
+            typed("DATE", "2020-02-29"),
+            typed("DATETIME", "2020-02-29 13:45:10"),
+            typed("TIMESTAMP", "0000-00-00 00:00:00"),
+            typed("TIME", "-27:00:01.000006"),
+        ];
+
+        assert_eq!(arguments.snapshot().unwrap(), values);
+
+        // bound again in the binary protocol, fractional seconds included
+        let mut replayed = MySqlArguments::default();
+
+        for value in &values {
+            replayed.add_snapshot_value(value);
+        }
+
+        replayed.add_snapshot_value(&typed("DATETIME", "2020-02-29 13:45:10.25"));
+        replayed.add_snapshot_value(&typed("GEOMETRY", "POINT(1 2)"));
+
+        assert_eq!(
+            replayed.snapshot().unwrap(),
+            vec![
+                typed("DATE", "2020-02-29"),
+                typed("DATETIME", "2020-02-29 13:45:10.000000"),
+                typed("TIMESTAMP", "0000-00-00 00:00:00.000000"),
+                typed("TIME", "-27:00:01.000006"),
+                typed("DATETIME", "2020-02-29 13:45:10.250000"),
+                SnapshotValue::Text("POINT(1 2)".into()),
+            ]
+        );
+    }
 }
@@ -0,0 +1,315 @@
+use std::io::{self, Read, Write};
+use std::ops::{Deref, DerefMut};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use bytes::{Buf, BytesMut};
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use futures_util::ready;
+use sqlx_rt::{AsyncRead, AsyncWrite};
+
+// https://dev.mysql.com/doc/dev/mysql-server/8.0.12/page_protocol_basic_compression.html
+
+// payloads shorter than this are sent uncompressed, as the MySQL client library does
+const MIN_COMPRESS_LENGTH: usize = 50;
+
+// the largest payload of a compressed packet
+const MAX_PAYLOAD_SIZE: usize = 0xFF_FF_FF;
+
+// a stream that, once compression is enabled, wraps everything written to it in compressed
+// packets and unwraps the compressed packets read from it
+//
+// the packets of the protocol are carried unchanged inside the compressed packets, so the
+// layers above read and write them as if the connection was not compressed
+pub struct CompressedStream<S> {
+    stream: S,
+    enabled: bool,
+
+    // the sequence of compressed packets, distinct from the sequence of the packets they carry
+    sequence_id: u8,
+
+    // bytes read from the stream that do not yet make a complete compressed packet
+    rbuf: BytesMut,
+
+    // decompressed bytes not yet read
+    inflated: BytesMut,
+
+    // bytes written since the last flush, compressed when flushed
+    pending: Vec<u8>,
+
+    // compressed packets not yet written to the stream
+    wbuf: Vec<u8>,
+}
+
+impl<S> CompressedStream<S> {
+    pub(crate) fn new(stream: S) -> Self {
+        Self {
+            stream,
+            enabled: false,
+            sequence_id: 0,
+            rbuf: BytesMut::new(),
+            inflated: BytesMut::new(),
+            pending: Vec::new(),
+            wbuf: Vec::new(),
+        }
+    }
+
+    // compress everything from now on, which the server does right after the OK packet
+    // that completes authentication
+    pub(crate) fn enable(&mut self) {
+        self.enabled = true;
+    }
+
+    // move the payload of the next compressed packet to `inflated`, if it was read in full
+    fn inflate(&mut self) -> io::Result<bool> {
+        if self.rbuf.len() < 7 {
+            return Ok(false);
+        }
+
+        let mut header = &self.rbuf[..7];
+
+        let compressed_len = header.get_uint_le(3) as usize;
+        let sequence_id = header.get_u8();
+        let uncompressed_len = header.get_uint_le(3) as usize;
+
+        if self.rbuf.len() < 7 + compressed_len {
+            return Ok(false);
+        }
+
+        self.rbuf.advance(7);
+        let payload = self.rbuf.split_to(compressed_len);
+
+        self.sequence_id = sequence_id.wrapping_add(1);
+
+        // a length of zero marks a payload sent uncompressed
+        if uncompressed_len == 0 {
+            self.inflated.extend_from_slice(&payload);
+        } else {
+            let offset = self.inflated.len();
+            self.inflated.resize(offset + uncompressed_len, 0);
+
+            ZlibDecoder::new(&payload[..]).read_exact(&mut self.inflated[offset..])?;
+        }
+
+        Ok(true)
+    }
+}
+
+// wrap `payload` in compressed packets, appended to `buf`
+fn compress(buf: &mut Vec<u8>, payload: &[u8], sequence_id: &mut u8) -> io::Result<()> {
+    for chunk in payload.chunks(MAX_PAYLOAD_SIZE) {
+        let offset = buf.len();
+        buf.extend_from_slice(&[0; 7]);
+
+        // a length of zero marks a payload sent as is, for being short or not compressing
+        let mut uncompressed_len = 0;
+
+        if chunk.len() >= MIN_COMPRESS_LENGTH {
+            let mut encoder = ZlibEncoder::new(&mut *buf, Compression::default());
+
+            encoder.write_all(chunk)?;
+            encoder.finish()?;
+
+            if buf.len() - offset - 7 < chunk.len() {
+                uncompressed_len = chunk.len();
+            } else {
+                buf.truncate(offset + 7);
+            }
+        }
+
+        if uncompressed_len == 0 {
+            buf.extend_from_slice(chunk);
+        }
+
+        let compressed_len = buf.len() - offset - 7;
+
+        buf[offset..offset + 3].copy_from_slice(&(compressed_len as u32).to_le_bytes()[..3]);
+        buf[offset + 3] = *sequence_id;
+        buf[offset + 4..offset + 7].copy_from_slice(&(uncompressed_len as u32).to_le_bytes()[..3]);
+
+        *sequence_id = sequence_id.wrapping_add(1);
+    }
+
+    Ok(())
+}
+
+impl<S> CompressedStream<S>
+where
+    S: AsyncWrite + Unpin,
+{
+    fn poll_write_compressed(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        if !self.pending.is_empty() {
+            // a command starts a new sequence of compressed packets, as it does of packets
+            if self.pending.get(3) == Some(&0) {
+                self.sequence_id = 0;
+            }
+
+            compress(&mut self.wbuf, &self.pending, &mut self.sequence_id)?;
+            self.pending.clear();
+        }
+
+        while !self.wbuf.is_empty() {
+            let written = ready!(Pin::new(&mut self.stream).poll_write(cx, &self.wbuf))?;
+
+            if written == 0 {
+                return Poll::Ready(Err(io::ErrorKind::WriteZero.into()));
+            }
+
+            self.wbuf.drain(..written);
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<S> AsyncRead for CompressedStream<S>
+where
+    S: AsyncRead + Unpin,
+{
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = &mut *self;
+
+        if !this.enabled || buf.is_empty() {
+            return Pin::new(&mut this.stream).poll_read(cx, buf);
+        }
+
+        loop {
+            if !this.inflated.is_empty() {
+                let n = buf.len().min(this.inflated.len());
+
+                buf[..n].copy_from_slice(&this.inflated[..n]);
+                this.inflated.advance(n);
+
+                return Poll::Ready(Ok(n));
+            }
+
+            if this.inflate()? {
+                continue;
+            }
+
+            // read more of the next compressed packet, through the buffer of the caller
+            let n = ready!(Pin::new(&mut this.stream).poll_read(cx, buf))?;
+
+            if n == 0 {
+                return Poll::Ready(Ok(0));
+            }
+
+            this.rbuf.extend_from_slice(&buf[..n]);
+        }
+    }
+}
+
+impl<S> AsyncWrite for CompressedStream<S>
+where
+    S: AsyncWrite + Unpin,
+{
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        if !self.enabled {
+            return Pin::new(&mut self.stream).poll_write(cx, buf);
+        }
+
+        self.pending.extend_from_slice(buf);
+
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        if self.enabled {
+            ready!(self.poll_write_compressed(cx))?;
+        }
+
+        Pin::new(&mut self.stream).poll_flush(cx)
+    }
+
+    #[cfg(any(feature = "_rt-actix", feature = "_rt-tokio"))]
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stream).poll_shutdown(cx)
+    }
+
+    #[cfg(feature = "_rt-async-std")]
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stream).poll_close(cx)
+    }
+}
+
+impl<S> Deref for CompressedStream<S> {
+    type Target = S;
+
+    fn deref(&self) -> &Self::Target {
+        &self.stream
+    }
+}
+
+impl<S> DerefMut for CompressedStream<S> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.stream
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{compress, CompressedStream};
+    use bytes::BytesMut;
+
+    fn stream(rbuf: &[u8]) -> CompressedStream<()> {
+        let mut stream = CompressedStream::new(());
+
+        stream.enable();
+        stream.rbuf = BytesMut::from(rbuf);
+
+        stream
+    }
+
+    #[test]
+    fn it_sends_short_payloads_uncompressed() {
+        let mut buf = Vec::new();
+        let mut sequence_id = 3;
+
+        compress(&mut buf, b"\x01\x00\x00\x00\x0e", &mut sequence_id).unwrap();
+
+        assert_eq!(buf, b"\x05\x00\x00\x03\x00\x00\x00\x01\x00\x00\x00\x0e");
+        assert_eq!(sequence_id, 4);
+    }
+
+    #[test]
+    fn it_round_trips_compressed_payloads() {
+        let payload = "SELECT 1 UNION ALL ".repeat(100).into_bytes();
+
+        let mut buf = Vec::new();
+        let mut sequence_id = 0;
+
+        compress(&mut buf, &payload, &mut sequence_id).unwrap();
+
+        // compressed, with the uncompressed length in the header
+        assert!(buf.len() < payload.len());
+        assert_eq!(&buf[4..7], &(payload.len() as u32).to_le_bytes()[..3]);
+
+        let mut stream = stream(&buf[..buf.len() - 1]);
+
+        // the last byte of the packet is missing
+        assert!(!stream.inflate().unwrap());
+
+        stream.rbuf.extend_from_slice(&buf[buf.len() - 1..]);
+
+        assert!(stream.inflate().unwrap());
+        assert_eq!(&stream.inflated[..], &payload[..]);
+        assert_eq!(stream.sequence_id, 1);
+    }
+
+    #[test]
+    fn it_rejects_corrupt_payloads() {
+        let mut stream = stream(b"\x03\x00\x00\x00\x10\x00\x00abc");
+
+        assert!(stream.inflate().is_err());
+    }
+}
@@ -25,7 +25,16 @@ impl MySqlConnection {
         // FIXME: server version parse is a bit ugly
         // expecting MAJOR.MINOR.PATCH
 
-        let mut server_version = handshake.server_version.split('.');
+        // MariaDB 10+ reports its version after a `5.5.5-` prefix kept for old clients,
+        // as in `5.5.5-10.5.8-MariaDB`
+        stream.mariadb = handshake.server_version.contains("MariaDB");
+
+        let mut server_version = if stream.mariadb {
+            handshake.server_version.trim_start_matches("5.5.5-")
+        } else {
+            handshake.server_version.as_str()
+        }
+        .split(&['.', '-'][..]);
 
         let server_version_major: u16 = server_version
             .next()
@@ -125,10 +134,17 @@ impl MySqlConnection {
             }
         }
 
+        // the server compresses everything after the packet that completes authentication
+        if stream.capabilities.contains(Capabilities::COMPRESS) {
+            stream.enable_compression();
+        }
+
         Ok(Self {
             stream,
             transaction_depth: 0,
+            last_sql: String::new(),
             cache_statement: StatementCache::new(options.statement_cache_capacity),
+            type_overrides: None,
             log_settings: options.log_settings.clone(),
         })
     }
@@ -3,9 +3,9 @@ use crate::describe::Describe;
 use crate::error::Error;
 use crate::executor::{Execute, Executor};
 use crate::ext::ustr::UStr;
+use crate::io::BufExt;
 use crate::logger::QueryLogger;
 use crate::mysql::connection::stream::Busy;
-use crate::mysql::io::MySqlBufExt;
 use crate::mysql::protocol::response::Status;
 use crate::mysql::protocol::statement::{
     BinaryRow, Execute as StatementExecute, Prepare, PrepareOk, StmtClose,
@@ -35,42 +35,17 @@ impl MySqlConnection {
             return Ok((*statement).clone());
         }
 
-        // https://dev.mysql.com/doc/internals/en/com-stmt-prepare.html
-        // https://dev.mysql.com/doc/internals/en/com-stmt-prepare-response.html#packet-COM_STMT_PREPARE_OK
+        // the response to a prepare cannot be resumed from the middle; the connection is
+        // broken if this future is dropped or fails before the response is read in full
+        self.stream.broken = true;
+        let prepared = prepare(&mut self.stream, sql).await;
 
-        self.stream.send_packet(Prepare { query: sql }).await?;
-
-        let ok: PrepareOk = self.stream.recv().await?;
-
-        // the parameter definitions are very unreliable so we skip over them
-        // as we have little use
-
-        if ok.params > 0 {
-            for _ in 0..ok.params {
-                let _def: ColumnDefinition = self.stream.recv().await?;
-            }
-
-            self.stream.maybe_recv_eof().await?;
+        // an error from the server is the whole response, unlike an error reading it
+        if matches!(prepared, Ok(_) | Err(Error::Database(_))) {
+            self.stream.broken = false;
         }
 
-        // the column definitions are berefit the type information from the
-        // to-be-bound parameters; we will receive the output column definitions
-        // once more on execute so we wait for that
-
-        let mut columns = Vec::new();
-
-        let column_names = if ok.columns > 0 {
-            recv_result_metadata(&mut self.stream, ok.columns as usize, &mut columns).await?
-        } else {
-            Default::default()
-        };
-
-        let id = ok.statement_id;
-        let metadata = MySqlStatementMetadata {
-            parameters: ok.params as usize,
-            columns: Arc::new(columns),
-            column_names: Arc::new(column_names),
-        };
+        let (id, metadata) = prepared?;
 
         if persistent && self.cache_statement.is_enabled() {
             // in case of the cache being full, close the least recently used statement
@@ -91,6 +66,9 @@ impl MySqlConnection {
     ) -> Result<impl Stream<Item = Result<Either<MySqlDone, MySqlRow>, Error>> + 'e, Error> {
         let mut logger = QueryLogger::new(sql, self.log_settings.clone());
 
+        self.last_sql.clear();
+        self.last_sql.push_str(sql);
+
         self.stream.wait_until_ready().await?;
         self.stream.busy = Busy::Result;
 
@@ -152,17 +130,26 @@ impl MySqlConnection {
                 // otherwise, this first packet is the start of the result-set metadata,
                 self.stream.busy = Busy::Row;
 
-                let num_columns = packet.get_uint_lenenc() as usize; // column count
+                let num_columns = packet.get_uint_lenenc()? as usize; // column count
+
+                // as with a prepare, the metadata cannot be resumed from the middle; the
+                // connection stays broken if it is not read in full
+                self.stream.broken = true;
 
-                if needs_metadata {
-                    column_names = Arc::new(recv_result_metadata(&mut self.stream, num_columns, Arc::make_mut(&mut columns)).await?);
+                let received = if needs_metadata {
+                    recv_result_metadata(&mut self.stream, num_columns, Arc::make_mut(&mut columns))
+                        .await
+                        .map(|names| column_names = Arc::new(names))
                 } else {
                     // next time we hit here, it'll be a new result set and we'll need the
                     // full metadata
                     needs_metadata = true;
 
-                    recv_result_columns(&mut self.stream, num_columns, Arc::make_mut(&mut columns)).await?;
-                }
+                    recv_result_columns(&mut self.stream, num_columns, Arc::make_mut(&mut columns)).await
+                };
+
+                received?;
+                self.stream.broken = false;
 
                 // finally, there will be none or many result-rows
                 loop {
@@ -196,6 +183,7 @@ impl MySqlConnection {
                         format,
                         columns: Arc::clone(&columns),
                         column_names: Arc::clone(&column_names),
+                        type_overrides: self.type_overrides.clone(),
                     });
 
                     logger.increment_rows();
@@ -305,6 +293,49 @@ impl<'c> Executor<'c> for &'c mut MySqlConnection {
     }
 }
 
+async fn prepare(
+    stream: &mut MySqlStream,
+    sql: &str,
+) -> Result<(u32, MySqlStatementMetadata), Error> {
+    // https://dev.mysql.com/doc/internals/en/com-stmt-prepare.html
+    // https://dev.mysql.com/doc/internals/en/com-stmt-prepare-response.html#packet-COM_STMT_PREPARE_OK
+
+    stream.send_packet(Prepare { query: sql }).await?;
+
+    let ok: PrepareOk = stream.recv().await?;
+
+    // the parameter definitions are very unreliable so we skip over them
+    // as we have little use
+
+    if ok.params > 0 {
+        for _ in 0..ok.params {
+            let _def: ColumnDefinition = stream.recv().await?;
+        }
+
+        stream.maybe_recv_eof().await?;
+    }
+
+    // the column definitions are berefit the type information from the
+    // to-be-bound parameters; we will receive the output column definitions
+    // once more on execute so we wait for that
+
+    let mut columns = Vec::new();
+
+    let column_names = if ok.columns > 0 {
+        recv_result_metadata(stream, ok.columns as usize, &mut columns).await?
+    } else {
+        Default::default()
+    };
+
+    let metadata = MySqlStatementMetadata {
+        parameters: ok.params as usize,
+        columns: Arc::new(columns),
+        column_names: Arc::new(column_names),
+    };
+
+    Ok((ok.statement_id, metadata))
+}
+
 async fn recv_result_columns(
     stream: &mut MySqlStream,
     num_columns: usize,
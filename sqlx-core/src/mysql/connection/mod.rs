@@ -1,16 +1,20 @@
 use crate::common::StatementCache;
-use crate::connection::{Connection, LogSettings};
+use crate::connection::{Capability, Connection, ConnectionState, LogSettings};
 use crate::error::Error;
+use crate::executor::Executor;
 use crate::mysql::protocol::statement::StmtClose;
-use crate::mysql::protocol::text::{Ping, Quit};
+use crate::mysql::protocol::text::{Ping, Quit, ResetConnection};
 use crate::mysql::statement::MySqlStatementMetadata;
 use crate::mysql::{MySql, MySqlConnectOptions};
 use crate::transaction::Transaction;
+use crate::type_overrides::TypeOverrides;
 use futures_core::future::BoxFuture;
 use futures_util::FutureExt;
 use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
 
 mod auth;
+mod compression;
 mod establish;
 mod executor;
 mod stream;
@@ -33,9 +37,55 @@ pub struct MySqlConnection {
     // cache by query string to the statement id and metadata
     cache_statement: StatementCache<(u32, MySqlStatementMetadata)>,
 
+    // most recently executed query, kept for diagnostics
+    pub(crate) last_sql: String,
+
+    // shared with the rows returned by this connection
+    type_overrides: Option<Arc<TypeOverrides<MySql>>>,
+
     log_settings: LogSettings,
 }
 
+impl MySqlConnection {
+    // configure the parameters of the session, once established or reset
+    pub(crate) async fn configure_session(&mut self) -> Result<(), Error> {
+        // https://mariadb.com/kb/en/sql-mode/
+
+        // PIPES_AS_CONCAT - Allows using the pipe character (ASCII 124) as string concatenation operator.
+        //                   This means that "A" || "B" can be used in place of CONCAT("A", "B").
+
+        // NO_ENGINE_SUBSTITUTION - If not set, if the available storage engine specified by a CREATE TABLE is
+        //                          not available, a warning is given and the default storage
+        //                          engine is used instead.
+
+        // NO_ZERO_DATE - Don't allow '0000-00-00'. This is invalid in Rust.
+
+        // NO_ZERO_IN_DATE - Don't allow 'YYYY-00-00'. This is invalid in Rust.
+
+        // --
+
+        // Setting the time zone allows us to assume that the output
+        // from a TIMESTAMP field is UTC
+
+        // --
+
+        // https://mathiasbynens.be/notes/mysql-utf8mb4
+
+        let mut options = String::new();
+        options.push_str(r#"SET sql_mode=(SELECT CONCAT(@@sql_mode, ',PIPES_AS_CONCAT,NO_ENGINE_SUBSTITUTION')),"#);
+        options.push_str(r#"time_zone='+00:00',"#);
+        options.push_str(&format!(
+            r#"NAMES {} COLLATE {};"#,
+            self.stream.charset.as_str(),
+            self.stream.collation.as_str()
+        ));
+
+        self.execute(&*options).await?;
+
+        Ok(())
+    }
+}
+
 impl Debug for MySqlConnection {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         f.debug_struct("MySqlConnection").finish()
@@ -59,13 +109,36 @@ impl Connection for MySqlConnection {
     fn ping(&mut self) -> BoxFuture<'_, Result<(), Error>> {
         Box::pin(async move {
             self.stream.wait_until_ready().await?;
+
+            // the OK is read by the next operation if this one is cancelled
+            self.stream.busy = Busy::Result;
             self.stream.send_packet(Ping).await?;
             self.stream.recv_ok().await?;
+            self.stream.busy = Busy::NotBusy;
 
             Ok(())
         })
     }
 
+    fn reset(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move {
+            self.stream.wait_until_ready().await?;
+
+            // the OK is read by the next operation if this one is cancelled
+            self.stream.busy = Busy::Result;
+            self.stream.send_packet(ResetConnection).await?;
+            self.stream.recv_ok().await?;
+            self.stream.busy = Busy::NotBusy;
+
+            // the server closed every prepared statement and rolled back the transaction
+            self.cache_statement.clear();
+            self.transaction_depth = 0;
+
+            // the session parameters are back to their defaults
+            self.configure_session().await
+        })
+    }
+
     #[doc(hidden)]
     fn flush(&mut self) -> BoxFuture<'_, Result<(), Error>> {
         self.stream.wait_until_ready().boxed()
@@ -75,6 +148,30 @@ impl Connection for MySqlConnection {
         self.cache_statement.len()
     }
 
+    fn set_type_overrides(&mut self, overrides: TypeOverrides<MySql>) {
+        self.type_overrides = overrides.into_shared();
+    }
+
+    fn dump_state(&self) -> ConnectionState {
+        let (major, minor, patch) = self.stream.server_version;
+
+        let mut state = ConnectionState {
+            transaction_depth: self.transaction_depth,
+            cached_statements: self.cache_statement.len(),
+            read_buffer_capacity: self.stream.rbuf_capacity(),
+            write_buffer_capacity: self.stream.wbuf.capacity(),
+            last_sql: Some(self.last_sql.clone()).filter(|sql| !sql.is_empty()),
+            ..ConnectionState::default()
+        };
+
+        state.server_parameters.insert(
+            "server_version".to_owned(),
+            format!("{}.{}.{}", major, minor, patch),
+        );
+
+        state
+    }
+
     fn clear_cached_statements(&mut self) -> BoxFuture<'_, Result<(), Error>> {
         Box::pin(async move {
             while let Some((statement_id, _)) = self.cache_statement.remove_lru() {
@@ -89,11 +186,60 @@ impl Connection for MySqlConnection {
         })
     }
 
+    fn close_cached_statement<'e>(
+        &'e mut self,
+        sql: &'e str,
+    ) -> BoxFuture<'e, Result<bool, Error>> {
+        Box::pin(async move {
+            let statement_id = match self.cache_statement.remove(sql) {
+                Some((statement_id, _)) => statement_id,
+                None => return Ok(false),
+            };
+
+            // finish reading the results of any previous query before the packet is sent
+            self.stream.wait_until_ready().await?;
+
+            self.stream
+                .send_packet(StmtClose {
+                    statement: statement_id,
+                })
+                .await?;
+
+            Ok(true)
+        })
+    }
+
     #[doc(hidden)]
     fn should_flush(&self) -> bool {
         !self.stream.wbuf.is_empty()
     }
 
+    fn received_shutdown_notice(&self) -> bool {
+        self.stream.shutdown
+    }
+
+    fn is_broken(&self) -> bool {
+        self.stream.broken
+    }
+
+    fn supports(&self, capability: Capability) -> bool {
+        let version = self.stream.server_version;
+
+        if self.stream.mariadb {
+            return match capability {
+                Capability::Returning => version >= (10, 5, 0),
+                Capability::SkipLocked => version >= (10, 6, 0),
+                Capability::JsonOperators | Capability::OnConflict => false,
+            };
+        }
+
+        match capability {
+            Capability::SkipLocked => version >= (8, 0, 1),
+            Capability::JsonOperators => version >= (5, 7, 13),
+            Capability::Returning | Capability::OnConflict => false,
+        }
+    }
+
     fn begin(&mut self) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
     where
         Self: Sized,
@@ -1,24 +1,40 @@
 use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
 
 use bytes::{Buf, Bytes};
 
 use crate::error::Error;
-use crate::io::{BufStream, Decode, Encode};
+use crate::frame::FrameSink;
+use crate::io::{BufExt, BufStream, Decode, Encode};
 use crate::mysql::collation::{CharSet, Collation};
-use crate::mysql::io::MySqlBufExt;
+use crate::mysql::connection::compression::CompressedStream;
 use crate::mysql::protocol::response::{EofPacket, ErrPacket, OkPacket, Status};
 use crate::mysql::protocol::{Capabilities, Packet};
 use crate::mysql::{MySqlConnectOptions, MySqlDatabaseError};
 use crate::net::{MaybeTlsStream, Socket};
 
 pub struct MySqlStream {
-    stream: BufStream<MaybeTlsStream<Socket>>,
+    stream: BufStream<CompressedStream<MaybeTlsStream<Socket>>>,
     pub(crate) server_version: (u16, u16, u16),
+    pub(crate) mariadb: bool,
     pub(super) capabilities: Capabilities,
     pub(crate) sequence_id: u8,
     pub(crate) busy: Busy,
     pub(crate) charset: CharSet,
     pub(crate) collation: Collation,
+
+    // packets larger than this are skipped, and streamed to the sink if any, instead of being
+    // read into memory
+    max_frame_size: Option<usize>,
+    frame_sink: Option<Arc<dyn FrameSink>>,
+
+    // set once the server has reported that it is shutting down or killed the session
+    pub(crate) shutdown: bool,
+
+    // set for the duration of an exchange with the server that cannot be resumed, such as
+    // reading the response to a prepare; if the future driving the exchange is dropped before
+    // it completes, this stays set and the connection is unusable
+    pub(crate) broken: bool,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -64,25 +80,44 @@ impl MySqlStream {
             capabilities |= Capabilities::CONNECT_WITH_DB;
         }
 
+        if options.compression {
+            capabilities |= Capabilities::COMPRESS;
+        }
+
         Ok(Self {
             busy: Busy::NotBusy,
             capabilities,
             server_version: (0, 0, 0),
+            mariadb: false,
             sequence_id: 0,
             collation,
             charset,
-            stream: BufStream::new(MaybeTlsStream::Raw(socket)),
+            stream: BufStream::new(CompressedStream::new(MaybeTlsStream::Raw(socket))),
+            max_frame_size: options.max_frame_size,
+            frame_sink: options.frame_sink.as_ref().map(|sink| Arc::clone(&sink.0)),
+            shutdown: false,
+            broken: false,
         })
     }
 
     pub(crate) async fn wait_until_ready(&mut self) -> Result<(), Error> {
+        if self.broken {
+            return Err(err_protocol!(
+                "a cancelled operation left the connection in the middle of an exchange with the server"
+            ));
+        }
+
         if !self.stream.wbuf.is_empty() {
             self.stream.flush().await?;
         }
 
         while self.busy != Busy::NotBusy {
             while self.busy == Busy::Row {
-                let packet = self.recv_packet().await?;
+                let packet = match self.recv_packet().await {
+                    // the rest of an abandoned result may hold more oversized rows, already skipped
+                    Err(Error::FrameTooLarge { .. }) => continue,
+                    packet => packet?,
+                };
 
                 if packet[0] == 0xfe && packet.len() < 9 {
                     let eof = packet.eof(self.capabilities)?;
@@ -96,7 +131,11 @@ impl MySqlStream {
             }
 
             while self.busy == Busy::Result {
-                let packet = self.recv_packet().await?;
+                let packet = match self.recv_packet().await {
+                    // the rest of an abandoned result may hold more oversized rows, already skipped
+                    Err(Error::FrameTooLarge { .. }) => continue,
+                    packet => packet?,
+                };
 
                 if packet[0] == 0x00 || packet[0] == 0xff {
                     let ok = packet.ok()?;
@@ -106,7 +145,10 @@ impl MySqlStream {
                     }
                 } else {
                     self.busy = Busy::Row;
+
+                    self.broken = true;
                     self.skip_result_metadata(packet).await?;
+                    self.broken = false;
                 }
             }
         }
@@ -114,6 +156,11 @@ impl MySqlStream {
         Ok(())
     }
 
+    // compress the packets exchanged from now on, once authentication has completed
+    pub(crate) fn enable_compression(&mut self) {
+        self.stream.enable();
+    }
+
     pub(crate) async fn send_packet<'en, T>(&mut self, payload: T) -> Result<(), Error>
     where
         T: Encode<'en, Capabilities>,
@@ -137,16 +184,30 @@ impl MySqlStream {
         // https://dev.mysql.com/doc/dev/mysql-server/8.0.12/page_protocol_basic_packets.html
         // https://mariadb.com/kb/en/library/0-packet/#standard-packet
 
-        let mut header: Bytes = self.stream.read(4).await?;
+        let mut header = self.stream.peek(4).await?;
 
         let packet_size = header.get_uint_le(3) as usize;
         let sequence_id = header.get_u8();
 
-        self.sequence_id = sequence_id.wrapping_add(1);
+        // the header is only consumed along with the payload, so that a cancelled receive
+        // leaves the stream at the start of the packet
+        if let Some(max) = self.max_frame_size.filter(|&max| packet_size > max) {
+            self.sequence_id = sequence_id.wrapping_add(1);
+            self.stream
+                .skip_frame(4, packet_size, self.frame_sink.clone())
+                .await?;
+
+            return Err(Error::FrameTooLarge {
+                size: packet_size,
+                max,
+            });
+        }
+
+        let mut payload: Bytes = self.stream.read(4 + packet_size).await?;
+        payload.advance(4);
 
-        let payload: Bytes = self.stream.read(packet_size).await?;
+        self.sequence_id = sequence_id.wrapping_add(1);
 
-        // TODO: packet compression
         // TODO: packet joining
 
         if payload[0] == 0xff {
@@ -154,9 +215,13 @@ impl MySqlStream {
 
             // instead of letting this packet be looked at everywhere, we check here
             // and emit a proper Error
-            return Err(
-                MySqlDatabaseError(ErrPacket::decode_with(payload, self.capabilities)?).into(),
-            );
+            let error = MySqlDatabaseError(ErrPacket::decode_with(payload, self.capabilities)?);
+
+            if error.is_server_shutdown() {
+                self.shutdown = true;
+            }
+
+            return Err(error.into());
         }
 
         Ok(Packet(payload))
@@ -182,7 +247,7 @@ impl MySqlStream {
     }
 
     async fn skip_result_metadata(&mut self, mut packet: Packet<Bytes>) -> Result<(), Error> {
-        let num_columns: u64 = packet.get_uint_lenenc(); // column count
+        let num_columns: u64 = packet.get_uint_lenenc()?; // column count
 
         for _ in 0..num_columns {
             let _ = self.recv_packet().await?;
@@ -195,7 +260,7 @@ impl MySqlStream {
 }
 
 impl Deref for MySqlStream {
-    type Target = BufStream<MaybeTlsStream<Socket>>;
+    type Target = BufStream<CompressedStream<MaybeTlsStream<Socket>>>;
 
     fn deref(&self) -> &Self::Target {
         &self.stream
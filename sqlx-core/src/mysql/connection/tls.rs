@@ -53,6 +53,7 @@ async fn upgrade(stream: &mut MySqlStream, options: &MySqlConnectOptions) -> Res
             accept_invalid_certs,
             accept_invalid_host_names,
             options.ssl_ca.as_deref(),
+            options.ssl_session_resumption,
         )
         .await?;
 
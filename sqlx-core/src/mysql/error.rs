@@ -27,6 +27,12 @@ impl MySqlDatabaseError {
     pub fn message(&self) -> &str {
         &self.0.error_message
     }
+
+    // the server is shutting down or has killed the session
+    pub(crate) fn is_server_shutdown(&self) -> bool {
+        // ER_SERVER_SHUTDOWN, ER_NORMAL_SHUTDOWN, ER_CONNECTION_KILLED, ER_SESSION_WAS_KILLED
+        matches!(self.number(), 1053 | 1077 | 1927 | 3169)
+    }
 }
 
 impl Debug for MySqlDatabaseError {
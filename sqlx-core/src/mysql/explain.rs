@@ -0,0 +1,103 @@
+use futures_core::future::BoxFuture;
+
+use crate::error::Error;
+use crate::executor::{Execute, Executor};
+use crate::explain::{Access, Explain, PlanStep, QueryPlan};
+use crate::mysql::{MySql, MySqlConnection};
+use crate::row::Row;
+
+impl Explain for MySqlConnection {
+    fn explain<'e, 'q: 'e, E>(&'e mut self, mut query: E) -> BoxFuture<'e, Result<QueryPlan, Error>>
+    where
+        E: 'q + Execute<'q, MySql>,
+    {
+        let sql = format!("EXPLAIN {}", query.sql());
+        let arguments = query.take_arguments();
+
+        Box::pin(async move {
+            let rows = self.fetch_all((&*sql, arguments)).await?;
+            let mut steps = Vec::with_capacity(rows.len());
+
+            // the columns of the plan are not consistently typed across MySQL and MariaDB
+            for row in rows {
+                steps.push(plan_step(
+                    row.try_get_unchecked("table")?,
+                    row.try_get_unchecked("type")?,
+                    row.try_get_unchecked("key")?,
+                    row.try_get_unchecked("Extra")?,
+                ));
+            }
+
+            Ok(QueryPlan::new(steps))
+        })
+    }
+}
+
+// each row of the plan is a table read with a join type, such as `ALL` for a full table scan
+// or `ref` for a lookup through the index in `key`; `index` reads every entry of the index in
+// `key` and is a full scan as well
+fn plan_step(
+    table: Option<String>,
+    join_type: Option<String>,
+    key: Option<String>,
+    extra: Option<String>,
+) -> PlanStep {
+    let access = match (join_type.as_deref(), &key) {
+        (Some("ALL"), _) | (Some("index"), _) => Access::FullScan,
+        (_, Some(_)) => Access::Index,
+        _ => Access::Other,
+    };
+
+    let mut detail = format!(
+        "table: {}, type: {}, key: {}",
+        table.as_deref().unwrap_or("NULL"),
+        join_type.as_deref().unwrap_or("NULL"),
+        key.as_deref().unwrap_or("NULL")
+    );
+
+    if let Some(extra) = extra.filter(|extra| !extra.is_empty()) {
+        detail.push_str(", extra: ");
+        detail.push_str(&extra);
+    }
+
+    PlanStep {
+        access,
+        table,
+        index: key,
+        detail,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::plan_step;
+    use crate::explain::Access;
+
+    #[test]
+    fn it_classifies_join_types() {
+        let s = |s: &str| Some(s.to_owned());
+
+        let scan = plan_step(s("orders"), s("ALL"), None, s("Using where"));
+
+        assert_eq!(scan.access(), Access::FullScan);
+        assert_eq!(scan.table(), Some("orders"));
+        assert_eq!(
+            scan.detail(),
+            "table: orders, type: ALL, key: NULL, extra: Using where"
+        );
+
+        let lookup = plan_step(s("users"), s("eq_ref"), s("PRIMARY"), None);
+
+        assert_eq!(lookup.access(), Access::Index);
+        assert_eq!(lookup.index(), Some("PRIMARY"));
+
+        let index_scan = plan_step(s("users"), s("index"), s("users_email"), s("Using index"));
+
+        assert_eq!(index_scan.access(), Access::FullScan);
+        assert_eq!(index_scan.index(), Some("users_email"));
+
+        let no_table = plan_step(None, None, None, s("No tables used"));
+
+        assert_eq!(no_table.access(), Access::Other);
+    }
+}
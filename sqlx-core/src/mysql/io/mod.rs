@@ -1,5 +0,0 @@
-mod buf;
-mod buf_mut;
-
-pub use buf::MySqlBufExt;
-pub use buf_mut::MySqlBufMutExt;
@@ -7,12 +7,13 @@ mod connection;
 mod database;
 mod done;
 mod error;
-mod io;
+mod explain;
 mod options;
 mod protocol;
 mod row;
 mod statement;
 mod transaction;
+mod two_phase;
 mod type_info;
 pub mod types;
 mod value;
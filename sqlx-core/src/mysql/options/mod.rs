@@ -1,10 +1,12 @@
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 mod connect;
 mod parse;
 mod ssl_mode;
 
 use crate::connection::LogSettings;
+use crate::frame::{FrameSink, SharedFrameSink};
 pub use ssl_mode::MySqlSslMode;
 
 /// Options and flags which can be used to configure a MySQL connection.
@@ -63,6 +65,10 @@ pub struct MySqlConnectOptions {
     pub(crate) database: Option<String>,
     pub(crate) ssl_mode: MySqlSslMode,
     pub(crate) ssl_ca: Option<PathBuf>,
+    pub(crate) ssl_session_resumption: bool,
+    pub(crate) compression: bool,
+    pub(crate) max_frame_size: Option<usize>,
+    pub(crate) frame_sink: Option<SharedFrameSink>,
     pub(crate) statement_cache_capacity: usize,
     pub(crate) charset: String,
     pub(crate) collation: Option<String>,
@@ -89,6 +95,10 @@ impl MySqlConnectOptions {
             collation: None,
             ssl_mode: MySqlSslMode::Preferred,
             ssl_ca: None,
+            ssl_session_resumption: false,
+            compression: false,
+            max_frame_size: None,
+            frame_sink: None,
             statement_cache_capacity: 100,
             log_settings: Default::default(),
         }
@@ -171,6 +181,94 @@ impl MySqlConnectOptions {
         self
     }
 
+    /// Sets whether TLS sessions may be resumed when reconnecting to the server.
+    ///
+    /// Resuming a session skips most of the TLS handshake, which saves a round trip and the
+    /// cost of verifying the server certificate on every new connection. Sessions are only
+    /// shared between connections that verify the server in the same way.
+    ///
+    /// Only supported with the `rustls` TLS backend: with `native-tls`, which does not let the
+    /// session cache be controlled, upgrading the connection to TLS fails when this is enabled.
+    ///
+    /// The default is `false`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_core::mysql::{MySqlSslMode, MySqlConnectOptions};
+    /// let options = MySqlConnectOptions::new()
+    ///     .ssl_mode(MySqlSslMode::Required)
+    ///     .ssl_session_resumption(true);
+    /// ```
+    pub fn ssl_session_resumption(mut self, enabled: bool) -> Self {
+        self.ssl_session_resumption = enabled;
+        self
+    }
+
+    /// Sets whether to compress the traffic with the server, if the server supports it.
+    ///
+    /// Packets are compressed with zlib once the connection is established, trading CPU time
+    /// on both ends for bandwidth. This pays off over slow links and for large results, rather
+    /// than on a local network.
+    ///
+    /// The default is `false`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_core::mysql::MySqlConnectOptions;
+    /// let options = MySqlConnectOptions::new()
+    ///     .compression(true);
+    /// ```
+    pub fn compression(mut self, enabled: bool) -> Self {
+        self.compression = enabled;
+        self
+    }
+
+    /// Sets the maximum size, in bytes, of a single packet from the server.
+    ///
+    /// A larger packet, typically a row holding a huge value, is read off the connection in
+    /// small chunks instead of being buffered in memory. The chunks are streamed to the
+    /// [`frame_sink`][Self::frame_sink], if one is set, and discarded otherwise. The query then
+    /// fails with [`Error::FrameTooLarge`] and the connection remains usable.
+    ///
+    /// By default, the size of packets is only limited by the protocol, to 16 MiB.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_core::mysql::MySqlConnectOptions;
+    /// let options = MySqlConnectOptions::new()
+    ///     .max_frame_size(4 * 1024 * 1024);
+    /// ```
+    ///
+    /// [`Error::FrameTooLarge`]: crate::error::Error::FrameTooLarge
+    pub fn max_frame_size(mut self, bytes: usize) -> Self {
+        self.max_frame_size = Some(bytes);
+        self
+    }
+
+    /// Sets the sink receiving the packets larger than the
+    /// [`max_frame_size`][Self::max_frame_size], in chunks, as they are read.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_core::mysql::MySqlConnectOptions;
+    /// # use std::sync::atomic::{AtomicUsize, Ordering};
+    /// let streamed = AtomicUsize::new(0);
+    ///
+    /// let options = MySqlConnectOptions::new()
+    ///     .max_frame_size(4 * 1024 * 1024)
+    ///     .frame_sink(move |chunk: &[u8]| {
+    ///         streamed.fetch_add(chunk.len(), Ordering::Relaxed);
+    ///     });
+    /// ```
+    pub fn frame_sink(mut self, sink: impl FrameSink) -> Self {
+        self.frame_sink = Some(SharedFrameSink(Arc::new(sink)));
+        self
+    }
+
     /// Sets the capacity of the connection's statement cache in a number of stored
     /// distinct statements. Caching is handled using LRU, meaning when the
     /// amount of queries hits the defined limit, the oldest statement will get
@@ -24,24 +24,28 @@ pub(crate) struct Handshake {
 
 impl Decode<'_> for Handshake {
     fn decode_with(mut buf: Bytes, _: ()) -> Result<Self, Error> {
-        let protocol_version = buf.get_u8(); // int<1>
+        let protocol_version = buf.try_get_u8()?; // int<1>
         let server_version = buf.get_str_nul()?; // string<NUL>
-        let connection_id = buf.get_u32_le(); // int<4>
-        let auth_plugin_data_1 = buf.get_bytes(8); // string<8>
+
+        let connection_id = buf.try_get_u32_le()?; // int<4>
+        let auth_plugin_data_1 = buf.get_bytes(8)?; // string<8>
+
+        // the fixed-size fields up to the second part of the auth plugin data
+        buf.ensure_remaining(1 + 2 + 1 + 2 + 2 + 1 + 6 + 4)?;
 
         buf.advance(1); // reserved: string<1>
 
-        let capabilities_1 = buf.get_u16_le(); // int<2>
+        let capabilities_1 = buf.try_get_u16_le()?; // int<2>
         let mut capabilities = Capabilities::from_bits_truncate(capabilities_1.into());
 
-        let collation = buf.get_u8(); // int<1>
-        let status = Status::from_bits_truncate(buf.get_u16_le());
+        let collation = buf.try_get_u8()?; // int<1>
+        let status = Status::from_bits_truncate(buf.try_get_u16_le()?);
 
-        let capabilities_2 = buf.get_u16_le(); // int<2>
+        let capabilities_2 = buf.try_get_u16_le()?; // int<2>
         capabilities |= Capabilities::from_bits_truncate(((capabilities_2 as u32) << 16).into());
 
         let auth_plugin_data_len = if capabilities.contains(Capabilities::PLUGIN_AUTH) {
-            buf.get_u8()
+            buf.try_get_u8()?
         } else {
             buf.advance(1); // int<1>
             0
@@ -52,13 +56,15 @@ impl Decode<'_> for Handshake {
         if capabilities.contains(Capabilities::MYSQL) {
             buf.advance(4); // reserved: string<4>
         } else {
-            let capabilities_3 = buf.get_u32_le(); // int<4>
+            let capabilities_3 = buf.try_get_u32_le()?; // int<4>
             capabilities |= Capabilities::from_bits_truncate((capabilities_3 as u64) << 32);
         }
 
         let auth_plugin_data_2 = if capabilities.contains(Capabilities::SECURE_CONNECTION) {
             let len = ((auth_plugin_data_len as isize) - 9).max(12) as usize;
-            let v = buf.get_bytes(len);
+            buf.ensure_remaining(len + 1)?;
+
+            let v = buf.get_bytes(len)?;
             buf.advance(1); // NUL-terminator
 
             v
@@ -191,3 +197,12 @@ fn test_decode_handshake_mariadb_10_4_7() {
         &[116, 54, 76, 92, 106, 34, 100, 83, 85, 49, 52, 79, 112, 104, 57, 34, 60, 72, 53, 110,]
     );
 }
+
+#[test]
+fn test_decode_truncated_handshake() {
+    const HANDSHAKE_MYSQL_8_0_18: &[u8] = b"\n8.0.18\x00\x19\x00\x00\x00\x114aB0c\x06g\x00\xff\xff\xff\x02\x00\xff\xc7\x15\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00tL\x03s\x0f[4\rl4. \x00caching_sha2_password\x00";
+
+    for len in 0..HANDSHAKE_MYSQL_8_0_18.len() {
+        assert!(Handshake::decode(HANDSHAKE_MYSQL_8_0_18[..len].into()).is_err());
+    }
+}
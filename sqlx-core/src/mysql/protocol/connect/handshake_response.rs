@@ -1,5 +1,4 @@
 use crate::io::{BufMutExt, Encode};
-use crate::mysql::io::MySqlBufMutExt;
 use crate::mysql::protocol::auth::AuthPlugin;
 use crate::mysql::protocol::connect::ssl_request::SslRequest;
 use crate::mysql::protocol::Capabilities;
@@ -1,7 +1,7 @@
-use bytes::{Buf, Bytes};
+use bytes::Bytes;
 
 use crate::error::Error;
-use crate::io::Decode;
+use crate::io::{BufExt, Decode};
 use crate::mysql::protocol::response::Status;
 use crate::mysql::protocol::Capabilities;
 
@@ -19,7 +19,7 @@ pub struct EofPacket {
 
 impl Decode<'_, Capabilities> for EofPacket {
     fn decode_with(mut buf: Bytes, _: Capabilities) -> Result<Self, Error> {
-        let header = buf.get_u8();
+        let header = buf.try_get_u8()?;
         if header != 0xfe {
             return Err(err_protocol!(
                 "expected 0xfe (EOF_Packet) but found 0x{:x}",
@@ -27,9 +27,23 @@ impl Decode<'_, Capabilities> for EofPacket {
             ));
         }
 
-        let warnings = buf.get_u16_le();
-        let status = Status::from_bits_truncate(buf.get_u16_le());
+        let warnings = buf.try_get_u16_le()?;
+        let status = Status::from_bits_truncate(buf.try_get_u16_le()?);
 
         Ok(Self { status, warnings })
     }
 }
+
+#[test]
+fn test_decode_truncated_eof_packet() {
+    const DATA: &[u8] = b"\xfe\x00\x00\x02\x00";
+
+    let p = EofPacket::decode_with(DATA.into(), Capabilities::empty()).unwrap();
+
+    assert_eq!(p.warnings, 0);
+    assert!(p.status.contains(Status::SERVER_STATUS_AUTOCOMMIT));
+
+    for len in 0..DATA.len() {
+        assert!(EofPacket::decode_with(DATA[..len].into(), Capabilities::empty()).is_err());
+    }
+}
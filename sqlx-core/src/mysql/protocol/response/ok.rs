@@ -1,8 +1,7 @@
-use bytes::{Buf, Bytes};
+use bytes::Bytes;
 
 use crate::error::Error;
-use crate::io::Decode;
-use crate::mysql::io::MySqlBufExt;
+use crate::io::{BufExt, Decode};
 use crate::mysql::protocol::response::Status;
 
 /// Indicates successful completion of a previous command sent by the client.
@@ -16,7 +15,7 @@ pub struct OkPacket {
 
 impl Decode<'_> for OkPacket {
     fn decode_with(mut buf: Bytes, _: ()) -> Result<Self, Error> {
-        let header = buf.get_u8();
+        let header = buf.try_get_u8()?;
         if header != 0 && header != 0xfe {
             return Err(err_protocol!(
                 "expected 0x00 or 0xfe (OK_Packet) but found 0x{:02x}",
@@ -24,10 +23,11 @@ impl Decode<'_> for OkPacket {
             ));
         }
 
-        let affected_rows = buf.get_uint_lenenc();
-        let last_insert_id = buf.get_uint_lenenc();
-        let status = Status::from_bits_truncate(buf.get_u16_le());
-        let warnings = buf.get_u16_le();
+        let affected_rows = buf.get_uint_lenenc()?;
+        let last_insert_id = buf.get_uint_lenenc()?;
+
+        let status = Status::from_bits_truncate(buf.try_get_u16_le()?);
+        let warnings = buf.try_get_u16_le()?;
 
         Ok(Self {
             affected_rows,
@@ -50,3 +50,12 @@ fn test_decode_ok_packet() {
     assert!(p.status.contains(Status::SERVER_STATUS_AUTOCOMMIT));
     assert!(p.status.contains(Status::SERVER_SESSION_STATE_CHANGED));
 }
+
+#[test]
+fn test_decode_truncated_ok_packet() {
+    const DATA: &[u8] = b"\x00\x00\x00\x02@\x00\x00";
+
+    for len in 0..DATA.len() {
+        assert!(OkPacket::decode(DATA[..len].into()).is_err());
+    }
+}
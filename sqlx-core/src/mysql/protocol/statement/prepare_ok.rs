@@ -1,7 +1,7 @@
-use bytes::{Buf, Bytes};
+use bytes::Bytes;
 
 use crate::error::Error;
-use crate::io::Decode;
+use crate::io::{BufExt, Decode};
 use crate::mysql::protocol::Capabilities;
 
 // https://dev.mysql.com/doc/internals/en/com-stmt-prepare-response.html#packet-COM_STMT_PREPARE_OK
@@ -16,7 +16,7 @@ pub(crate) struct PrepareOk {
 
 impl Decode<'_, Capabilities> for PrepareOk {
     fn decode_with(mut buf: Bytes, _: Capabilities) -> Result<Self, Error> {
-        let status = buf.get_u8();
+        let status = buf.try_get_u8()?;
         if status != 0x00 {
             return Err(err_protocol!(
                 "expected 0x00 (COM_STMT_PREPARE_OK) but found 0x{:02x}",
@@ -24,13 +24,13 @@ impl Decode<'_, Capabilities> for PrepareOk {
             ));
         }
 
-        let statement_id = buf.get_u32_le();
-        let columns = buf.get_u16_le();
-        let params = buf.get_u16_le();
+        let statement_id = buf.try_get_u32_le()?;
+        let columns = buf.try_get_u16_le()?;
+        let params = buf.try_get_u16_le()?;
 
-        buf.advance(1); // reserved: string<1>
+        buf.try_get_u8()?; // reserved: string<1>
 
-        let warnings = buf.get_u16_le();
+        let warnings = buf.try_get_u16_le()?;
 
         Ok(Self {
             statement_id,
@@ -40,3 +40,18 @@ impl Decode<'_, Capabilities> for PrepareOk {
         })
     }
 }
+
+#[test]
+fn test_decode_truncated_prepare_ok() {
+    const DATA: &[u8] = b"\x00\x01\x00\x00\x00\x02\x00\x01\x00\x00\x00\x00";
+
+    let p = PrepareOk::decode_with(DATA.into(), Capabilities::empty()).unwrap();
+
+    assert_eq!(p.statement_id, 1);
+    assert_eq!(p.columns, 2);
+    assert_eq!(p.params, 1);
+
+    for len in 0..DATA.len() {
+        assert!(PrepareOk::decode_with(DATA[..len].into(), Capabilities::empty()).is_err());
+    }
+}
@@ -2,7 +2,6 @@ use bytes::{Buf, Bytes};
 
 use crate::error::Error;
 use crate::io::{BufExt, Decode};
-use crate::mysql::io::MySqlBufExt;
 use crate::mysql::protocol::text::ColumnType;
 use crate::mysql::protocol::Row;
 use crate::mysql::MySqlColumn;
@@ -15,7 +14,7 @@ pub(crate) struct BinaryRow(pub(crate) Row);
 
 impl<'de> Decode<'de, &'de [MySqlColumn]> for BinaryRow {
     fn decode_with(mut buf: Bytes, columns: &'de [MySqlColumn]) -> Result<Self, Error> {
-        let header = buf.get_u8();
+        let header = buf.try_get_u8()?;
         if header != 0 {
             return Err(err_protocol!(
                 "exepcted 0x00 (ROW) but found 0x{:02x}",
@@ -27,7 +26,7 @@ impl<'de> Decode<'de, &'de [MySqlColumn]> for BinaryRow {
         let offset = buf.len();
 
         let null_bitmap_len = (columns.len() + 9) / 8;
-        let null_bitmap = buf.get_bytes(null_bitmap_len);
+        let null_bitmap = buf.get_bytes(null_bitmap_len)?;
 
         let mut values = Vec::with_capacity(columns.len());
 
@@ -59,7 +58,7 @@ impl<'de> Decode<'de, &'de [MySqlColumn]> for BinaryRow {
                 | ColumnType::Bit
                 | ColumnType::Decimal
                 | ColumnType::Json
-                | ColumnType::NewDecimal => buf.get_uint_lenenc() as usize,
+                | ColumnType::NewDecimal => buf.get_uint_lenenc()? as usize,
 
                 ColumnType::LongLong => 8,
                 ColumnType::Long | ColumnType::Int24 => 4,
@@ -73,6 +72,7 @@ impl<'de> Decode<'de, &'de [MySqlColumn]> for BinaryRow {
                 | ColumnType::Date
                 | ColumnType::Datetime => {
                     // The size of this type is important for decoding
+                    buf.ensure_remaining(1)?;
                     buf[0] as usize + 1
                 }
 
@@ -80,6 +80,8 @@ impl<'de> Decode<'de, &'de [MySqlColumn]> for BinaryRow {
                 ColumnType::Null => unreachable!(),
             };
 
+            buf.ensure_remaining(size)?;
+
             let offset = offset - buf.len();
 
             values.push(Some(offset..(offset + size)));
@@ -1,11 +1,10 @@
 use std::str::from_utf8;
 
 use bitflags::bitflags;
-use bytes::{Buf, Bytes};
+use bytes::Bytes;
 
 use crate::error::Error;
-use crate::io::Decode;
-use crate::mysql::io::MySqlBufExt;
+use crate::io::{BufExt, Decode};
 use crate::mysql::protocol::Capabilities;
 
 // https://dev.mysql.com/doc/dev/mysql-server/8.0.12/group__group__cs__column__definition__flags.html
@@ -129,18 +128,19 @@ impl ColumnDefinition {
 
 impl Decode<'_, Capabilities> for ColumnDefinition {
     fn decode_with(mut buf: Bytes, _: Capabilities) -> Result<Self, Error> {
-        let catalog = buf.get_bytes_lenenc();
-        let schema = buf.get_bytes_lenenc();
-        let table_alias = buf.get_bytes_lenenc();
-        let table = buf.get_bytes_lenenc();
-        let alias = buf.get_bytes_lenenc();
-        let name = buf.get_bytes_lenenc();
-        let _next_len = buf.get_uint_lenenc(); // always 0x0c
-        let char_set = buf.get_u16_le();
-        let max_size = buf.get_u32_le();
-        let type_id = buf.get_u8();
-        let flags = buf.get_u16_le();
-        let decimals = buf.get_u8();
+        let catalog = buf.get_bytes_lenenc()?;
+        let schema = buf.get_bytes_lenenc()?;
+        let table_alias = buf.get_bytes_lenenc()?;
+        let table = buf.get_bytes_lenenc()?;
+        let alias = buf.get_bytes_lenenc()?;
+        let name = buf.get_bytes_lenenc()?;
+        let _next_len = buf.get_uint_lenenc()?; // always 0x0c
+
+        let char_set = buf.try_get_u16_le()?;
+        let max_size = buf.try_get_u32_le()?;
+        let type_id = buf.try_get_u8()?;
+        let flags = buf.try_get_u16_le()?;
+        let decimals = buf.try_get_u8()?;
 
         Ok(Self {
             catalog,
@@ -257,3 +257,18 @@ impl ColumnType {
         })
     }
 }
+
+#[test]
+fn test_decode_truncated_column_definition() {
+    // `def`.``.``.`` AS `x` (`x`), a BIGINT in the binary character set
+    const DATA: &[u8] = b"\x03def\x00\x00\x00\x01x\x01x\x0c?\x00\x14\x00\x00\x00\x08\x00\x00\x00";
+
+    let column = ColumnDefinition::decode_with(DATA.into(), Capabilities::empty()).unwrap();
+
+    assert_eq!(column.name().unwrap(), "x");
+    assert_eq!(column.r#type, ColumnType::LongLong);
+
+    for len in 0..DATA.len() {
+        assert!(ColumnDefinition::decode_with(DATA[..len].into(), Capabilities::empty()).is_err());
+    }
+}
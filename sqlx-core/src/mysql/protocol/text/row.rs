@@ -1,8 +1,7 @@
 use bytes::{Buf, Bytes};
 
 use crate::error::Error;
-use crate::io::Decode;
-use crate::mysql::io::MySqlBufExt;
+use crate::io::{BufExt, Decode};
 use crate::mysql::protocol::Row;
 use crate::mysql::MySqlColumn;
 
@@ -17,12 +16,16 @@ impl<'de> Decode<'de, &'de [MySqlColumn]> for TextRow {
         let mut values = Vec::with_capacity(columns.len());
 
         for _ in columns {
+            buf.ensure_remaining(1)?;
+
             if buf[0] == 0xfb {
                 // NULL is sent as 0xfb
                 values.push(None);
                 buf.advance(1);
             } else {
-                let size = buf.get_uint_lenenc() as usize;
+                let size = buf.get_uint_lenenc()? as usize;
+                buf.ensure_remaining(size)?;
+
                 let offset = offset - buf.len();
 
                 values.push(Some(offset..(offset + size)));
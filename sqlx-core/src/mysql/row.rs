@@ -3,6 +3,7 @@ use crate::error::Error;
 use crate::ext::ustr::UStr;
 use crate::mysql::{protocol, MySql, MySqlColumn, MySqlValueFormat, MySqlValueRef};
 use crate::row::Row;
+use crate::type_overrides::TypeOverrides;
 use crate::HashMap;
 use std::sync::Arc;
 
@@ -13,9 +14,14 @@ pub struct MySqlRow {
     pub(crate) format: MySqlValueFormat,
     pub(crate) columns: Arc<Vec<MySqlColumn>>,
     pub(crate) column_names: Arc<HashMap<UStr, usize>>,
+    pub(crate) type_overrides: Option<Arc<TypeOverrides<MySql>>>,
 }
 
-impl crate::row::private_row::Sealed for MySqlRow {}
+impl crate::row::private_row::Sealed for MySqlRow {
+    fn data_len(&self) -> usize {
+        self.row.storage.len()
+    }
+}
 
 impl Row for MySqlRow {
     type Database = MySql;
@@ -37,8 +43,13 @@ impl Row for MySqlRow {
             row: Some(&self.row.storage),
             type_info: column.type_info.clone(),
             value,
+            type_overrides: self.type_overrides.as_ref(),
         })
     }
+
+    fn type_overrides(&self) -> Option<&TypeOverrides<MySql>> {
+        self.type_overrides.as_deref()
+    }
 }
 
 impl ColumnIndex<MySqlRow> for &'_ str {
@@ -58,6 +69,7 @@ impl From<MySqlRow> for crate::any::AnyRow {
             columns: row.columns.iter().map(|col| col.clone().into()).collect(),
 
             kind: crate::any::row::AnyRowKind::MySql(row),
+            type_overrides: None,
         }
     }
 }
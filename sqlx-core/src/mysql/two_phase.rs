@@ -0,0 +1,86 @@
+use futures_core::future::BoxFuture;
+use futures_util::TryStreamExt;
+
+use crate::error::Error;
+use crate::executor::Executor;
+use crate::mysql::MySqlConnection;
+use crate::row::Row;
+use crate::two_phase::TwoPhase;
+
+// transaction ids are string literals in XA statements and cannot be bound; a hex literal
+// is not affected by the SQL mode or character set of the connection
+fn quote(xid: &str) -> String {
+    format!("X'{}'", hex::encode(xid))
+}
+
+impl TwoPhase for MySqlConnection {
+    fn begin_branch<'e>(&'e mut self, xid: &'e str) -> BoxFuture<'e, Result<(), Error>> {
+        Box::pin(async move {
+            self.execute(&*format!("XA START {}", quote(xid))).await?;
+
+            Ok(())
+        })
+    }
+
+    fn prepare_branch<'e>(&'e mut self, xid: &'e str) -> BoxFuture<'e, Result<(), Error>> {
+        Box::pin(async move {
+            self.execute(&*format!("XA END {}", quote(xid))).await?;
+            self.execute(&*format!("XA PREPARE {}", quote(xid))).await?;
+
+            Ok(())
+        })
+    }
+
+    fn abort_branch<'e>(&'e mut self, xid: &'e str) -> BoxFuture<'e, Result<(), Error>> {
+        Box::pin(async move {
+            // the branch may have been ended already by a failed `prepare_branch`
+            let _ = self.execute(&*format!("XA END {}", quote(xid))).await;
+            self.execute(&*format!("XA ROLLBACK {}", quote(xid)))
+                .await?;
+
+            Ok(())
+        })
+    }
+
+    fn commit_prepared<'e>(&'e mut self, xid: &'e str) -> BoxFuture<'e, Result<(), Error>> {
+        Box::pin(async move {
+            self.execute(&*format!("XA COMMIT {}", quote(xid))).await?;
+
+            Ok(())
+        })
+    }
+
+    fn rollback_prepared<'e>(&'e mut self, xid: &'e str) -> BoxFuture<'e, Result<(), Error>> {
+        Box::pin(async move {
+            self.execute(&*format!("XA ROLLBACK {}", quote(xid)))
+                .await?;
+
+            Ok(())
+        })
+    }
+
+    fn prepared_branches(&mut self) -> BoxFuture<'_, Result<Vec<String>, Error>> {
+        Box::pin(async move {
+            let mut branches = Vec::new();
+            let mut rows = self.fetch("XA RECOVER");
+
+            while let Some(row) = rows.try_next().await? {
+                // `data` holds the global transaction id followed by the branch qualifier,
+                // which is always empty for the branches started by `begin_branch`
+                let gtrid_length: i64 = row.try_get("gtrid_length")?;
+                let bqual_length: i64 = row.try_get("bqual_length")?;
+                let data: Vec<u8> = row.try_get("data")?;
+
+                if bqual_length != 0 {
+                    continue;
+                }
+
+                if let Some(gtrid) = data.get(..gtrid_length as usize) {
+                    branches.push(String::from_utf8_lossy(gtrid).into_owned());
+                }
+            }
+
+            Ok(branches)
+        })
+    }
+}
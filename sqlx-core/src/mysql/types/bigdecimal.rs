@@ -3,7 +3,7 @@ use bigdecimal::BigDecimal;
 use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
-use crate::mysql::io::MySqlBufMutExt;
+use crate::io::BufMutExt;
 use crate::mysql::protocol::text::ColumnType;
 use crate::mysql::{MySql, MySqlTypeInfo, MySqlValueRef};
 use crate::types::Type;
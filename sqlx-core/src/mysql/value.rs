@@ -1,10 +1,12 @@
 use crate::error::{BoxDynError, UnexpectedNullError};
 use crate::mysql::protocol::text::ColumnType;
 use crate::mysql::{MySql, MySqlTypeInfo};
+use crate::type_overrides::TypeOverrides;
 use crate::value::{Value, ValueRef};
 use bytes::Bytes;
 use std::borrow::Cow;
 use std::str::from_utf8;
+use std::sync::Arc;
 
 #[derive(Debug, Clone, Copy)]
 #[repr(u8)]
@@ -19,6 +21,7 @@ pub struct MySqlValue {
     value: Option<Bytes>,
     type_info: MySqlTypeInfo,
     format: MySqlValueFormat,
+    type_overrides: Option<Arc<TypeOverrides<MySql>>>,
 }
 
 /// Implementation of [`ValueRef`] for MySQL.
@@ -28,6 +31,7 @@ pub struct MySqlValueRef<'r> {
     pub(crate) row: Option<&'r Bytes>,
     pub(crate) type_info: MySqlTypeInfo,
     pub(crate) format: MySqlValueFormat,
+    pub(crate) type_overrides: Option<&'r Arc<TypeOverrides<MySql>>>,
 }
 
 impl<'r> MySqlValueRef<'r> {
@@ -56,6 +60,7 @@ impl Value for MySqlValue {
             row: None,
             type_info: self.type_info.clone(),
             format: self.format,
+            type_overrides: self.type_overrides.as_ref(),
         }
     }
 
@@ -66,6 +71,10 @@ impl Value for MySqlValue {
     fn is_null(&self) -> bool {
         is_null(self.value.as_deref(), &self.type_info)
     }
+
+    fn type_overrides(&self) -> Option<&TypeOverrides<MySql>> {
+        self.type_overrides.as_deref()
+    }
 }
 
 impl<'r> ValueRef<'r> for MySqlValueRef<'r> {
@@ -84,6 +93,7 @@ impl<'r> ValueRef<'r> for MySqlValueRef<'r> {
             value,
             format: self.format,
             type_info: self.type_info.clone(),
+            type_overrides: self.type_overrides.cloned(),
         }
     }
 
@@ -104,6 +114,7 @@ impl<'r> From<MySqlValueRef<'r>> for crate::any::AnyValueRef<'r> {
         crate::any::AnyValueRef {
             type_info: value.type_info.clone().into(),
             kind: crate::any::value::AnyValueRefKind::MySql(value),
+            type_overrides: None,
         }
     }
 }
@@ -115,6 +126,7 @@ impl From<MySqlValue> for crate::any::AnyValue {
         crate::any::AnyValue {
             type_info: value.type_info.clone().into(),
             kind: crate::any::value::AnyValueKind::MySql(value),
+            type_overrides: None,
         }
     }
 }
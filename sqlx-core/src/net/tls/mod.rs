@@ -38,11 +38,13 @@ where
         accept_invalid_certs: bool,
         accept_invalid_hostnames: bool,
         root_cert_path: Option<&Path>,
+        session_resumption: bool,
     ) -> Result<(), Error> {
         let connector = configure_tls_connector(
             accept_invalid_certs,
             accept_invalid_hostnames,
             root_cert_path,
+            session_resumption,
         )
         .await?;
 
@@ -75,12 +77,20 @@ async fn configure_tls_connector(
     accept_invalid_certs: bool,
     accept_invalid_hostnames: bool,
     root_cert_path: Option<&Path>,
+    session_resumption: bool,
 ) -> Result<sqlx_rt::TlsConnector, Error> {
     use sqlx_rt::{
         fs,
         native_tls::{Certificate, TlsConnector},
     };
 
+    // native-tls does not expose control over session caching
+    if session_resumption {
+        return Err(Error::Configuration(
+            "TLS session resumption is only supported with the `rustls` TLS backend".into(),
+        ));
+    }
+
     let mut builder = TlsConnector::builder();
     builder
         .danger_accept_invalid_certs(accept_invalid_certs)
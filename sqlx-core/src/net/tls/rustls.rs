@@ -1,21 +1,47 @@
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
 use rustls::{
-    Certificate, ClientConfig, RootCertStore, ServerCertVerified, ServerCertVerifier, TLSError,
-    WebPKIVerifier,
+    Certificate, ClientConfig, ClientSessionMemoryCache, NoClientSessionStorage, RootCertStore,
+    ServerCertVerified, ServerCertVerifier, TLSError, WebPKIVerifier,
 };
 use sqlx_rt::fs;
+use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::{io::Cursor, path::Path};
 use webpki::DNSNameRef;
 
 use crate::error::Error;
 
+// the maximum number of sessions remembered by each cache
+const SESSION_CACHE_SIZE: usize = 256;
+
+// a resumed session skips certificate verification, so sessions are only shared between
+// connections that verify the server in the same way
+type SessionCacheKey = (bool, bool, Option<PathBuf>);
+
+static SESSION_CACHES: Lazy<Mutex<HashMap<SessionCacheKey, Arc<ClientSessionMemoryCache>>>> =
+    Lazy::new(Default::default);
+
 pub async fn configure_tls_connector(
     accept_invalid_certs: bool,
     accept_invalid_hostnames: bool,
     root_cert_path: Option<&Path>,
+    session_resumption: bool,
 ) -> Result<sqlx_rt::TlsConnector, Error> {
     let mut config = ClientConfig::new();
 
+    if session_resumption {
+        config.set_persistence(session_cache((
+            accept_invalid_certs,
+            accept_invalid_hostnames,
+            root_cert_path.map(Path::to_path_buf),
+        )));
+    } else {
+        config.set_persistence(Arc::new(NoClientSessionStorage {}));
+        config.enable_tickets = false;
+    }
+
     if accept_invalid_certs {
         config
             .dangerous()
@@ -43,6 +69,15 @@ pub async fn configure_tls_connector(
     Ok(Arc::new(config).into())
 }
 
+// the session cache shared by the connections configured with `key`
+fn session_cache(key: SessionCacheKey) -> Arc<ClientSessionMemoryCache> {
+    SESSION_CACHES
+        .lock()
+        .entry(key)
+        .or_insert_with(|| ClientSessionMemoryCache::new(SESSION_CACHE_SIZE))
+        .clone()
+}
+
 struct DummyTlsVerifier;
 
 impl ServerCertVerifier for DummyTlsVerifier {
@@ -76,3 +111,61 @@ impl ServerCertVerifier for NoHostnameTlsVerifier {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::session_cache;
+    use rustls::StoresClientSessions;
+    use std::path::PathBuf;
+    use std::sync::Arc;
+
+    #[test]
+    fn it_shares_session_caches_between_identical_configurations() {
+        let root = Some(PathBuf::from("/tmp/sqlx-test-ca.pem"));
+
+        let cache = session_cache((false, false, root.clone()));
+
+        assert!(Arc::ptr_eq(&cache, &session_cache((false, false, root))));
+    }
+
+    #[test]
+    fn it_separates_session_caches_by_verification() {
+        let root = Some(PathBuf::from("/tmp/sqlx-test-root.pem"));
+
+        let verified = session_cache((false, false, root.clone()));
+
+        let others = vec![
+            session_cache((true, false, root.clone())),
+            session_cache((false, true, root)),
+            session_cache((
+                false,
+                false,
+                Some(PathBuf::from("/tmp/sqlx-test-other.pem")),
+            )),
+            session_cache((false, false, None)),
+        ];
+
+        for other in &others {
+            assert!(!Arc::ptr_eq(&verified, other));
+        }
+    }
+
+    #[test]
+    fn it_resumes_sessions_only_with_the_same_verification() {
+        let root = Some(PathBuf::from("/tmp/sqlx-test-resume.pem"));
+
+        let key = b"session-key".to_vec();
+        let value = b"session-value".to_vec();
+
+        assert!(session_cache((false, false, root.clone())).put(key.clone(), value.clone()));
+
+        // a later connection that verifies the server in the same way resumes the session
+        assert_eq!(
+            session_cache((false, false, root.clone())).get(&key),
+            Some(value)
+        );
+
+        // one that does not verify the server does not
+        assert_eq!(session_cache((true, false, root)).get(&key), None);
+    }
+}
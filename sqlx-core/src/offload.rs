@@ -0,0 +1,165 @@
+//! Decode very large rows on the blocking thread pool of the runtime.
+
+use std::mem;
+
+use crate::error::Error;
+use crate::from_row::FromRow;
+use crate::row::Row;
+
+const DEFAULT_CHUNK_SIZE: usize = 16 * 1024 * 1024;
+
+/// Moves the decoding of large rows off of the async executor.
+///
+/// Decoding a row holding tens of megabytes of JSON or binary data can occupy a thread of the
+/// executor long enough to stall every other task scheduled on it. Rows holding at least
+/// `threshold` bytes of data are instead decoded on the blocking thread pool of the runtime.
+/// Consecutive large rows are sent to the pool together, in chunks of roughly
+/// [`chunk_size`][Self::chunk_size] bytes, to avoid a round trip per row.
+///
+/// ```rust,ignore
+/// let offload = DecodeOffload::new(1024 * 1024);
+///
+/// let documents: Vec<Document> = sqlx::query_as("SELECT id, body FROM documents")
+///     .fetch_all_offloaded(&pool, &offload)
+///     .await?;
+/// ```
+///
+/// The size of a row is only known for PostgreSQL, MySQL and MSSQL. SQLite rows are always
+/// decoded in place, as their values are read from SQLite on demand.
+#[derive(Debug, Clone)]
+pub struct DecodeOffload {
+    threshold: usize,
+    chunk_size: usize,
+}
+
+impl DecodeOffload {
+    /// Offload the decoding of rows holding at least `threshold` bytes.
+    pub fn new(threshold: usize) -> Self {
+        Self {
+            threshold,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+        }
+    }
+
+    /// Set the number of bytes of row data to decode in a single trip to the blocking
+    /// thread pool.
+    ///
+    /// Defaults to 16 MiB. A single row larger than this is always decoded on its own.
+    pub fn chunk_size(mut self, bytes: usize) -> Self {
+        self.chunk_size = bytes;
+        self
+    }
+
+    /// Returns `true` if `row` would be decoded on the blocking thread pool.
+    pub fn applies<R: Row>(&self, row: &R) -> bool {
+        let len = row.data_len();
+
+        len > 0 && len >= self.threshold
+    }
+
+    /// Decode a single row, on the blocking thread pool if it is large enough.
+    pub async fn decode<R, O>(&self, row: R) -> Result<O, Error>
+    where
+        R: Row,
+        O: 'static + Send + for<'r> FromRow<'r, R>,
+    {
+        if !self.applies(&row) {
+            return O::from_row(&row);
+        }
+
+        run_blocking(move || O::from_row(&row)).await
+    }
+
+    /// Decode rows in order, sending the large ones to the blocking thread pool in chunks.
+    pub async fn decode_all<R, O>(&self, rows: Vec<R>) -> Result<Vec<O>, Error>
+    where
+        R: Row,
+        O: 'static + Send + for<'r> FromRow<'r, R>,
+    {
+        let mut decoded = Vec::with_capacity(rows.len());
+
+        let mut chunk = Vec::new();
+        let mut chunk_len = 0;
+
+        for row in rows {
+            if !self.applies(&row) {
+                // rows are decoded in order so any pending chunk must go first
+                if !chunk.is_empty() {
+                    decoded.extend(decode_chunk::<R, O>(mem::take(&mut chunk)).await?);
+                    chunk_len = 0;
+                }
+
+                decoded.push(O::from_row(&row)?);
+                continue;
+            }
+
+            chunk_len += row.data_len();
+            chunk.push(row);
+
+            if chunk_len >= self.chunk_size {
+                decoded.extend(decode_chunk::<R, O>(mem::take(&mut chunk)).await?);
+                chunk_len = 0;
+            }
+        }
+
+        if !chunk.is_empty() {
+            decoded.extend(decode_chunk::<R, O>(chunk).await?);
+        }
+
+        Ok(decoded)
+    }
+}
+
+async fn decode_chunk<R, O>(rows: Vec<R>) -> Result<Vec<O>, Error>
+where
+    R: Row,
+    O: 'static + Send + for<'r> FromRow<'r, R>,
+{
+    run_blocking(move || rows.iter().map(|row| O::from_row(row)).collect()).await
+}
+
+// `block_in_place` is not used with Tokio as it panics on the current-thread scheduler
+#[cfg(all(
+    feature = "_rt-tokio",
+    not(any(feature = "_rt-actix", feature = "_rt-async-std")),
+))]
+async fn run_blocking<F, T>(f: F) -> Result<T, Error>
+where
+    F: 'static + Send + FnOnce() -> Result<T, Error>,
+    T: 'static + Send,
+{
+    match sqlx_rt::tokio::task::spawn_blocking(f).await {
+        Ok(result) => result,
+        Err(error) if error.is_panic() => std::panic::resume_unwind(error.into_panic()),
+        Err(error) => panic!("{}", error),
+    }
+}
+
+#[cfg(not(all(
+    feature = "_rt-tokio",
+    not(any(feature = "_rt-actix", feature = "_rt-async-std")),
+)))]
+async fn run_blocking<F, T>(f: F) -> Result<T, Error>
+where
+    F: 'static + Send + FnOnce() -> Result<T, Error>,
+    T: 'static + Send,
+{
+    sqlx_rt::blocking!(f())
+}
+
+#[cfg(all(
+    test,
+    feature = "_rt-tokio",
+    not(any(feature = "_rt-actix", feature = "_rt-async-std")),
+))]
+#[test]
+fn test_run_blocking_on_basic_scheduler() {
+    let mut runtime = sqlx_rt::tokio::runtime::Builder::new()
+        .basic_scheduler()
+        .build()
+        .unwrap();
+
+    let value = runtime.block_on(run_blocking(|| Ok(42))).unwrap();
+
+    assert_eq!(value, 42);
+}
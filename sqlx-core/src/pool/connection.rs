@@ -5,6 +5,7 @@ use crate::error::Error;
 use sqlx_rt::spawn;
 use std::fmt::{self, Debug, Formatter};
 use std::ops::{Deref, DerefMut};
+use std::panic::{self, AssertUnwindSafe};
 use std::sync::Arc;
 use std::time::Instant;
 
@@ -14,6 +15,9 @@ use std::time::Instant;
 pub struct PoolConnection<DB: Database> {
     live: Option<Live<DB>>,
     pub(crate) pool: Arc<SharedPool<DB>>,
+
+    // set when a panic was caught while the connection was in use
+    poisoned: bool,
 }
 
 pub(super) struct Live<DB: Database> {
@@ -56,14 +60,83 @@ impl<DB: Database> DerefMut for PoolConnection<DB> {
 }
 
 impl<DB: Database> PoolConnection<DB> {
-    /// Explicitly release a connection from the pool
-    pub fn release(mut self) -> DB::Connection {
+    /// Explicitly release a connection from the pool.
+    ///
+    /// Equivalent to [`detach`][Self::detach].
+    pub fn release(self) -> DB::Connection {
+        self.detach()
+    }
+
+    /// Take the connection out of the pool.
+    ///
+    /// The connection no longer counts against [`max_connections`], so the pool may open a
+    /// replacement while it is in use; in total this may exceed `max_connections`. Use
+    /// [`Pool::attach`] to give the connection back afterwards, or [`leak`][Self::leak] if
+    /// the limit must hold.
+    ///
+    /// This is intended for operations that hold a connection much longer than a query,
+    /// such as `COPY` or `LISTEN`.
+    ///
+    /// [`max_connections`]: crate::pool::PoolOptions::max_connections
+    /// [`Pool::attach`]: crate::pool::Pool::attach
+    pub fn detach(mut self) -> DB::Connection {
         self.live
             .take()
             .expect("PoolConnection double-dropped")
             .float(&self.pool)
             .detach()
     }
+
+    /// Take the connection out of the pool while keeping its slot.
+    ///
+    /// Unlike [`detach`][Self::detach], the connection continues to count against
+    /// [`max_connections`] until it is given back with [`Pool::attach`]. If it is dropped or
+    /// closed instead, the pool permanently loses that slot.
+    ///
+    /// [`max_connections`]: crate::pool::PoolOptions::max_connections
+    /// [`Pool::attach`]: crate::pool::Pool::attach
+    pub fn leak(mut self) -> DB::Connection {
+        let live = self.live.take().expect("PoolConnection double-dropped");
+
+        self.pool.leak();
+
+        live.raw
+    }
+
+    /// Run `f` with the connection, catching any panic it raises.
+    ///
+    /// A panic may leave the connection in the middle of a message exchange with the database,
+    /// so if `f` panics the connection is closed when dropped instead of being returned to the
+    /// pool. Use this rather than [`std::panic::catch_unwind`] in code that recovers from
+    /// panics, such as workers of a thread pool, as the connection cannot otherwise tell that
+    /// a panic occurred while it was in use.
+    ///
+    /// ```rust,ignore
+    /// let mut conn = pool.acquire().await?;
+    ///
+    /// let result = conn.catch_unwind(|conn| {
+    ///     sqlx_rt::block_on(sqlx::query("UPDATE jobs SET done = TRUE").execute(conn))
+    /// });
+    /// ```
+    pub fn catch_unwind<F, R>(&mut self, f: F) -> std::thread::Result<R>
+    where
+        F: FnOnce(&mut DB::Connection) -> R,
+    {
+        // the connection is discarded if `f` panics, so its state can never be observed
+        let result = panic::catch_unwind(AssertUnwindSafe(|| f(&mut **self)));
+
+        if result.is_err() {
+            self.poisoned = true;
+        }
+
+        result
+    }
+
+    /// Returns `true` if a panic was caught by [`catch_unwind`][Self::catch_unwind] while the
+    /// connection was in use, in which case it will not be returned to the pool.
+    pub fn is_poisoned(&self) -> bool {
+        self.poisoned
+    }
 }
 
 /// Returns the connection to the [`Pool`][crate::pool::Pool] it was checked-out from.
@@ -72,12 +145,34 @@ impl<DB: Database> Drop for PoolConnection<DB> {
         if let Some(mut live) = self.live.take() {
             let pool = self.pool.clone();
 
-            if live.raw.should_flush() {
+            if self.poisoned || std::thread::panicking() {
+                // the thread panicked while this connection was checked out; it may have been
+                // in the middle of a message exchange so we can't trust its protocol state
+                log::warn!("dropping pool connection that was checked out during a panic");
+
+                // drop the connection without returning it to the pool
+                drop(live.float(&pool));
+                return;
+            }
+
+            let reset = pool.options.reset_on_release;
+
+            if reset || live.raw.should_flush() {
                 spawn(async move {
-                    // flush the connection (will immediately return if not needed) before
-                    // we fully release to the pool
-                    if let Err(e) = live.raw.flush().await {
-                        log::error!("error occurred while flushing the connection: {}", e);
+                    // flush the connection (will immediately return if not needed), resetting
+                    // its session if configured, before we fully release to the pool
+                    let result = if reset {
+                        reset_session(&pool, &mut live.raw).await
+                    } else {
+                        live.raw.flush().await
+                    };
+
+                    if let Err(e) = result {
+                        if reset {
+                            log::error!("error occurred while resetting the connection: {}", e);
+                        } else {
+                            log::error!("error occurred while flushing the connection: {}", e);
+                        }
 
                         // we now consider the connection to be broken
                         // close the connection and drop from the pool
@@ -95,6 +190,21 @@ impl<DB: Database> Drop for PoolConnection<DB> {
     }
 }
 
+// reset the session of a connection given back to the pool, then run `after_connect` again
+// to restore the state it sets up
+async fn reset_session<DB: Database>(
+    pool: &SharedPool<DB>,
+    conn: &mut DB::Connection,
+) -> Result<(), Error> {
+    conn.reset().await?;
+
+    if let Some(callback) = &pool.options.after_connect {
+        callback(conn).await?;
+    }
+
+    Ok(())
+}
+
 impl<DB: Database> Live<DB> {
     pub fn float(self, pool: &SharedPool<DB>) -> Floating<'_, Self> {
         Floating {
@@ -155,6 +265,7 @@ impl<'s, DB: Database> Floating<'s, Live<DB>> {
         PoolConnection {
             live: Some(inner),
             pool: Arc::clone(pool),
+            poisoned: false,
         }
     }
 
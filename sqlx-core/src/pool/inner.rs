@@ -4,9 +4,11 @@ use crate::connection::Connection;
 use crate::database::Database;
 use crate::error::Error;
 use crate::pool::{deadline_as_timeout, PoolOptions};
+use crate::sqlstate;
 use crossbeam_queue::{ArrayQueue, SegQueue};
 use futures_core::task::{Poll, Waker};
 use futures_util::future;
+use parking_lot::RwLock;
 use sqlx_rt::{sleep, spawn, timeout};
 use std::cmp;
 use std::mem;
@@ -17,10 +19,13 @@ use std::task::Context;
 use std::time::Instant;
 
 pub(crate) struct SharedPool<DB: Database> {
-    pub(super) connect_options: <DB::Connection as Connection>::Options,
+    // replaced by `Pool::set_connect_options`; connections being opened keep their own handle
+    connect_options: RwLock<Arc<<DB::Connection as Connection>::Options>>,
     pub(super) idle_conns: ArrayQueue<Idle<DB>>,
     waiters: SegQueue<Weak<Waiter>>,
     pub(super) size: AtomicU32,
+    // number of slots in `size` held by connections from `PoolConnection::leak()`
+    leaked: AtomicU32,
     is_closed: AtomicBool,
     pub(super) options: PoolOptions<DB>,
 }
@@ -47,8 +52,9 @@ impl<DB: Database> SharedPool<DB> {
             }
         }
 
-        // ensure we wait until the pool is actually closed
-        while self.size() > 0 {
+        // ensure we wait until the pool is actually closed;
+        // leaked connections are never returned so we can't wait for them
+        while self.size() > self.leaked.load(Ordering::Acquire) {
             if let Some(idle) = self.idle_conns.pop() {
                 if let Err(e) = Floating::from_idle(idle, self).close().await {
                     log::warn!("error occurred while closing the pool connection: {}", e);
@@ -78,6 +84,17 @@ impl<DB: Database> SharedPool<DB> {
     }
 
     pub(super) fn release(&self, mut floating: Floating<'_, Live<DB>>) {
+        if floating.raw.received_shutdown_notice() {
+            // the connection is unusable; drop it and do not return to the pool
+            self.drain_after_shutdown();
+            return;
+        }
+
+        if floating.raw.is_broken() {
+            // a cancelled operation left the connection unusable; drop it
+            return;
+        }
+
         if let Some(test) = &self.options.after_release {
             if !test(&mut floating.raw) {
                 // drop the connection and do not return to the pool
@@ -102,6 +119,60 @@ impl<DB: Database> SharedPool<DB> {
         }
     }
 
+    // a connection was told the server is shutting down or has terminated the session; idle
+    // connections to the same server are most likely terminated as well, so drop them and
+    // let the pool open new connections as it is used
+    fn drain_after_shutdown(&self) {
+        log::info!("server shutdown notice received; closing idle connections");
+
+        while let Some(idle) = self.pop_idle() {
+            drop(idle);
+        }
+
+        if let Some(callback) = &self.options.on_server_shutdown {
+            callback();
+        }
+    }
+
+    /// Keep the slot of a connection that is taken out of the pool by `PoolConnection::leak()`.
+    pub(super) fn leak(&self) {
+        self.leaked.fetch_add(1, Ordering::AcqRel);
+    }
+
+    /// Return a connection that was opened outside of the pool or taken out of it.
+    ///
+    /// The slot of a leaked connection is reused if there is one, otherwise the pool size is
+    /// incremented. Gives back the connection if the pool is closed or full.
+    pub(super) fn attach(&self, conn: DB::Connection) -> Result<(), DB::Connection> {
+        if self.is_closed() {
+            return Err(conn);
+        }
+
+        let reused = self
+            .leaked
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |leaked| {
+                leaked.checked_sub(1)
+            })
+            .is_ok();
+
+        let guard = if reused {
+            DecrementSizeGuard::new(self)
+        } else {
+            match self.try_increment_size() {
+                Some(guard) => guard,
+                None => return Err(conn),
+            }
+        };
+
+        self.release(Floating::new_live(conn, guard));
+
+        Ok(())
+    }
+
+    pub(super) fn set_connect_options(&self, options: <DB::Connection as Connection>::Options) {
+        *self.connect_options.write() = Arc::new(options);
+    }
+
     /// Try to atomically increment the pool size for a new connection.
     ///
     /// Returns `None` if we are at max_connections or if the pool is closed.
@@ -162,10 +233,11 @@ impl<DB: Database> SharedPool<DB> {
         connect_options: <DB::Connection as Connection>::Options,
     ) -> Arc<Self> {
         let pool = Self {
-            connect_options,
+            connect_options: RwLock::new(Arc::new(connect_options)),
             idle_conns: ArrayQueue::new(options.max_connections as usize),
             waiters: SegQueue::new(),
             size: AtomicU32::new(0),
+            leaked: AtomicU32::new(0),
             is_closed: AtomicBool::new(false),
             options,
         };
@@ -191,7 +263,7 @@ impl<DB: Database> SharedPool<DB> {
                 // Attempt to immediately acquire a connection. This will return Some
                 // if there is an idle connection in our channel.
                 if let Some(conn) = self.pop_idle() {
-                    if let Some(live) = check_conn(conn, &self.options).await {
+                    if let Some(live) = check_conn(conn, self).await {
                         return Ok(live);
                     }
                 }
@@ -235,8 +307,10 @@ impl<DB: Database> SharedPool<DB> {
 
         let timeout = super::deadline_as_timeout::<DB>(deadline)?;
 
+        let connect_options = Arc::clone(&self.connect_options.read());
+
         // result here is `Result<Result<C, Error>, TimeoutError>`
-        match sqlx_rt::timeout(timeout, self.connect_options.connect()).await {
+        match sqlx_rt::timeout(timeout, connect_options.connect()).await {
             // successfully established connection
             Ok(Ok(mut raw)) => {
                 if let Some(callback) = &self.options.after_connect {
@@ -253,7 +327,11 @@ impl<DB: Database> SharedPool<DB> {
 
             // [postgres] the database system is starting up
             // TODO: Make this check actually check if this is postgres
-            Ok(Err(Error::Database(error))) if error.code().as_deref() == Some("57P03") => Ok(None),
+            Ok(Err(Error::Database(error)))
+                if error.code().as_deref() == Some(sqlstate::CANNOT_CONNECT_NOW) =>
+            {
+                Ok(None)
+            }
 
             // Any other error while connection should immediately
             // terminate and bubble the error up
@@ -283,8 +361,10 @@ fn is_beyond_idle<DB: Database>(idle: &Idle<DB>, options: &PoolOptions<DB>) -> b
 
 async fn check_conn<'s: 'p, 'p, DB: Database>(
     mut conn: Floating<'s, Idle<DB>>,
-    options: &'p PoolOptions<DB>,
+    pool: &'p SharedPool<DB>,
 ) -> Option<Floating<'s, Live<DB>>> {
+    let options = &pool.options;
+
     // If the connection we pulled has expired, close the connection and
     // immediately create a new connection
     if is_beyond_lifetime(&conn, options) {
@@ -299,6 +379,12 @@ async fn check_conn<'s: 'p, 'p, DB: Database>(
             // either way we're fine to just discard the connection
             // the error itself here isn't necessarily unexpected so WARN is too strong
             log::info!("ping on idle connection returned error: {}", e);
+
+            if conn.live.raw.received_shutdown_notice() {
+                drop(conn);
+                pool.drain_after_shutdown();
+            }
+
             // connection is broken so don't try to close nicely
             return None;
         }
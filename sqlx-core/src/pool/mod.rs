@@ -74,10 +74,12 @@ mod maybe;
 mod connection;
 mod inner;
 mod options;
+mod single_flight;
 
 pub use self::connection::PoolConnection;
 pub(crate) use self::maybe::MaybePoolConnection;
 pub use self::options::PoolOptions;
+pub use self::single_flight::SingleFlight;
 
 /// An asynchronous pool of SQLx database connections.
 ///
@@ -260,6 +262,38 @@ impl<DB: Database> Pool<DB> {
         self.0.try_acquire().map(|conn| conn.attach(&self.0))
     }
 
+    /// Give a connection back to the pool.
+    ///
+    /// This is intended for connections taken out of the pool with
+    /// [`PoolConnection::detach`] or [`PoolConnection::leak`] for long-running operations,
+    /// but any connection made with the same options may be attached. The connection becomes
+    /// idle and is subject to the [`after_release`][PoolOptions::after_release] hook as if it
+    /// had been returned by a `PoolConnection`.
+    ///
+    /// A connection from `leak()` takes back the slot it kept. Otherwise the connection
+    /// needs a free slot; if the pool is closed or already has
+    /// [`max_connections`][PoolOptions::max_connections], the connection is given back in `Err`.
+    pub fn attach(&self, conn: DB::Connection) -> Result<(), DB::Connection> {
+        self.0.attach(conn)
+    }
+
+    /// Replace the options used to open new connections, such as to rotate the credentials
+    /// of the pool without recreating it.
+    ///
+    /// Connections already open are not affected: they are used until they are closed as they
+    /// would otherwise be, by [`max_lifetime`][PoolOptions::max_lifetime],
+    /// [`idle_timeout`][PoolOptions::idle_timeout] or an error. Connections that are being
+    /// opened when this is called may still use the previous options.
+    ///
+    /// ```rust,ignore
+    /// let options = PgConnectOptions::from_str(&database_url)?.password(&new_password);
+    ///
+    /// pool.set_connect_options(options);
+    /// ```
+    pub fn set_connect_options(&self, options: <DB::Connection as Connection>::Options) {
+        self.0.set_connect_options(options);
+    }
+
     /// Retrieves a new connection and immediately begins a new transaction.
     pub async fn begin(&self) -> Result<Transaction<'static, DB>, Error> {
         Ok(Transaction::begin(MaybePoolConnection::PoolConnection(self.acquire().await?)).await?)
@@ -26,12 +26,14 @@ pub struct PoolOptions<DB: Database> {
     >,
     pub(crate) after_release:
         Option<Box<dyn Fn(&mut DB::Connection) -> bool + 'static + Send + Sync>>,
+    pub(crate) on_server_shutdown: Option<Box<dyn Fn() + 'static + Send + Sync>>,
     pub(crate) max_connections: u32,
     pub(crate) connect_timeout: Duration,
     pub(crate) min_connections: u32,
     pub(crate) max_lifetime: Option<Duration>,
     pub(crate) idle_timeout: Option<Duration>,
     pub(crate) fair: bool,
+    pub(crate) reset_on_release: bool,
 }
 
 impl<DB: Database> Default for PoolOptions<DB> {
@@ -47,12 +49,14 @@ impl<DB: Database> PoolOptions<DB> {
             test_before_acquire: true,
             before_acquire: None,
             after_release: None,
+            on_server_shutdown: None,
             max_connections: 10,
             min_connections: 0,
             connect_timeout: Duration::from_secs(30),
             idle_timeout: Some(Duration::from_secs(10 * 60)),
             max_lifetime: Some(Duration::from_secs(30 * 60)),
             fair: true,
+            reset_on_release: false,
         }
     }
 
@@ -124,6 +128,23 @@ impl<DB: Database> PoolOptions<DB> {
         self
     }
 
+    /// If true, the session of a connection is reset by a call to [`Connection::reset`] when
+    /// it is returned to the pool, so that temporary tables, session variables and prepared
+    /// statements do not leak to the next user of the connection.
+    ///
+    /// The [`after_connect`][Self::after_connect] callback is run again once the session is
+    /// reset. A connection that fails to reset is closed instead of returned to the pool.
+    /// Connections given back with [`Pool::attach`] are not reset.
+    ///
+    /// This costs a round-trip to the server on every release and prevents reusing the
+    /// statements prepared by the connection. Defaults to `false`.
+    ///
+    /// [`Connection::reset`]: crate::connection::Connection::reset
+    pub fn reset_on_release(mut self, reset: bool) -> Self {
+        self.reset_on_release = reset;
+        self
+    }
+
     /// If set to `true`, calls to `acquire()` are fair and connections  are issued
     /// in first-come-first-serve order. If `false`, "drive-by" tasks may steal idle connections
     /// ahead of tasks that have been waiting.
@@ -190,6 +211,37 @@ impl<DB: Database> PoolOptions<DB> {
         self
     }
 
+    /// Perform an action when the pool learns that the server is shutting down or has
+    /// terminated a session, e.g. during an administrative shutdown or a failover.
+    ///
+    /// When a connection returned to the pool, or checked before being acquired, has received
+    /// such a notice, the pool closes it along with all of its idle connections, which are most
+    /// likely terminated as well, and opens new connections as it is used. The callback is run
+    /// after the idle connections are closed, once for each connection that received a notice.
+    ///
+    /// See [`Connection::received_shutdown_notice`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn f() -> Result<(), Box<dyn std::error::Error>> {
+    /// use sqlx_core::postgres::PgPoolOptions;
+    /// let pool = PgPoolOptions::new()
+    ///     .on_server_shutdown(|| eprintln!("database is shutting down; reconnecting"))
+    ///     .connect("postgres:// …").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`Connection::received_shutdown_notice`]: crate::connection::Connection::received_shutdown_notice
+    pub fn on_server_shutdown<F>(mut self, callback: F) -> Self
+    where
+        F: Fn() + 'static + Send + Sync,
+    {
+        self.on_server_shutdown = Some(Box::new(callback));
+        self
+    }
+
     /// Creates a new pool from this configuration and immediately establishes one connection.
     pub async fn connect(self, uri: &str) -> Result<Pool<DB>, Error> {
         self.connect_with(uri.parse()?).await
@@ -262,6 +314,7 @@ impl<DB: Database> Debug for PoolOptions<DB> {
             .field("max_lifetime", &self.max_lifetime)
             .field("idle_timeout", &self.idle_timeout)
             .field("test_before_acquire", &self.test_before_acquire)
+            .field("reset_on_release", &self.reset_on_release)
             .finish()
     }
 }
@@ -0,0 +1,226 @@
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+
+use futures_channel::oneshot;
+use parking_lot::Mutex;
+
+use crate::arguments::Arguments;
+use crate::database::Database;
+use crate::error::Error;
+use crate::executor::{Captured, Execute, Executor};
+use crate::pool::Pool;
+use crate::snapshot::SnapshotValue;
+use crate::HashMap;
+
+type FlightResult<DB> = Result<Arc<Vec<<DB as Database>::Row>>, Arc<Error>>;
+
+/// Coalesces concurrent executions of the same read query on a [`Pool`] into one.
+///
+/// While a query is running through a `SingleFlight`, the same query with the same arguments
+/// does not run again: it waits for the running query and receives the same rows. This keeps
+/// a burst of identical queries, such as the one following the expiry of a cache entry, from
+/// all hitting the database at once.
+///
+/// ```rust,ignore
+/// use sqlx::pool::SingleFlight;
+///
+/// let flights = SingleFlight::new(pool.clone());
+///
+/// // any concurrent call for the same user shares a single execution
+/// let rows = flights
+///     .fetch_all(sqlx::query("SELECT * FROM users WHERE id = $1").bind(user_id))
+///     .await?;
+/// ```
+///
+/// Only queries without side effects should run through a `SingleFlight`, as the callers that
+/// join a running query do not run it themselves. Queries are identical if their SQL and their
+/// arguments, as captured by [`Arguments::snapshot`], are equal; queries with arguments the
+/// driver cannot capture are never coalesced.
+///
+/// If the execution fails, each caller, including the one running it, receives
+/// [`Error::Coalesced`] with the error, whether or not other callers joined it. If the caller
+/// running the query is cancelled, one of those that joined it runs its own query instead.
+///
+/// The query runs with its prepared statement and persistence, as set by
+/// [`Query::persistent`]; callers joining it share its results whatever their own are.
+///
+/// [`Query::persistent`]: crate::query::Query::persistent
+pub struct SingleFlight<DB: Database> {
+    pool: Pool<DB>,
+
+    // running queries, by SQL
+    flights: Arc<Mutex<HashMap<String, Vec<Flight<DB>>>>>,
+}
+
+struct Flight<DB: Database> {
+    arguments: Vec<SnapshotValue>,
+    waiters: Vec<oneshot::Sender<FlightResult<DB>>>,
+}
+
+// `new` is kept out of the bounded impl below, as the bound cannot be resolved before the
+// database is inferred from the pool
+impl<DB: Database> SingleFlight<DB> {
+    pub fn new(pool: Pool<DB>) -> Self {
+        Self {
+            pool,
+            flights: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// The pool queries are run on.
+    pub fn pool(&self) -> &Pool<DB> {
+        &self.pool
+    }
+}
+
+impl<DB: Database> SingleFlight<DB>
+where
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+{
+    /// Execute the query and return all the generated results, or wait for the results of
+    /// the same query if it is already running.
+    ///
+    /// Fails with [`Error::Coalesced`].
+    pub async fn fetch_all<'q, E>(&self, mut query: E) -> Result<Arc<Vec<DB::Row>>, Error>
+    where
+        E: 'q + Execute<'q, DB>,
+    {
+        let sql = query.sql();
+        let arguments = query.take_arguments();
+
+        let snapshot = match &arguments {
+            Some(arguments) => arguments.snapshot(),
+            None => Some(Vec::new()),
+        };
+
+        let query = Captured::new(query, arguments);
+
+        let snapshot = match snapshot {
+            Some(snapshot) => snapshot,
+            None => return self.run(query).await.map_err(Error::Coalesced),
+        };
+
+        // join the flight of the same query if there is one, or start one; if the caller
+        // running the query is cancelled, those waiting for it try again
+        while let Some(waiter) = self.join(sql, &snapshot) {
+            if let Ok(result) = waiter.await {
+                return result.map_err(Error::Coalesced);
+            }
+        }
+
+        let mut landing = Landing {
+            flights: &self.flights,
+            sql,
+            arguments: &snapshot,
+            landed: false,
+        };
+
+        let result = self.run(query).await;
+
+        for waiter in landing.land() {
+            let _ = waiter.send(result.clone());
+        }
+
+        result.map_err(Error::Coalesced)
+    }
+
+    async fn run<'q, E>(&self, query: E) -> FlightResult<DB>
+    where
+        E: 'q + Execute<'q, DB>,
+    {
+        self.pool
+            .fetch_all(query)
+            .await
+            .map(Arc::new)
+            .map_err(Arc::new)
+    }
+
+    // returns the receiver of the results of the same query if it is running, or records
+    // that the caller is now running it
+    fn join(
+        &self,
+        sql: &str,
+        arguments: &[SnapshotValue],
+    ) -> Option<oneshot::Receiver<FlightResult<DB>>> {
+        let mut flights = self.flights.lock();
+        let flights = flights.entry(sql.to_owned()).or_default();
+
+        if let Some(flight) = flights.iter_mut().find(|f| f.arguments == arguments) {
+            let (tx, rx) = oneshot::channel();
+            flight.waiters.push(tx);
+
+            return Some(rx);
+        }
+
+        flights.push(Flight {
+            arguments: arguments.to_vec(),
+            waiters: Vec::new(),
+        });
+
+        None
+    }
+}
+
+// removes the flight of a query once it completes or its caller is cancelled, in which case
+// the waiters are dropped and retry
+struct Landing<'a, DB: Database> {
+    flights: &'a Mutex<HashMap<String, Vec<Flight<DB>>>>,
+    sql: &'a str,
+    arguments: &'a [SnapshotValue],
+    landed: bool,
+}
+
+impl<DB: Database> Landing<'_, DB> {
+    fn land(&mut self) -> Vec<oneshot::Sender<FlightResult<DB>>> {
+        if self.landed {
+            return Vec::new();
+        }
+
+        self.landed = true;
+
+        let mut flights = self.flights.lock();
+
+        let same_sql = match flights.get_mut(self.sql) {
+            Some(same_sql) => same_sql,
+            None => return Vec::new(),
+        };
+
+        let flight = same_sql
+            .iter()
+            .position(|f| f.arguments == self.arguments)
+            .map(|index| same_sql.swap_remove(index));
+
+        if same_sql.is_empty() {
+            flights.remove(self.sql);
+        }
+
+        flight.map_or_else(Vec::new, |flight| flight.waiters)
+    }
+}
+
+impl<DB: Database> Drop for Landing<'_, DB> {
+    fn drop(&mut self) {
+        self.land();
+    }
+}
+
+impl<DB: Database> Clone for SingleFlight<DB> {
+    fn clone(&self) -> Self {
+        Self {
+            pool: self.pool.clone(),
+            flights: Arc::clone(&self.flights),
+        }
+    }
+}
+
+impl<DB: Database> Debug for SingleFlight<DB> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SingleFlight")
+            .field("pool", &self.pool)
+            .field(
+                "in_flight",
+                &self.flights.lock().values().map(Vec::len).sum::<usize>(),
+            )
+            .finish()
+    }
+}
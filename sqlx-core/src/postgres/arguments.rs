@@ -1,10 +1,17 @@
+use std::convert::TryFrom;
+use std::fmt::{self, Write};
 use std::ops::{Deref, DerefMut};
 
+use bytes::Buf;
+
 use crate::arguments::Arguments;
 use crate::encode::{Encode, IsNull};
 use crate::error::Error;
 use crate::ext::ustr::UStr;
-use crate::postgres::{PgConnection, PgTypeInfo, Postgres};
+use crate::postgres::type_info::PgType;
+use crate::postgres::{PgConnection, PgTypeInfo, PgTypeKind, PgValueFormat, Postgres};
+use crate::snapshot::SnapshotValue;
+use crate::type_info::TypeInfo;
 use crate::types::Type;
 
 // TODO: buf.patch(|| ...) is a poor name, can we think of a better name? Maybe `buf.lazy(||)` ?
@@ -54,6 +61,9 @@ pub struct PgArguments {
 
     // Buffer of encoded bind parameters
     pub(crate) buffer: PgArgumentBuffer,
+
+    // Format of each bind parameter, left empty while they are all binary
+    pub(crate) formats: Vec<PgValueFormat>,
 }
 
 impl PgArguments {
@@ -65,6 +75,10 @@ impl PgArguments {
         self.types
             .push(value.produces().unwrap_or_else(T::type_info));
 
+        if !self.formats.is_empty() {
+            self.formats.push(PgValueFormat::Binary);
+        }
+
         // encode the value into our buffer
         self.buffer.encode(value);
 
@@ -72,6 +86,28 @@ impl PgArguments {
         self.buffer.count += 1;
     }
 
+    // Add a value in the text format of its type, to be parsed by the server
+    pub(crate) fn add_text(&mut self, ty: PgTypeInfo, text: &str) {
+        if self.formats.is_empty() {
+            self.formats.resize(self.types.len(), PgValueFormat::Binary);
+        }
+
+        self.types.push(ty);
+        self.formats.push(PgValueFormat::Text);
+
+        self.buffer.encode(text);
+        self.buffer.count += 1;
+    }
+
+    // The format codes to send for the parameters
+    pub(crate) fn formats(&self) -> &[PgValueFormat] {
+        if self.formats.is_empty() {
+            &[PgValueFormat::Binary]
+        } else {
+            &self.formats
+        }
+    }
+
     // Apply patches
     // This should only go out and ask postgres if we have not seen the type name yet
     pub(crate) async fn apply_patches(
@@ -116,6 +152,301 @@ impl<'q> Arguments<'q> for PgArguments {
     {
         self.add(value)
     }
+
+    fn format_placeholder<W: Write>(&self, writer: &mut W) -> fmt::Result {
+        write!(writer, "${}", self.buffer.count)
+    }
+
+    fn snapshot(&self) -> Option<Vec<SnapshotValue>> {
+        let mut buf = &self.buffer[..];
+        let mut values = Vec::with_capacity(self.types.len());
+
+        for (index, ty) in self.types.iter().enumerate() {
+            // every value is prefixed with its length, or -1 for NULL
+            let len = buf.get_i32();
+
+            if len < 0 {
+                values.push(SnapshotValue::Null);
+                continue;
+            }
+
+            let (value, rest) = buf.split_at(len as usize);
+            buf = rest;
+
+            values.push(if self.formats.get(index) == Some(&PgValueFormat::Text) {
+                SnapshotValue::Typed {
+                    type_name: ty.name().to_owned(),
+                    text: String::from_utf8_lossy(value).into_owned(),
+                }
+            } else {
+                snapshot_value(ty, value)
+            });
+        }
+
+        Some(values)
+    }
+
+    fn add_snapshot_value(&mut self, value: &SnapshotValue) {
+        match value {
+            // the type is resolved by name when the query is prepared
+            SnapshotValue::Typed { type_name, text } => self.add_text(
+                PgTypeInfo(PgType::DeclareWithName(UStr::new(type_name))),
+                text,
+            ),
+
+            _ => self.add(value.clone()),
+        }
+    }
+}
+
+// read a value in the binary format of its type, or keep it as is
+fn snapshot_value(ty: &PgTypeInfo, value: &[u8]) -> SnapshotValue {
+    let mut buf = value;
+
+    match (&ty.0, value.len()) {
+        (PgType::Bool, 1) => SnapshotValue::Bool(value[0] != 0),
+        (PgType::Int2, 2) => SnapshotValue::Int(buf.get_i16().into()),
+        (PgType::Int4, 4) => SnapshotValue::Int(buf.get_i32().into()),
+        (PgType::Int8, 8) => SnapshotValue::Int(buf.get_i64()),
+        (PgType::Float4, 4) => SnapshotValue::Float(buf.get_f32().into()),
+        (PgType::Float8, 8) => SnapshotValue::Float(buf.get_f64()),
+
+        (PgType::Text, _)
+        | (PgType::Varchar, _)
+        | (PgType::Bpchar, _)
+        | (PgType::Name, _)
+        | (PgType::Unknown, _)
+        | (PgType::Json, _) => SnapshotValue::Text(String::from_utf8_lossy(value).into_owned()),
+
+        // JSONB is prefixed with the version of its format
+        (PgType::Jsonb, _) if value.first() == Some(&1) => {
+            SnapshotValue::Text(String::from_utf8_lossy(&value[1..]).into_owned())
+        }
+
+        (PgType::Oid, 4) => SnapshotValue::Int(buf.get_u32().into()),
+
+        _ => match render_text(ty, value) {
+            Some(text) => SnapshotValue::Typed {
+                type_name: ty.name().to_owned(),
+                text,
+            },
+
+            None => SnapshotValue::Bytes(value.to_vec()),
+        },
+    }
+}
+
+// render a value in the binary format of its type as the text the server would accept for it
+fn render_text(ty: &PgTypeInfo, value: &[u8]) -> Option<String> {
+    let mut buf = value;
+
+    Some(match (&ty.0, value.len()) {
+        (PgType::Uuid, 16) => {
+            let hex: String = value.iter().map(|b| format!("{:02x}", b)).collect();
+
+            format!(
+                "{}-{}-{}-{}-{}",
+                &hex[..8],
+                &hex[8..12],
+                &hex[12..16],
+                &hex[16..20],
+                &hex[20..]
+            )
+        }
+
+        (PgType::Date, 4) => match buf.get_i32() {
+            i32::MAX => "infinity".to_owned(),
+            i32::MIN => "-infinity".to_owned(),
+            days => render_date(days.into()),
+        },
+
+        (PgType::Time, 8) => render_time(buf.get_i64()),
+
+        (PgType::Timetz, 12) => {
+            let time = render_time(buf.get_i64());
+
+            // the offset is stored in seconds west of UTC
+            let offset = -buf.get_i32();
+            let sign = if offset < 0 { '-' } else { '+' };
+            let offset = offset.abs();
+
+            format!(
+                "{}{}{:02}:{:02}:{:02}",
+                time,
+                sign,
+                offset / 3600,
+                offset / 60 % 60,
+                offset % 60
+            )
+        }
+
+        (PgType::Timestamp, 8) | (PgType::Timestamptz, 8) => {
+            let text = match buf.get_i64() {
+                i64::MAX => return Some("infinity".to_owned()),
+                i64::MIN => return Some("-infinity".to_owned()),
+
+                // microseconds since 2000-01-01, in UTC for TIMESTAMPTZ
+                micros => format!(
+                    "{} {}",
+                    render_date(micros.div_euclid(MICROS_PER_DAY)),
+                    render_time(micros.rem_euclid(MICROS_PER_DAY))
+                ),
+            };
+
+            if let PgType::Timestamptz = ty.0 {
+                format!("{}+00", text)
+            } else {
+                text
+            }
+        }
+
+        (PgType::Interval, 16) => {
+            let micros = buf.get_i64();
+            let days = buf.get_i32();
+            let months = buf.get_i32();
+
+            format!("{} months {} days {} microseconds", months, days, micros)
+        }
+
+        (PgType::Numeric, len) if len >= 8 => render_numeric(buf)?,
+
+        (PgType::Inet, len) | (PgType::Cidr, len) if len >= 4 => {
+            let family = buf.get_u8();
+            let bits = buf.get_u8();
+            let _is_cidr = buf.get_u8();
+            let len = usize::from(buf.get_u8());
+
+            let address = match (family, len, buf) {
+                (2, 4, address) => address
+                    .iter()
+                    .map(|b| b.to_string())
+                    .collect::<Vec<_>>()
+                    .join("."),
+
+                (3, 16, address) => address
+                    .chunks(2)
+                    .map(|group| format!("{:x}", u16::from_be_bytes([group[0], group[1]])))
+                    .collect::<Vec<_>>()
+                    .join(":"),
+
+                _ => return None,
+            };
+
+            format!("{}/{}", address, bits)
+        }
+
+        (PgType::Macaddr, 6) | (PgType::Macaddr8, 8) => value
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<Vec<_>>()
+            .join(":"),
+
+        // the binary format of an enum is its label
+        (PgType::Custom(custom), _) if matches!(custom.kind, PgTypeKind::Enum(_)) => {
+            String::from_utf8(value.to_vec()).ok()?
+        }
+
+        _ => return None,
+    })
+}
+
+const MICROS_PER_DAY: i64 = 86_400_000_000;
+
+// render a date given as the number of days since 2000-01-01
+fn render_date(days: i64) -> String {
+    // https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 10_957 + 719_468;
+    let era = if z >= 0 { z } else { z - 146_096 } / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    // there is no year zero, the year before 1 AD is 1 BC
+    if year > 0 {
+        format!("{:04}-{:02}-{:02}", year, month, day)
+    } else {
+        format!("{:04}-{:02}-{:02} BC", 1 - year, month, day)
+    }
+}
+
+// render a time of day given in microseconds
+fn render_time(micros: i64) -> String {
+    let seconds = micros / 1_000_000;
+
+    format!(
+        "{:02}:{:02}:{:02}.{:06}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60,
+        micros % 1_000_000
+    )
+}
+
+fn render_numeric(mut buf: &[u8]) -> Option<String> {
+    let count = usize::from(buf.get_u16());
+    let weight = i64::from(buf.get_i16());
+    let sign = buf.get_u16();
+    let scale = usize::from(buf.get_u16());
+
+    match sign {
+        0x4000 | 0x0000 => {}
+        0xC000 => return Some("NaN".to_owned()),
+        0xD000 => return Some("Infinity".to_owned()),
+        0xF000 => return Some("-Infinity".to_owned()),
+
+        _ => return None,
+    }
+
+    if buf.len() != count * 2 {
+        return None;
+    }
+
+    // the digits are in base 10000, the first one multiplied by 10000 ^ weight
+    let digits: Vec<i16> = (0..count).map(|_| buf.get_i16()).collect();
+    let digit = |index: i64| {
+        usize::try_from(index)
+            .ok()
+            .and_then(|index| digits.get(index))
+            .copied()
+            .unwrap_or(0)
+    };
+
+    let mut text = String::new();
+
+    if sign == 0x4000 {
+        text.push('-');
+    }
+
+    if weight < 0 {
+        text.push('0');
+    } else {
+        text.push_str(&digit(0).to_string());
+
+        for index in 1..=weight {
+            text.push_str(&format!("{:04}", digit(index)));
+        }
+    }
+
+    if scale > 0 {
+        let mut fraction = String::new();
+        let mut index = weight + 1;
+
+        while fraction.len() < scale {
+            fraction.push_str(&format!("{:04}", digit(index)));
+            index += 1;
+        }
+
+        fraction.truncate(scale);
+
+        text.push('.');
+        text.push_str(&fraction);
+    }
+
+    Some(text)
 }
 
 impl PgArgumentBuffer {
@@ -178,3 +509,124 @@ impl DerefMut for PgArgumentBuffer {
         &mut self.buffer
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::PgArguments;
+    use crate::arguments::Arguments;
+    use crate::postgres::{PgTypeInfo, PgValueFormat};
+    use crate::snapshot::SnapshotValue;
+
+    fn typed(type_name: &str, text: &str) -> SnapshotValue {
+        SnapshotValue::Typed {
+            type_name: type_name.into(),
+            text: text.into(),
+        }
+    }
+
+    #[test]
+    fn it_snapshots_arguments() {
+        let mut arguments = PgArguments::default();
+
+        arguments.add(true);
+        arguments.add(-5_i16);
+        arguments.add(42_i64);
+        arguments.add(1.5_f64);
+        arguments.add("alice");
+        arguments.add(None::<i32>);
+        arguments.add(&b"\x00\x01"[..]);
+
+        assert_eq!(
+            arguments.snapshot().unwrap(),
+            vec![
+                SnapshotValue::Bool(true),
+                SnapshotValue::Int(-5),
+                SnapshotValue::Int(42),
+                SnapshotValue::Float(1.5),
+                SnapshotValue::Text("alice".into()),
+                SnapshotValue::Null,
+                SnapshotValue::Bytes(vec![0, 1]),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_snapshots_typed_arguments() {
+        let mut arguments = PgArguments::default();
+
+        // values in the binary format of their type
+        let mut add = |ty: PgTypeInfo, value: &[u8]| {
+            arguments.types.push(ty);
+            arguments.buffer.encode(value);
+            arguments.buffer.count += 1;
+        };
+
+        let timestamp = (7305 * 86_400 + 12 * 3600 + 30 * 60) * 1_000_000_i64 + 500_000;
+        let numeric = [0, 3, 0, 1, 0, 0, 0, 3, 0, 1, 0x09, 0x29, 0x1a, 0x7c];
+        let interval = [
+            &3_600_000_000_i64.to_be_bytes()[..],
+            &[0, 0, 0, 2, 0, 0, 0, 1],
+        ]
+        .concat();
+
+        add(PgTypeInfo::UUID, &(0..16).collect::<Vec<u8>>());
+        add(PgTypeInfo::DATE, &7305_i32.to_be_bytes());
+        add(PgTypeInfo::DATE, &(-730_485_i32).to_be_bytes());
+        add(PgTypeInfo::TIMESTAMPTZ, &timestamp.to_be_bytes());
+        add(PgTypeInfo::TIMESTAMP, &i64::MAX.to_be_bytes());
+        add(PgTypeInfo::NUMERIC, &numeric);
+        add(
+            PgTypeInfo::NUMERIC,
+            &[0, 1, 0xff, 0xff, 0x40, 0, 0, 4, 0, 12],
+        );
+        add(PgTypeInfo::INET, &[2, 24, 0, 4, 192, 168, 0, 1]);
+        add(PgTypeInfo::INTERVAL, &interval);
+
+        assert_eq!(
+            arguments.snapshot().unwrap(),
+            vec![
+                typed("UUID", "00010203-0405-0607-0809-0a0b0c0d0e0f"),
+                typed("DATE", "2020-01-01"),
+                typed("DATE", "0001-01-01 BC"),
+                typed("TIMESTAMPTZ", "2020-01-01 12:30:00.500000+00"),
+                typed("TIMESTAMP", "infinity"),
+                typed("NUMERIC", "12345.678"),
+                typed("NUMERIC", "-0.0012"),
+                typed("INET", "192.168.0.1/24"),
+                typed("INTERVAL", "1 months 2 days 3600000000 microseconds"),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_binds_typed_snapshot_values_as_text() {
+        let mut arguments = PgArguments::default();
+
+        arguments.add(1_i32);
+        arguments.add_snapshot_value(&typed("UUID", "00010203-0405-0607-0809-0a0b0c0d0e0f"));
+        arguments.add(2_i32);
+
+        assert_eq!(
+            arguments.formats(),
+            &[
+                PgValueFormat::Binary,
+                PgValueFormat::Text,
+                PgValueFormat::Binary
+            ][..]
+        );
+
+        assert_eq!(
+            arguments.snapshot().unwrap(),
+            vec![
+                SnapshotValue::Int(1),
+                typed("UUID", "00010203-0405-0607-0809-0a0b0c0d0e0f"),
+                SnapshotValue::Int(2),
+            ]
+        );
+
+        assert_eq!(
+            PgArguments::default().formats(),
+            &[PgValueFormat::Binary][..]
+        );
+    }
+}
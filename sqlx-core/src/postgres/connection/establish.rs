@@ -137,11 +137,13 @@ impl PgConnection {
             secret_key,
             transaction_status,
             transaction_depth: 0,
+            last_sql: String::new(),
             pending_ready_for_query_count: 0,
             next_statement_id: 1,
             cache_statement: StatementCache::new(options.statement_cache_capacity),
             cache_type_oid: HashMap::new(),
             cache_type_info: HashMap::new(),
+            type_overrides: None,
             log_settings: options.log_settings.clone(),
         })
     }
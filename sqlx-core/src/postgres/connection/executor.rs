@@ -201,6 +201,9 @@ impl PgConnection {
     ) -> Result<impl Stream<Item = Result<Either<PgDone, PgRow>, Error>> + 'e, Error> {
         let mut logger = QueryLogger::new(query, self.log_settings.clone());
 
+        self.last_sql.clear();
+        self.last_sql.push_str(query);
+
         // before we continue, wait until we are "ready" to accept more queries
         self.wait_until_ready().await?;
 
@@ -222,7 +225,7 @@ impl PgConnection {
             self.stream.write(Bind {
                 portal: None,
                 statement,
-                formats: &[PgValueFormat::Binary],
+                formats: arguments.formats(),
                 num_params: arguments.types.len() as i16,
                 params: &*arguments.buffer,
                 result_formats: &[PgValueFormat::Binary],
@@ -305,6 +308,7 @@ impl PgConnection {
                             data,
                             format,
                             metadata: Arc::clone(&metadata),
+                            type_overrides: self.type_overrides.clone(),
                         };
 
                         r#yield!(Either::Right(row));
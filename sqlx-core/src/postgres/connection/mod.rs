@@ -6,7 +6,7 @@ use futures_core::future::BoxFuture;
 use futures_util::{FutureExt, TryFutureExt};
 
 use crate::common::StatementCache;
-use crate::connection::{Connection, LogSettings};
+use crate::connection::{Capability, Connection, ConnectionState, LogSettings};
 use crate::error::Error;
 use crate::executor::Executor;
 use crate::ext::ustr::UStr;
@@ -18,6 +18,7 @@ use crate::postgres::message::{
 use crate::postgres::statement::PgStatementMetadata;
 use crate::postgres::{PgConnectOptions, PgTypeInfo, Postgres};
 use crate::transaction::Transaction;
+use crate::type_overrides::TypeOverrides;
 
 pub(crate) mod describe;
 mod establish;
@@ -61,6 +62,12 @@ pub struct PgConnection {
     transaction_status: TransactionStatus,
     pub(crate) transaction_depth: usize,
 
+    // most recently executed query, kept for diagnostics
+    pub(crate) last_sql: String,
+
+    // shared with the rows returned by this connection
+    type_overrides: Option<Arc<TypeOverrides<Postgres>>>,
+
     log_settings: LogSettings,
 }
 
@@ -72,7 +79,11 @@ impl PgConnection {
         }
 
         while self.pending_ready_for_query_count > 0 {
-            let message = self.stream.recv().await?;
+            let message = match self.stream.recv().await {
+                // the rest of an abandoned result may hold more oversized rows, already skipped
+                Err(Error::FrameTooLarge { .. }) => continue,
+                message => message?,
+            };
 
             if let MessageFormat::ReadyForQuery = message.format {
                 self.handle_ready_for_query(message)?;
@@ -100,6 +111,27 @@ impl PgConnection {
 
         Ok(())
     }
+
+    // the version reported by the server on startup, or zero if it was not reported
+    fn server_version(&self) -> (u16, u16) {
+        self.stream
+            .parameter_statuses
+            .get("server_version")
+            .map_or((0, 0), |version| parse_server_version(version))
+    }
+}
+
+// `13.1`, `9.6.20` or `14.2 (Debian 14.2-1.pgdg110+1)`; only the major version, and the minor
+// version before 10, are of interest
+fn parse_server_version(version: &str) -> (u16, u16) {
+    let mut parts = version
+        .split(|c: char| !c.is_ascii_digit() && c != '.')
+        .next()
+        .unwrap_or_default()
+        .split('.')
+        .map(|part| part.parse().unwrap_or(0));
+
+    (parts.next().unwrap_or(0), parts.next().unwrap_or(0))
 }
 
 impl Debug for PgConnection {
@@ -133,6 +165,25 @@ impl Connection for PgConnection {
         self.execute("/* SQLx ping */").map_ok(|_| ()).boxed()
     }
 
+    fn reset(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move {
+            self.wait_until_ready().await?;
+
+            // DISCARD ALL cannot run inside a transaction block
+            if !matches!(self.transaction_status, TransactionStatus::Idle) {
+                self.execute("ROLLBACK").await?;
+            }
+
+            self.execute("DISCARD ALL").await?;
+
+            // the server deallocated every prepared statement
+            self.cache_statement.clear();
+            self.transaction_depth = 0;
+
+            Ok(())
+        })
+    }
+
     fn begin(&mut self) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
     where
         Self: Sized,
@@ -167,6 +218,44 @@ impl Connection for PgConnection {
         })
     }
 
+    fn close_cached_statement<'e>(
+        &'e mut self,
+        sql: &'e str,
+    ) -> BoxFuture<'e, Result<bool, Error>> {
+        Box::pin(async move {
+            self.wait_until_ready().await?;
+
+            let id = match self.cache_statement.remove(sql) {
+                Some((id, _)) => id,
+                None => return Ok(false),
+            };
+
+            self.stream.write(Close::Statement(id));
+            self.write_sync();
+            self.stream.flush().await?;
+
+            self.wait_for_close_complete(1).await?;
+            self.recv_ready_for_query().await?;
+
+            Ok(true)
+        })
+    }
+
+    fn set_type_overrides(&mut self, overrides: TypeOverrides<Postgres>) {
+        self.type_overrides = overrides.into_shared();
+    }
+
+    fn dump_state(&self) -> ConnectionState {
+        ConnectionState {
+            transaction_depth: self.transaction_depth,
+            cached_statements: self.cache_statement.len(),
+            read_buffer_capacity: self.stream.rbuf_capacity(),
+            write_buffer_capacity: self.stream.wbuf.capacity(),
+            last_sql: Some(self.last_sql.clone()).filter(|sql| !sql.is_empty()),
+            server_parameters: self.stream.parameter_statuses.clone(),
+        }
+    }
+
     #[doc(hidden)]
     fn flush(&mut self) -> BoxFuture<'_, Result<(), Error>> {
         self.wait_until_ready().boxed()
@@ -176,4 +265,30 @@ impl Connection for PgConnection {
     fn should_flush(&self) -> bool {
         !self.stream.wbuf.is_empty()
     }
+
+    fn received_shutdown_notice(&self) -> bool {
+        self.stream.shutdown
+    }
+
+    fn supports(&self, capability: Capability) -> bool {
+        let version = self.server_version();
+
+        match capability {
+            Capability::Returning => true,
+            Capability::SkipLocked | Capability::OnConflict => version >= (9, 5),
+            Capability::JsonOperators => version >= (9, 3),
+        }
+    }
+}
+
+#[test]
+fn test_parse_server_version() {
+    assert_eq!(parse_server_version("13.1"), (13, 1));
+    assert_eq!(parse_server_version("9.6.20"), (9, 6));
+    assert_eq!(
+        parse_server_version("14.2 (Debian 14.2-1.pgdg110+1)"),
+        (14, 2)
+    );
+    assert_eq!(parse_server_version("15beta1"), (15, 0));
+    assert_eq!(parse_server_version(""), (0, 0));
 }
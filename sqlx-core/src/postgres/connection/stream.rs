@@ -1,14 +1,17 @@
+use std::collections::BTreeMap;
 use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
 
 use bytes::{Buf, Bytes};
-use futures_channel::mpsc::UnboundedSender;
-use futures_util::SinkExt;
 use log::Level;
+use parking_lot::Mutex;
 
-use crate::error::Error;
+use crate::error::{DatabaseError, Error};
+use crate::frame::FrameSink;
 use crate::io::{BufStream, Decode, Encode};
 use crate::net::{MaybeTlsStream, Socket};
-use crate::postgres::message::{Message, MessageFormat, Notice, Notification};
+use crate::postgres::listener::NotificationBuffer;
+use crate::postgres::message::{Message, MessageFormat, Notice, Notification, ParameterStatus};
 use crate::postgres::{PgConnectOptions, PgDatabaseError, PgSeverity};
 
 // the stream is a separate type from the connection to uphold the invariant where an instantiated
@@ -26,7 +29,18 @@ pub struct PgStream {
     // buffer of unreceived notification messages from `PUBLISH`
     // this is set when creating a PgListener and only written to if that listener is
     // re-used for query execution in-between receiving messages
-    pub(crate) notifications: Option<UnboundedSender<Notification>>,
+    pub(crate) notifications: Option<Arc<Mutex<NotificationBuffer>>>,
+
+    // current values of the run-time parameters reported by the server
+    pub(crate) parameter_statuses: BTreeMap<String, String>,
+
+    // messages larger than this are skipped, and streamed to the sink if any, instead of being
+    // read into memory
+    max_frame_size: Option<usize>,
+    frame_sink: Option<Arc<dyn FrameSink>>,
+
+    // set once the server has reported that it is shutting down or terminated the session
+    pub(crate) shutdown: bool,
 }
 
 impl PgStream {
@@ -41,6 +55,10 @@ impl PgStream {
         Ok(Self {
             inner,
             notifications: None,
+            parameter_statuses: BTreeMap::new(),
+            max_frame_size: options.max_frame_size,
+            frame_sink: options.frame_sink.as_ref().map(|sink| Arc::clone(&sink.0)),
+            shutdown: false,
         })
     }
 
@@ -73,12 +91,27 @@ impl PgStream {
     pub(crate) async fn recv_unchecked(&mut self) -> Result<Message, Error> {
         // all packets in postgres start with a 5-byte header
         // this header contains the message type and the total length of the message
-        let mut header: Bytes = self.inner.read(5).await?;
+        let mut header = self.inner.peek(5).await?;
 
         let format = MessageFormat::try_from_u8(header.get_u8())?;
-        let size = (header.get_u32() - 4) as usize;
+        let size = header
+            .get_u32()
+            .checked_sub(4)
+            .ok_or_else(|| err_protocol!("message length must include its own 4 bytes"))?
+            as usize;
+
+        // the header is only consumed along with the contents of the message, so that
+        // a cancelled receive leaves the stream at the start of the message
+        if let Some(max) = self.max_frame_size.filter(|&max| size > max) {
+            self.inner
+                .skip_frame(5, size, self.frame_sink.clone())
+                .await?;
+
+            return Err(Error::FrameTooLarge { size, max });
+        }
 
-        let contents = self.inner.read(size).await?;
+        let mut contents: Bytes = self.inner.read(5 + size).await?;
+        contents.advance(5);
 
         Ok(Message { format, contents })
     }
@@ -92,13 +125,20 @@ impl PgStream {
             match message.format {
                 MessageFormat::ErrorResponse => {
                     // An error returned from the database server.
-                    return Err(PgDatabaseError(message.decode()?).into());
+                    let error: Box<dyn DatabaseError> =
+                        Box::new(PgDatabaseError(message.decode()?));
+
+                    if error.is_server_shutdown() {
+                        self.shutdown = true;
+                    }
+
+                    return Err(Error::Database(error));
                 }
 
                 MessageFormat::NotificationResponse => {
-                    if let Some(buffer) = &mut self.notifications {
+                    if let Some(buffer) = &self.notifications {
                         let notification: Notification = message.decode()?;
-                        let _ = buffer.send(notification).await;
+                        buffer.lock().push(notification);
 
                         continue;
                     }
@@ -108,7 +148,10 @@ impl PgStream {
                     // informs the frontend about the current (initial)
                     // setting of backend parameters
 
-                    // we currently have no use for that data so we promptly ignore this message
+                    // we only keep these around for diagnostics
+                    let ParameterStatus { name, value } = message.decode()?;
+                    self.parameter_statuses.insert(name, value);
+
                     continue;
                 }
 
@@ -71,6 +71,7 @@ async fn upgrade(stream: &mut PgStream, options: &PgConnectOptions) -> Result<bo
             accept_invalid_certs,
             accept_invalid_hostnames,
             options.ssl_root_cert.as_deref(),
+            options.ssl_session_resumption,
         )
         .await?;
 
@@ -0,0 +1,129 @@
+use futures_core::future::BoxFuture;
+
+use crate::error::Error;
+use crate::executor::{Execute, Executor};
+use crate::explain::{Access, Explain, PlanStep, QueryPlan};
+use crate::postgres::{PgConnection, Postgres};
+use crate::row::Row;
+
+impl Explain for PgConnection {
+    fn explain<'e, 'q: 'e, E>(&'e mut self, mut query: E) -> BoxFuture<'e, Result<QueryPlan, Error>>
+    where
+        E: 'q + Execute<'q, Postgres>,
+    {
+        let sql = format!("EXPLAIN {}", query.sql());
+        let arguments = query.take_arguments();
+
+        Box::pin(async move {
+            let rows = self.fetch_all((&*sql, arguments)).await?;
+
+            let lines = rows
+                .iter()
+                .map(|row| row.try_get::<String, _>(0))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            Ok(parse_plan(&lines))
+        })
+    }
+}
+
+// each node of the plan is on its own line, which starts with `->` except for the root; the
+// other lines hold the conditions and filters of the node above them
+fn parse_plan(lines: &[String]) -> QueryPlan {
+    let nodes = lines.iter().enumerate().filter_map(|(i, line)| {
+        let line = line.trim_start();
+
+        match line.strip_prefix("->") {
+            Some(node) => Some(node.trim_start()),
+            None if i == 0 => Some(line),
+            None => None,
+        }
+    });
+
+    QueryPlan::new(nodes.map(parse_node).collect())
+}
+
+fn parse_node(node: &str) -> PlanStep {
+    // drop the estimates, as in `Seq Scan on users  (cost=0.00..35.50 rows=2550 width=4)`
+    let detail = node.split("  (").next().unwrap_or(node).trim_end();
+
+    let word_after = |prefix: &str| {
+        detail
+            .find(prefix)
+            .and_then(|i| detail[i + prefix.len()..].split_whitespace().next())
+            .map(unquote)
+    };
+
+    let (access, table, index) =
+        if detail.starts_with("Seq Scan on ") || detail.starts_with("Parallel Seq Scan on ") {
+            (Access::FullScan, word_after(" on "), None)
+        } else if detail.contains("Index") && detail.contains(" using ") {
+            // `Index Scan using users_pkey on users`, `Index Only Scan Backward using ...`
+            (Access::Index, word_after(" on "), word_after(" using "))
+        } else if detail.starts_with("Bitmap Index Scan on ") {
+            (Access::Index, None, word_after(" on "))
+        } else if detail.starts_with("Bitmap Heap Scan on ") {
+            (Access::Other, word_after(" on "), None)
+        } else {
+            (Access::Other, None, None)
+        };
+
+    PlanStep {
+        access,
+        table,
+        index,
+        detail: detail.to_owned(),
+    }
+}
+
+fn unquote(name: &str) -> String {
+    if name.len() >= 2 && name.starts_with('"') && name.ends_with('"') {
+        name[1..name.len() - 1].replace("\"\"", "\"")
+    } else {
+        name.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_plan;
+    use crate::explain::Access;
+
+    #[test]
+    fn it_parses_plans() {
+        let lines: Vec<String> = r#"Nested Loop  (cost=0.29..24.45 rows=3 width=68)
+  ->  Seq Scan on "Orders" o  (cost=0.00..15.00 rows=3 width=36)
+        Filter: (total > 100)
+  ->  Index Scan using users_pkey on users u  (cost=0.29..3.15 rows=1 width=36)
+        Index Cond: (id = o.user_id)
+  ->  Bitmap Heap Scan on events  (cost=4.18..12.64 rows=4 width=8)
+        ->  Bitmap Index Scan on events_kind_idx  (cost=0.00..4.18 rows=4 width=0)"#
+            .lines()
+            .map(ToOwned::to_owned)
+            .collect();
+
+        let plan = parse_plan(&lines);
+        let steps = plan.steps();
+
+        assert_eq!(steps.len(), 5);
+
+        assert_eq!(steps[0].access(), Access::Other);
+        assert_eq!(steps[0].detail(), "Nested Loop");
+
+        assert_eq!(steps[1].access(), Access::FullScan);
+        assert_eq!(steps[1].table(), Some("Orders"));
+
+        assert_eq!(steps[2].access(), Access::Index);
+        assert_eq!(steps[2].table(), Some("users"));
+        assert_eq!(steps[2].index(), Some("users_pkey"));
+
+        assert_eq!(steps[3].access(), Access::Other);
+        assert_eq!(steps[3].table(), Some("events"));
+
+        assert_eq!(steps[4].access(), Access::Index);
+        assert_eq!(steps[4].index(), Some("events_kind_idx"));
+
+        assert!(plan.scans_table("orders"));
+        assert!(plan.uses_index("events_kind_idx"));
+    }
+}
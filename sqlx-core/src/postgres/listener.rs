@@ -6,12 +6,15 @@ use crate::pool::{Pool, PoolConnection};
 use crate::postgres::message::{MessageFormat, Notification};
 use crate::postgres::{PgConnection, PgDone, PgRow, PgStatement, PgTypeInfo, Postgres};
 use either::Either;
-use futures_channel::mpsc;
 use futures_core::future::BoxFuture;
 use futures_core::stream::{BoxStream, Stream};
+use futures_util::{future, stream, FutureExt, StreamExt};
+use parking_lot::Mutex;
+use std::collections::VecDeque;
 use std::fmt::{self, Debug};
 use std::io;
 use std::str::from_utf8;
+use std::sync::Arc;
 
 /// A stream of asynchronous notifications from Postgres.
 ///
@@ -19,17 +22,160 @@ use std::str::from_utf8;
 /// connection being used ever dies, this listener will detect that event, create a
 /// new connection, will re-subscribe to all of the originally specified channels, and will resume
 /// operations as normal.
+///
+/// Notifications received while the listener is used to run queries are buffered until they
+/// are received. The buffer is unbounded by default; see [`set_buffer_limit`] to bound it.
+///
+/// [`set_buffer_limit`]: #method.set_buffer_limit
 pub struct PgListener {
     pool: Pool<Postgres>,
     connection: Option<PoolConnection<Postgres>>,
-    buffer_rx: mpsc::UnboundedReceiver<Notification>,
-    buffer_tx: Option<mpsc::UnboundedSender<Notification>>,
+    buffer: Arc<Mutex<NotificationBuffer>>,
     channels: Vec<String>,
 }
 
 /// An asynchronous notification from Postgres.
 pub struct PgNotification(Notification);
 
+/// What a [`PgListener`] does with a notification that arrives while its buffer is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PgListenerOverflow {
+    /// Drop the oldest notification in the buffer to make room for the new one.
+    DropOldest,
+
+    /// Keep every notification, but refuse to run queries through the listener while its
+    /// buffer is full: they fail with [`Error::NotificationBufferBlocked`] without being sent
+    /// until enough notifications have been received.
+    ///
+    /// Queries are refused rather than waited on, as only the listener itself can receive
+    /// from its buffer. The buffer can still grow past its capacity by the notifications that
+    /// arrive while a single query runs.
+    RejectQueries,
+
+    /// Drop the new notification, and fail the next receive on the listener with
+    /// [`Error::NotificationBufferFull`], so that the consumer learns it missed notifications.
+    Error,
+}
+
+/// Statistics on the notification buffer of a [`PgListener`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PgListenerStats {
+    buffered: usize,
+    peak: usize,
+    received: u64,
+    dropped: u64,
+}
+
+impl PgListenerStats {
+    /// The number of notifications currently in the buffer.
+    pub fn buffered(&self) -> usize {
+        self.buffered
+    }
+
+    /// The largest number of notifications the buffer has held at once.
+    pub fn peak(&self) -> usize {
+        self.peak
+    }
+
+    /// The number of notifications that arrived while the listener was running queries,
+    /// whether or not they were kept.
+    pub fn received(&self) -> u64 {
+        self.received
+    }
+
+    /// The number of notifications dropped as the buffer was full.
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+}
+
+// notifications received by the connection of a listener while running queries, shared
+// between the listener and its connection
+#[derive(Debug)]
+pub(crate) struct NotificationBuffer {
+    queue: VecDeque<Notification>,
+    capacity: Option<usize>,
+    overflow: PgListenerOverflow,
+    stats: PgListenerStats,
+
+    // notifications dropped by `PgListenerOverflow::Error` that are yet to be reported
+    unreported: u64,
+}
+
+impl NotificationBuffer {
+    fn new() -> Self {
+        Self {
+            queue: VecDeque::new(),
+            capacity: None,
+            overflow: PgListenerOverflow::DropOldest,
+            stats: PgListenerStats::default(),
+            unreported: 0,
+        }
+    }
+
+    fn is_full(&self) -> bool {
+        matches!(self.capacity, Some(capacity) if self.queue.len() >= capacity)
+    }
+
+    pub(crate) fn push(&mut self, notification: Notification) {
+        self.stats.received += 1;
+
+        if self.is_full() {
+            match self.overflow {
+                PgListenerOverflow::DropOldest => {
+                    self.stats.dropped += 1;
+
+                    // with a capacity of zero, there is nothing older to make room with
+                    if self.queue.pop_front().is_none() {
+                        return;
+                    }
+                }
+
+                PgListenerOverflow::Error => {
+                    self.stats.dropped += 1;
+                    self.unreported += 1;
+
+                    return;
+                }
+
+                PgListenerOverflow::RejectQueries => {}
+            }
+        }
+
+        self.queue.push_back(notification);
+        self.stats.peak = self.stats.peak.max(self.queue.len());
+    }
+
+    fn pop(&mut self) -> Result<Option<Notification>, Error> {
+        if self.unreported > 0 {
+            let dropped = self.unreported;
+            self.unreported = 0;
+
+            return Err(Error::NotificationBufferFull { dropped });
+        }
+
+        Ok(self.queue.pop_front())
+    }
+
+    fn stats(&self) -> PgListenerStats {
+        PgListenerStats {
+            buffered: self.queue.len(),
+            ..self.stats
+        }
+    }
+
+    // the error a query run through the listener fails with, if its buffer is full
+    fn check_blocked(&self) -> Result<(), Error> {
+        if self.overflow == PgListenerOverflow::RejectQueries && self.is_full() {
+            return Err(Error::NotificationBufferBlocked {
+                buffered: self.queue.len(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
 impl PgListener {
     pub async fn connect(uri: &str) -> Result<Self, Error> {
         // Create a pool of 1 without timeouts (as they don't apply here)
@@ -49,18 +195,35 @@ impl PgListener {
         let mut connection = pool.acquire().await?;
 
         // Setup a notification buffer
-        let (sender, receiver) = mpsc::unbounded();
-        connection.stream.notifications = Some(sender);
+        let buffer = Arc::new(Mutex::new(NotificationBuffer::new()));
+        connection.stream.notifications = Some(Arc::clone(&buffer));
 
         Ok(Self {
             pool: pool.clone(),
             connection: Some(connection),
-            buffer_rx: receiver,
-            buffer_tx: None,
+            buffer,
             channels: Vec::new(),
         })
     }
 
+    /// Bounds the buffer of notifications received while the listener is used to run queries
+    /// to `capacity` notifications, with `overflow` deciding what happens to those that arrive
+    /// while it is full.
+    ///
+    /// Notifications already in the buffer are kept, even if there are more than `capacity`.
+    pub fn set_buffer_limit(&mut self, capacity: usize, overflow: PgListenerOverflow) {
+        let mut buffer = self.buffer.lock();
+
+        buffer.capacity = Some(capacity);
+        buffer.overflow = overflow;
+    }
+
+    /// Statistics on the buffer of notifications received while the listener is used to run
+    /// queries.
+    pub fn buffer_stats(&self) -> PgListenerStats {
+        self.buffer.lock().stats()
+    }
+
     /// Starts listening for notifications on a channel.
     /// The channel name is quoted here to ensure case sensitivity.
     pub async fn listen(&mut self, channel: &str) -> Result<(), Error> {
@@ -117,7 +280,7 @@ impl PgListener {
     async fn connect_if_needed(&mut self) -> Result<(), Error> {
         if self.connection.is_none() {
             let mut connection = self.pool.acquire().await?;
-            connection.stream.notifications = self.buffer_tx.take();
+            connection.stream.notifications = Some(Arc::clone(&self.buffer));
 
             connection
                 .execute(&*build_listen_all_query(&self.channels))
@@ -200,7 +363,7 @@ impl PgListener {
     pub async fn try_recv(&mut self) -> Result<Option<PgNotification>, Error> {
         // Flush the buffer first, if anything
         // This would only fill up if this listener is used as a connection
-        if let Ok(Some(notification)) = self.buffer_rx.try_next() {
+        if let Some(notification) = self.buffer.lock().pop()? {
             return Ok(Some(PgNotification(notification)));
         }
 
@@ -214,7 +377,7 @@ impl PgListener {
                 // The connection is dead, ensure that it is dropped,
                 // update self state, and loop to try again.
                 Err(Error::Io(err)) if err.kind() == io::ErrorKind::ConnectionAborted => {
-                    self.buffer_tx = self.connection().stream.notifications.take();
+                    self.connection().stream.notifications = None;
                     self.connection = None;
 
                     // lost connection
@@ -271,6 +434,10 @@ impl<'c> Executor<'c> for &'c mut PgListener {
         'c: 'e,
         E: Execute<'q, Self::Database>,
     {
+        if let Err(error) = self.buffer.lock().check_blocked() {
+            return stream::once(future::ready(Err(error))).boxed();
+        }
+
         self.connection().fetch_many(query)
     }
 
@@ -282,6 +449,10 @@ impl<'c> Executor<'c> for &'c mut PgListener {
         'c: 'e,
         E: Execute<'q, Self::Database>,
     {
+        if let Err(error) = self.buffer.lock().check_blocked() {
+            return future::ready(Err(error)).boxed();
+        }
+
         self.connection().fetch_optional(query)
     }
 
@@ -330,6 +501,15 @@ impl PgNotification {
     }
 }
 
+impl Drop for PgListener {
+    fn drop(&mut self) {
+        // the connection goes back to the pool, where nothing receives from the buffer
+        if let Some(connection) = &mut self.connection {
+            connection.stream.notifications = None;
+        }
+    }
+}
+
 impl Debug for PgListener {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("PgListener").finish()
@@ -377,3 +557,74 @@ fn test_build_listen_all_query_with_multiple_channels() {
     let output = build_listen_all_query(&["channel.0", "channel.1"]);
     assert_eq!(output.as_str(), r#"LISTEN "channel.0";LISTEN "channel.1";"#);
 }
+
+#[cfg(test)]
+fn notification(payload: &'static str) -> Notification {
+    Notification {
+        process_id: 1,
+        channel: bytes::Bytes::from_static(b"test"),
+        payload: bytes::Bytes::from_static(payload.as_bytes()),
+    }
+}
+
+#[test]
+fn test_notification_buffer_drops_oldest() {
+    let mut buffer = NotificationBuffer::new();
+    buffer.capacity = Some(2);
+
+    for payload in &["1", "2", "3"] {
+        buffer.push(notification(payload));
+    }
+
+    assert_eq!(&buffer.pop().unwrap().unwrap().payload[..], b"2");
+    assert_eq!(&buffer.pop().unwrap().unwrap().payload[..], b"3");
+    assert!(buffer.pop().unwrap().is_none());
+
+    let stats = buffer.stats();
+    assert_eq!(stats.received(), 3);
+    assert_eq!(stats.dropped(), 1);
+    assert_eq!(stats.peak(), 2);
+    assert_eq!(stats.buffered(), 0);
+}
+
+#[test]
+fn test_notification_buffer_reports_dropped_notifications() {
+    let mut buffer = NotificationBuffer::new();
+    buffer.capacity = Some(1);
+    buffer.overflow = PgListenerOverflow::Error;
+
+    for payload in &["1", "2", "3"] {
+        buffer.push(notification(payload));
+    }
+
+    assert!(matches!(
+        buffer.pop(),
+        Err(Error::NotificationBufferFull { dropped: 2 })
+    ));
+
+    assert_eq!(&buffer.pop().unwrap().unwrap().payload[..], b"1");
+    assert!(buffer.pop().unwrap().is_none());
+}
+
+#[test]
+fn test_notification_buffer_rejects_queries_while_full() {
+    let mut buffer = NotificationBuffer::new();
+    buffer.capacity = Some(1);
+    buffer.overflow = PgListenerOverflow::RejectQueries;
+
+    buffer.push(notification("1"));
+    buffer.push(notification("2"));
+
+    assert_eq!(buffer.stats().buffered(), 2);
+    assert_eq!(buffer.stats().dropped(), 0);
+    assert!(matches!(
+        buffer.check_blocked(),
+        Err(Error::NotificationBufferBlocked { buffered: 2 })
+    ));
+
+    buffer.pop().unwrap();
+    assert!(buffer.check_blocked().is_err());
+
+    buffer.pop().unwrap();
+    assert!(buffer.check_blocked().is_ok());
+}
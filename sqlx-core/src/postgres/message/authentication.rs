@@ -4,7 +4,7 @@ use bytes::{Buf, Bytes};
 use memchr::memchr;
 
 use crate::error::Error;
-use crate::io::Decode;
+use crate::io::{BufExt, Decode};
 
 // On startup, the server sends an appropriate authentication request message,
 // to which the frontend must reply with an appropriate authentication
@@ -60,12 +60,14 @@ pub enum Authentication {
 
 impl Decode<'_> for Authentication {
     fn decode_with(mut buf: Bytes, _: ()) -> Result<Self, Error> {
-        Ok(match buf.get_u32() {
+        Ok(match buf.try_get_u32()? {
             0 => Authentication::Ok,
 
             3 => Authentication::CleartextPassword,
 
             5 => {
+                buf.ensure_remaining(4)?;
+
                 let mut salt = [0; 4];
                 buf.copy_to_slice(&mut salt);
 
@@ -4,7 +4,7 @@ use byteorder::{BigEndian, ByteOrder};
 use bytes::Bytes;
 
 use crate::error::Error;
-use crate::io::Decode;
+use crate::io::{BufExt, Decode};
 
 /// A row of data from the database.
 #[derive(Debug)]
@@ -28,6 +28,8 @@ impl DataRow {
 
 impl Decode<'_> for DataRow {
     fn decode_with(buf: Bytes, _: ()) -> Result<Self, Error> {
+        buf.ensure_remaining(2)?;
+
         let cnt = BigEndian::read_u16(&buf) as usize;
 
         let mut values = Vec::with_capacity(cnt);
@@ -37,12 +39,25 @@ impl Decode<'_> for DataRow {
             // Length of the column value, in bytes (this count does not include itself).
             // Can be zero. As a special case, -1 indicates a NULL column value.
             // No value bytes follow in the NULL case.
+            if buf.len() < offset as usize + 4 {
+                return Err(err_protocol!(
+                    "DataRow is missing the length of a column value"
+                ));
+            }
+
             let length = BigEndian::read_i32(&buf[(offset as usize)..]);
             offset += 4;
 
             if length < 0 {
                 values.push(None);
             } else {
+                if buf.len() < offset as usize + length as usize {
+                    return Err(err_protocol!(
+                        "DataRow column value of {} bytes is truncated",
+                        length
+                    ));
+                }
+
                 values.push(Some(offset..(offset + length as u32)));
                 offset += length as u32;
             }
@@ -73,6 +88,13 @@ fn test_decode_data_row() {
     assert_eq!(row.get(7).unwrap(), &[0_u8, 0, 0, 80][..]);
 }
 
+#[test]
+fn test_decode_data_row_truncated() {
+    assert!(DataRow::decode(Bytes::from_static(b"\x00")).is_err());
+    assert!(DataRow::decode(Bytes::from_static(b"\x00\x01\x00\x00")).is_err());
+    assert!(DataRow::decode(Bytes::from_static(b"\x00\x01\x00\x00\x00\x04\x00")).is_err());
+}
+
 #[cfg(all(test, not(debug_assertions)))]
 #[bench]
 fn bench_data_row_get(b: &mut test::Bencher) {
@@ -1,4 +1,4 @@
-use bytes::{Buf, Bytes};
+use bytes::Bytes;
 
 use crate::error::Error;
 use crate::io::{BufExt, Decode};
@@ -13,7 +13,7 @@ pub struct Notification {
 impl Decode<'_> for Notification {
     #[inline]
     fn decode_with(mut buf: Bytes, _: ()) -> Result<Self, Error> {
-        let process_id = buf.get_u32();
+        let process_id = buf.try_get_u32()?;
         let channel = buf.get_bytes_nul()?;
         let payload = buf.get_bytes_nul()?;
 
@@ -1,8 +1,8 @@
-use bytes::{Buf, Bytes};
+use bytes::Bytes;
 use smallvec::SmallVec;
 
 use crate::error::Error;
-use crate::io::Decode;
+use crate::io::{BufExt, Decode};
 
 #[derive(Debug)]
 pub struct ParameterDescription {
@@ -11,11 +11,11 @@ pub struct ParameterDescription {
 
 impl Decode<'_> for ParameterDescription {
     fn decode_with(mut buf: Bytes, _: ()) -> Result<Self, Error> {
-        let cnt = buf.get_u16();
+        let cnt = buf.try_get_u16()?;
         let mut types = SmallVec::with_capacity(cnt as usize);
 
         for _ in 0..cnt {
-            types.push(buf.get_u32());
+            types.push(buf.try_get_u32()?);
         }
 
         Ok(Self { types })
@@ -42,6 +42,15 @@ fn test_decode_empty_parameter_description() {
     assert!(m.types.is_empty());
 }
 
+#[test]
+fn test_decode_truncated_parameter_description() {
+    const DATA: &[u8] = b"\x00\x02\x00\x00\x00\x00\x00\x00\x05\x00";
+
+    for len in 0..DATA.len() {
+        assert!(ParameterDescription::decode(DATA[..len].into()).is_err());
+    }
+}
+
 #[cfg(all(test, not(debug_assertions)))]
 #[bench]
 fn bench_decode_parameter_description(b: &mut test::Bencher) {
@@ -0,0 +1,33 @@
+use bytes::Bytes;
+
+use crate::error::Error;
+use crate::io::{BufExt, Decode};
+
+/// Reports the current (initial) setting of a run-time parameter of the backend.
+#[derive(Debug)]
+pub struct ParameterStatus {
+    /// The name of the run-time parameter being reported.
+    pub name: String,
+
+    /// The current value of the parameter.
+    pub value: String,
+}
+
+impl Decode<'_> for ParameterStatus {
+    fn decode_with(mut buf: Bytes, _: ()) -> Result<Self, Error> {
+        let name = buf.get_str_nul()?;
+        let value = buf.get_str_nul()?;
+
+        Ok(Self { name, value })
+    }
+}
+
+#[test]
+fn test_decode_parameter_status() {
+    const DATA: &[u8] = b"server_version\x0013.1\x00";
+
+    let m = ParameterStatus::decode(DATA.into()).unwrap();
+
+    assert_eq!(m.name, "server_version");
+    assert_eq!(m.value, "13.1");
+}
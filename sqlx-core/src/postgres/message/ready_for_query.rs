@@ -1,7 +1,7 @@
 use bytes::Bytes;
 
 use crate::error::Error;
-use crate::io::Decode;
+use crate::io::{BufExt, Decode};
 
 #[derive(Debug)]
 #[repr(u8)]
@@ -22,8 +22,8 @@ pub struct ReadyForQuery {
 }
 
 impl Decode<'_> for ReadyForQuery {
-    fn decode_with(buf: Bytes, _: ()) -> Result<Self, Error> {
-        let status = match buf[0] {
+    fn decode_with(mut buf: Bytes, _: ()) -> Result<Self, Error> {
+        let status = match buf.try_get_u8()? {
             b'I' => TransactionStatus::Idle,
             b'T' => TransactionStatus::Transaction,
             b'E' => TransactionStatus::Error,
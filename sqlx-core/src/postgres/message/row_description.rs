@@ -1,4 +1,4 @@
-use bytes::{Buf, Bytes};
+use bytes::Bytes;
 
 use crate::error::Error;
 use crate::io::{BufExt, Decode};
@@ -38,17 +38,17 @@ pub struct Field {
 
 impl Decode<'_> for RowDescription {
     fn decode_with(mut buf: Bytes, _: ()) -> Result<Self, Error> {
-        let cnt = buf.get_u16();
+        let cnt = buf.try_get_u16()?;
         let mut fields = Vec::with_capacity(cnt as usize);
 
         for _ in 0..cnt {
             let name = buf.get_str_nul()?.to_owned();
-            let relation_id = buf.get_i32();
-            let relation_attribute_no = buf.get_i16();
-            let data_type_id = buf.get_u32();
-            let data_type_size = buf.get_i16();
-            let type_modifier = buf.get_i32();
-            let format = buf.get_i16();
+            let relation_id = buf.try_get_i32()?;
+            let relation_attribute_no = buf.try_get_i16()?;
+            let data_type_id = buf.try_get_u32()?;
+            let data_type_size = buf.try_get_i16()?;
+            let type_modifier = buf.try_get_i32()?;
+            let format = buf.try_get_i16()?;
 
             fields.push(Field {
                 name,
@@ -75,3 +75,19 @@ impl Decode<'_> for RowDescription {
 
 // TODO: Unit Test RowDescription
 // TODO: Benchmark RowDescription
+
+#[test]
+fn test_decode_truncated_row_description() {
+    const DATA: &[u8] =
+        b"\x00\x01id\0\x00\x00\x40\x01\x00\x01\x00\x00\x00\x17\x00\x04\xff\xff\xff\xff\x00\x01";
+
+    let m = RowDescription::decode(DATA.into()).unwrap();
+
+    assert_eq!(m.fields.len(), 1);
+    assert_eq!(m.fields[0].name, "id");
+    assert_eq!(m.fields[0].data_type_id, 23);
+
+    for len in 0..DATA.len() {
+        assert!(RowDescription::decode(DATA[..len].into()).is_err());
+    }
+}
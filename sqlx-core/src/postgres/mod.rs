@@ -6,6 +6,7 @@ mod connection;
 mod database;
 mod done;
 mod error;
+mod explain;
 mod io;
 mod listener;
 mod message;
@@ -13,6 +14,7 @@ mod options;
 mod row;
 mod statement;
 mod transaction;
+mod two_phase;
 mod type_info;
 pub mod types;
 mod value;
@@ -26,7 +28,7 @@ pub use connection::PgConnection;
 pub use database::Postgres;
 pub use done::PgDone;
 pub use error::{PgDatabaseError, PgErrorPosition};
-pub use listener::{PgListener, PgNotification};
+pub use listener::{PgListener, PgListenerOverflow, PgListenerStats, PgNotification};
 pub use message::PgSeverity;
 pub use options::{PgConnectOptions, PgSslMode};
 pub use row::PgRow;
@@ -1,10 +1,12 @@
 use std::env::var;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 mod connect;
 mod parse;
 mod ssl_mode;
 use crate::connection::LogSettings;
+use crate::frame::{FrameSink, SharedFrameSink};
 pub use ssl_mode::PgSslMode;
 
 /// Options and flags which can be used to configure a PostgreSQL connection.
@@ -83,6 +85,9 @@ pub struct PgConnectOptions {
     pub(crate) database: Option<String>,
     pub(crate) ssl_mode: PgSslMode,
     pub(crate) ssl_root_cert: Option<PathBuf>,
+    pub(crate) ssl_session_resumption: bool,
+    pub(crate) max_frame_size: Option<usize>,
+    pub(crate) frame_sink: Option<SharedFrameSink>,
     pub(crate) statement_cache_capacity: usize,
     pub(crate) application_name: Option<String>,
     pub(crate) log_settings: LogSettings,
@@ -135,6 +140,9 @@ impl PgConnectOptions {
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or_default(),
+            ssl_session_resumption: false,
+            max_frame_size: None,
+            frame_sink: None,
             statement_cache_capacity: 100,
             application_name: var("PGAPPNAME").ok(),
             log_settings: Default::default(),
@@ -271,6 +279,74 @@ impl PgConnectOptions {
         self
     }
 
+    /// Sets whether TLS sessions may be resumed when reconnecting to the server.
+    ///
+    /// Resuming a session skips most of the TLS handshake, which saves a round trip and the
+    /// cost of verifying the server certificate on every new connection. Sessions are only
+    /// shared between connections that verify the server in the same way.
+    ///
+    /// Only supported with the `rustls` TLS backend: with `native-tls`, which does not let the
+    /// session cache be controlled, upgrading the connection to TLS fails when this is enabled.
+    ///
+    /// The default is `false`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_core::postgres::{PgSslMode, PgConnectOptions};
+    /// let options = PgConnectOptions::new()
+    ///     .ssl_mode(PgSslMode::Require)
+    ///     .ssl_session_resumption(true);
+    /// ```
+    pub fn ssl_session_resumption(mut self, enabled: bool) -> Self {
+        self.ssl_session_resumption = enabled;
+        self
+    }
+
+    /// Sets the maximum size, in bytes, of a single message from the server.
+    ///
+    /// A larger message, typically a row holding a huge value, is read off the connection in
+    /// small chunks instead of being buffered in memory. The chunks are streamed to the
+    /// [`frame_sink`][Self::frame_sink], if one is set, and discarded otherwise. The query then
+    /// fails with [`Error::FrameTooLarge`] and the connection remains usable.
+    ///
+    /// By default, the size of messages is not limited.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_core::postgres::PgConnectOptions;
+    /// let options = PgConnectOptions::new()
+    ///     .max_frame_size(64 * 1024 * 1024);
+    /// ```
+    ///
+    /// [`Error::FrameTooLarge`]: crate::error::Error::FrameTooLarge
+    pub fn max_frame_size(mut self, bytes: usize) -> Self {
+        self.max_frame_size = Some(bytes);
+        self
+    }
+
+    /// Sets the sink receiving the messages larger than the
+    /// [`max_frame_size`][Self::max_frame_size], in chunks, as they are read.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_core::postgres::PgConnectOptions;
+    /// # use std::sync::atomic::{AtomicUsize, Ordering};
+    /// let streamed = AtomicUsize::new(0);
+    ///
+    /// let options = PgConnectOptions::new()
+    ///     .max_frame_size(4 * 1024 * 1024)
+    ///     .frame_sink(move |chunk: &[u8]| {
+    ///         streamed.fetch_add(chunk.len(), Ordering::Relaxed);
+    ///     });
+    /// ```
+    pub fn frame_sink(mut self, sink: impl FrameSink) -> Self {
+        self.frame_sink = Some(SharedFrameSink(Arc::new(sink)));
+        self
+    }
+
     /// Sets the capacity of the connection's statement cache in a number of stored
     /// distinct statements. Caching is handled using LRU, meaning when the
     /// amount of queries hits the defined limit, the oldest statement will get
@@ -5,6 +5,7 @@ use crate::postgres::statement::PgStatementMetadata;
 use crate::postgres::value::PgValueFormat;
 use crate::postgres::{PgColumn, PgValueRef, Postgres};
 use crate::row::Row;
+use crate::type_overrides::TypeOverrides;
 use std::sync::Arc;
 
 /// Implementation of [`Row`] for PostgreSQL.
@@ -12,9 +13,14 @@ pub struct PgRow {
     pub(crate) data: DataRow,
     pub(crate) format: PgValueFormat,
     pub(crate) metadata: Arc<PgStatementMetadata>,
+    pub(crate) type_overrides: Option<Arc<TypeOverrides<Postgres>>>,
 }
 
-impl crate::row::private_row::Sealed for PgRow {}
+impl crate::row::private_row::Sealed for PgRow {
+    fn data_len(&self) -> usize {
+        self.data.storage.len()
+    }
+}
 
 impl Row for PgRow {
     type Database = Postgres;
@@ -36,8 +42,13 @@ impl Row for PgRow {
             row: Some(&self.data.storage),
             type_info: column.type_info.clone(),
             value,
+            type_overrides: self.type_overrides.as_ref(),
         })
     }
+
+    fn type_overrides(&self) -> Option<&TypeOverrides<Postgres>> {
+        self.type_overrides.as_deref()
+    }
 }
 
 impl ColumnIndex<PgRow> for &'_ str {
@@ -63,6 +74,7 @@ impl From<PgRow> for crate::any::AnyRow {
                 .collect(),
 
             kind: crate::any::row::AnyRowKind::Postgres(row),
+            type_overrides: None,
         }
     }
 }
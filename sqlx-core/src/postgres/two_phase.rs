@@ -0,0 +1,76 @@
+use futures_core::future::BoxFuture;
+
+use crate::error::Error;
+use crate::executor::Executor;
+use crate::postgres::{PgConnection, PgTransactionManager};
+use crate::query_scalar::query_scalar;
+use crate::transaction::TransactionManager;
+use crate::two_phase::TwoPhase;
+
+// transaction ids are string literals in these statements and cannot be bound
+fn quote(xid: &str) -> String {
+    format!("'{}'", xid.replace('\'', "''"))
+}
+
+impl TwoPhase for PgConnection {
+    fn begin_branch<'e>(&'e mut self, _xid: &'e str) -> BoxFuture<'e, Result<(), Error>> {
+        Box::pin(async move {
+            // a savepoint cannot be prepared on its own
+            if self.transaction_depth > 0 {
+                return Err(Error::Configuration(
+                    "cannot begin a two-phase branch inside a transaction".into(),
+                ));
+            }
+
+            // the transaction is only named when it is prepared
+            PgTransactionManager::begin(self).await
+        })
+    }
+
+    fn prepare_branch<'e>(&'e mut self, xid: &'e str) -> BoxFuture<'e, Result<(), Error>> {
+        Box::pin(async move {
+            let result = self
+                .execute(&*format!("PREPARE TRANSACTION {}", quote(xid)))
+                .await;
+
+            // the session leaves the transaction whether it was prepared or, on failure,
+            // rolled back
+            self.transaction_depth = self.transaction_depth.saturating_sub(1);
+
+            result?;
+
+            Ok(())
+        })
+    }
+
+    fn abort_branch<'e>(&'e mut self, _xid: &'e str) -> BoxFuture<'e, Result<(), Error>> {
+        PgTransactionManager::rollback(self)
+    }
+
+    fn commit_prepared<'e>(&'e mut self, xid: &'e str) -> BoxFuture<'e, Result<(), Error>> {
+        Box::pin(async move {
+            self.execute(&*format!("COMMIT PREPARED {}", quote(xid)))
+                .await?;
+
+            Ok(())
+        })
+    }
+
+    fn rollback_prepared<'e>(&'e mut self, xid: &'e str) -> BoxFuture<'e, Result<(), Error>> {
+        Box::pin(async move {
+            self.execute(&*format!("ROLLBACK PREPARED {}", quote(xid)))
+                .await?;
+
+            Ok(())
+        })
+    }
+
+    fn prepared_branches(&mut self) -> BoxFuture<'_, Result<Vec<String>, Error>> {
+        Box::pin(async move {
+            // prepared transactions can only be finished from the database they belong to
+            query_scalar("SELECT gid FROM pg_prepared_xacts WHERE database = current_database()")
+                .fetch_all(self)
+                .await
+        })
+    }
+}
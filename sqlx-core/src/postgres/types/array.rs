@@ -208,6 +208,7 @@ where
                         row: None,
                         type_info: element_type_info.clone(),
                         format,
+                        type_overrides: None,
                     })?);
 
                     value.clear();
@@ -45,6 +45,10 @@ impl Type<Postgres> for f64 {
     fn type_info() -> PgTypeInfo {
         PgTypeInfo::FLOAT8
     }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        *ty == PgTypeInfo::FLOAT4 || *ty == PgTypeInfo::FLOAT8
+    }
 }
 
 impl Type<Postgres> for [f64] {
@@ -70,7 +74,17 @@ impl Encode<'_, Postgres> for f64 {
 impl Decode<'_, Postgres> for f64 {
     fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
         Ok(match value.format() {
-            PgValueFormat::Binary => BigEndian::read_f64(value.as_bytes()?),
+            PgValueFormat::Binary => {
+                let buf = value.as_bytes()?;
+
+                // a FLOAT4 value, which `f64` is widened from
+                if buf.len() == 4 {
+                    BigEndian::read_f32(buf).into()
+                } else {
+                    BigEndian::read_f64(buf)
+                }
+            }
+
             PgValueFormat::Text => value.as_str()?.parse()?,
         })
     }
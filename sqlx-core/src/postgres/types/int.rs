@@ -1,3 +1,5 @@
+use std::convert::TryFrom;
+
 use byteorder::{BigEndian, ByteOrder};
 
 use crate::decode::Decode;
@@ -113,6 +115,10 @@ impl Type<Postgres> for i32 {
     fn type_info() -> PgTypeInfo {
         PgTypeInfo::INT4
     }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        *ty == PgTypeInfo::INT2 || *ty == PgTypeInfo::INT4
+    }
 }
 
 impl Type<Postgres> for [i32] {
@@ -137,10 +143,7 @@ impl Encode<'_, Postgres> for i32 {
 
 impl Decode<'_, Postgres> for i32 {
     fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
-        Ok(match value.format() {
-            PgValueFormat::Binary => BigEndian::read_i32(value.as_bytes()?),
-            PgValueFormat::Text => value.as_str()?.parse()?,
-        })
+        Ok(i32::try_from(decode_int(value)?)?)
     }
 }
 
@@ -148,6 +151,10 @@ impl Type<Postgres> for i64 {
     fn type_info() -> PgTypeInfo {
         PgTypeInfo::INT8
     }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        *ty == PgTypeInfo::INT2 || *ty == PgTypeInfo::INT4 || *ty == PgTypeInfo::INT8
+    }
 }
 
 impl Type<Postgres> for [i64] {
@@ -172,9 +179,52 @@ impl Encode<'_, Postgres> for i64 {
 
 impl Decode<'_, Postgres> for i64 {
     fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
-        Ok(match value.format() {
-            PgValueFormat::Binary => BigEndian::read_i64(value.as_bytes()?),
-            PgValueFormat::Text => value.as_str()?.parse()?,
-        })
+        decode_int(value)
     }
 }
+
+// decodes an INT2, INT4 or INT8 value, which `i32` and `i64` are widened from
+fn decode_int(value: PgValueRef<'_>) -> Result<i64, BoxDynError> {
+    Ok(match value.format() {
+        PgValueFormat::Binary => {
+            let buf = value.as_bytes()?;
+
+            match buf.len() {
+                2 => BigEndian::read_i16(buf).into(),
+                4 => BigEndian::read_i32(buf).into(),
+                8 => BigEndian::read_i64(buf),
+
+                len => {
+                    return Err(
+                        format!("expected 2, 4 or 8 bytes for an integer, got {}", len).into(),
+                    );
+                }
+            }
+        }
+
+        PgValueFormat::Text => value.as_str()?.parse()?,
+    })
+}
+
+#[test]
+fn test_decode_widened_int() {
+    let value = |value, type_info| PgValueRef {
+        value: Some(value),
+        row: None,
+        type_info,
+        format: PgValueFormat::Binary,
+        type_overrides: None,
+    };
+
+    let int2 = value(&[0xff, 0xfe], PgTypeInfo::INT2);
+    assert_eq!(<i64 as Decode<Postgres>>::decode(int2).unwrap(), -2);
+
+    let int4 = value(&[0, 0, 1, 0], PgTypeInfo::INT4);
+    assert_eq!(<i64 as Decode<Postgres>>::decode(int4).unwrap(), 256);
+
+    let int8 = value(&[0, 0, 0, 1, 0, 0, 0, 0], PgTypeInfo::INT8);
+    assert!(<i32 as Decode<Postgres>>::decode(int8).is_err());
+
+    let truncated = value(&[0, 0, 1], PgTypeInfo::INT4);
+    assert!(<i64 as Decode<Postgres>>::decode(truncated).is_err());
+}
@@ -21,6 +21,8 @@
 //! [`PgRange<T>`]: struct.PgRange.html
 //! [`PgMoney`]: struct.PgMoney.html
 //!
+//! When decoding, `i32` also accepts INT2, `i64` accepts INT2 and INT4, and `f64` accepts FLOAT4.
+//!
 //! ### [`bigdecimal`](https://crates.io/crates/bigdecimal)
 //! Requires the `bigdecimal` Cargo feature flag.
 //!
@@ -493,6 +493,7 @@ where
                             format: PgValueFormat::Text,
                             value: Some(element.as_bytes()),
                             row: None,
+                            type_overrides: None,
                         })?);
 
                         if count == 1 {
@@ -196,6 +196,7 @@ impl<'r> PgRecordDecoder<'r> {
                     format: self.fmt,
                     value: buf,
                     row: None,
+                    type_overrides: None,
                 })
             }
         }
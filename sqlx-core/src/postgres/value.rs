@@ -1,9 +1,11 @@
 use crate::error::{BoxDynError, UnexpectedNullError};
 use crate::postgres::{PgTypeInfo, Postgres};
+use crate::type_overrides::TypeOverrides;
 use crate::value::{Value, ValueRef};
 use bytes::{Buf, Bytes};
 use std::borrow::Cow;
 use std::str::from_utf8;
+use std::sync::Arc;
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 #[repr(u8)]
@@ -19,6 +21,7 @@ pub struct PgValueRef<'r> {
     pub(crate) row: Option<&'r Bytes>,
     pub(crate) type_info: PgTypeInfo,
     pub(crate) format: PgValueFormat,
+    pub(crate) type_overrides: Option<&'r Arc<TypeOverrides<Postgres>>>,
 }
 
 /// Implementation of [`Value`] for PostgreSQL.
@@ -27,6 +30,7 @@ pub struct PgValue {
     pub(crate) value: Option<Bytes>,
     pub(crate) type_info: PgTypeInfo,
     pub(crate) format: PgValueFormat,
+    pub(crate) type_overrides: Option<Arc<TypeOverrides<Postgres>>>,
 }
 
 impl<'r> PgValueRef<'r> {
@@ -47,6 +51,7 @@ impl<'r> PgValueRef<'r> {
             row: None,
             type_info: ty,
             format,
+            type_overrides: None,
         }
     }
 
@@ -76,6 +81,7 @@ impl Value for PgValue {
             row: None,
             type_info: self.type_info.clone(),
             format: self.format,
+            type_overrides: self.type_overrides.as_ref(),
         }
     }
 
@@ -86,6 +92,10 @@ impl Value for PgValue {
     fn is_null(&self) -> bool {
         self.value.is_none()
     }
+
+    fn type_overrides(&self) -> Option<&TypeOverrides<Postgres>> {
+        self.type_overrides.as_deref()
+    }
 }
 
 impl<'r> ValueRef<'r> for PgValueRef<'r> {
@@ -104,6 +114,7 @@ impl<'r> ValueRef<'r> for PgValueRef<'r> {
             value,
             format: self.format,
             type_info: self.type_info.clone(),
+            type_overrides: self.type_overrides.cloned(),
         }
     }
 
@@ -123,6 +134,7 @@ impl<'r> From<PgValueRef<'r>> for crate::any::AnyValueRef<'r> {
         crate::any::AnyValueRef {
             type_info: value.type_info.clone().into(),
             kind: crate::any::value::AnyValueRefKind::Postgres(value),
+            type_overrides: None,
         }
     }
 }
@@ -134,6 +146,7 @@ impl From<PgValue> for crate::any::AnyValue {
         crate::any::AnyValue {
             type_info: value.type_info.clone().into(),
             kind: crate::any::value::AnyValueKind::Postgres(value),
+            type_overrides: None,
         }
     }
 }
@@ -9,8 +9,9 @@ use crate::database::{Database, HasArguments, HasStatement, HasStatementCache};
 use crate::encode::Encode;
 use crate::error::Error;
 use crate::executor::{Execute, Executor};
+use crate::returning::{Returning, ReturningStream};
 use crate::statement::Statement;
-use crate::types::Type;
+use crate::types::{Null, Type};
 
 /// Raw SQL query with bind parameters. Returned by [`query`][crate::query::query].
 #[must_use = "query must be executed to affect database"]
@@ -82,6 +83,17 @@ impl<'q, DB: Database> Query<'q, DB, <DB as HasArguments<'q>>::Arguments> {
 
         self
     }
+
+    /// Bind a `NULL` of the SQL type that `T` maps to.
+    ///
+    /// This is equivalent to `.bind(None::<T>)` or `.bind(Null::<T>::new())`, but states the
+    /// intent clearly. See [`Null`].
+    pub fn bind_null<T>(self) -> Self
+    where
+        Null<T>: 'q + Encode<'q, DB> + Type<DB>,
+    {
+        self.bind(Null::<T>::new())
+    }
 }
 
 impl<'q, DB, A> Query<'q, DB, A>
@@ -188,6 +200,33 @@ where
         executor.fetch_many(self)
     }
 
+    /// Execute a statement with a `RETURNING` clause, such as a bulk `INSERT`, `UPDATE` or
+    /// `DELETE`, and return the rows it returns as a stream that also counts the rows the
+    /// statement affected.
+    ///
+    /// See [`ReturningStream`].
+    #[inline]
+    pub fn fetch_returning<'e, 'c: 'e, E>(self, executor: E) -> ReturningStream<'e, DB, DB::Row>
+    where
+        'q: 'e,
+        A: 'e,
+        E: Executor<'c, Database = DB>,
+    {
+        ReturningStream::new(executor.fetch_many(self))
+    }
+
+    /// Execute a statement with a `RETURNING` clause and return the rows it returns along
+    /// with the number of rows it affected.
+    #[inline]
+    pub async fn execute_returning<'e, 'c: 'e, E>(self, executor: E) -> Result<Returning<DB>, Error>
+    where
+        'q: 'e,
+        A: 'e,
+        E: Executor<'c, Database = DB>,
+    {
+        self.fetch_returning(executor).finish().await
+    }
+
     /// Execute the query and return all the generated results, collected into a [`Vec`].
     #[inline]
     pub async fn fetch_all<'e, 'c: 'e, E>(self, executor: E) -> Result<Vec<DB::Row>, Error>
@@ -303,6 +342,37 @@ where
         })
     }
 
+    /// Execute a statement with a `RETURNING` clause and return the rows it returns as a
+    /// stream that also counts the rows the statement affected.
+    ///
+    /// See [`Query::fetch_returning`](crate::query::Query::fetch_returning).
+    pub fn fetch_returning<'e, 'c: 'e, E>(self, executor: E) -> ReturningStream<'e, DB, O>
+    where
+        'q: 'e,
+        E: 'e + Executor<'c, Database = DB>,
+        DB: 'e,
+        F: 'e,
+        O: 'e,
+    {
+        ReturningStream::new(self.fetch_many(executor))
+    }
+
+    /// Execute a statement with a `RETURNING` clause and return the rows it returns along
+    /// with the number of rows it affected.
+    pub async fn execute_returning<'e, 'c: 'e, E>(
+        self,
+        executor: E,
+    ) -> Result<Returning<DB, O>, Error>
+    where
+        'q: 'e,
+        E: 'e + Executor<'c, Database = DB>,
+        DB: 'e,
+        F: 'e,
+        O: 'e,
+    {
+        self.fetch_returning(executor).finish().await
+    }
+
     /// Execute the query and return all the generated results, collected into a [`Vec`].
     pub async fn fetch_all<'e, 'c: 'e, E>(self, executor: E) -> Result<Vec<O>, Error>
     where
@@ -10,8 +10,10 @@ use crate::encode::Encode;
 use crate::error::Error;
 use crate::executor::{Execute, Executor};
 use crate::from_row::FromRow;
+use crate::offload::DecodeOffload;
 use crate::query::{query, query_statement, query_statement_with, query_with, Query};
-use crate::types::Type;
+use crate::returning::{Returning, ReturningStream};
+use crate::types::{Null, Type};
 
 /// Raw SQL query with bind parameters, mapped to a concrete type using [`FromRow`].
 /// Returned from [`query_as`].
@@ -55,6 +57,17 @@ impl<'q, DB: Database, O> QueryAs<'q, DB, O, <DB as HasArguments<'q>>::Arguments
         self.inner = self.inner.bind(value);
         self
     }
+
+    /// Bind a `NULL` of the SQL type that `T` maps to.
+    ///
+    /// See [`Query::bind_null`](crate::query::Query::bind_null).
+    pub fn bind_null<T>(mut self) -> Self
+    where
+        Null<T>: 'q + Encode<'q, DB> + Type<DB>,
+    {
+        self.inner = self.inner.bind_null::<T>();
+        self
+    }
 }
 
 // FIXME: This is very close, nearly 1:1 with `Map`
@@ -106,6 +119,37 @@ where
         })
     }
 
+    /// Execute a statement with a `RETURNING` clause and return the rows it returns as a
+    /// stream that also counts the rows the statement affected.
+    ///
+    /// See [`Query::fetch_returning`](crate::query::Query::fetch_returning).
+    pub fn fetch_returning<'e, 'c: 'e, E>(self, executor: E) -> ReturningStream<'e, DB, O>
+    where
+        'q: 'e,
+        E: 'e + Executor<'c, Database = DB>,
+        DB: 'e,
+        O: 'e,
+        A: 'e,
+    {
+        ReturningStream::new(self.fetch_many(executor))
+    }
+
+    /// Execute a statement with a `RETURNING` clause and return the rows it returns along
+    /// with the number of rows it affected.
+    pub async fn execute_returning<'e, 'c: 'e, E>(
+        self,
+        executor: E,
+    ) -> Result<Returning<DB, O>, Error>
+    where
+        'q: 'e,
+        E: 'e + Executor<'c, Database = DB>,
+        DB: 'e,
+        O: 'e,
+        A: 'e,
+    {
+        self.fetch_returning(executor).finish().await
+    }
+
     /// Execute the query and return all the generated results, collected into a [`Vec`].
     #[inline]
     pub async fn fetch_all<'e, 'c: 'e, E>(self, executor: E) -> Result<Vec<O>, Error>
@@ -119,6 +163,27 @@ where
         self.fetch(executor).try_collect().await
     }
 
+    /// Execute the query and return all the generated results, collected into a [`Vec`],
+    /// decoding large rows on the blocking thread pool.
+    ///
+    /// See [`DecodeOffload`].
+    pub async fn fetch_all_offloaded<'e, 'c: 'e, E>(
+        self,
+        executor: E,
+        offload: &DecodeOffload,
+    ) -> Result<Vec<O>, Error>
+    where
+        'q: 'e,
+        E: 'e + Executor<'c, Database = DB>,
+        DB: 'e,
+        O: 'static,
+        A: 'e,
+    {
+        let rows = executor.fetch_all(self.inner).await?;
+
+        offload.decode_all(rows).await
+    }
+
     /// Execute the query and returns exactly one row.
     pub async fn fetch_one<'e, 'c: 'e, E>(self, executor: E) -> Result<O, Error>
     where
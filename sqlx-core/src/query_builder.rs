@@ -0,0 +1,324 @@
+//! Runtime query-builder API.
+
+use std::fmt::Display;
+use std::marker::PhantomData;
+
+use either::Either;
+
+use crate::arguments::Arguments;
+use crate::database::{Database, HasArguments};
+use crate::encode::Encode;
+use crate::query::Query;
+use crate::types::Type;
+
+/// A builder type for constructing queries at runtime.
+///
+/// SQL fragments are appended with [`push`][Self::push] and bind parameters with
+/// [`push_bind`][Self::push_bind]; the builder takes care of emitting the placeholder syntax
+/// expected by the database (`?`, `$N` or `@pN`).
+///
+/// Note that the builder does not check or escape the fragments passed to `push`. Only pass
+/// trusted SQL (table and column names from your own code) and use `push_bind` for everything
+/// that came from a user.
+///
+/// ```rust,ignore
+/// let mut builder = QueryBuilder::<Postgres>::new("SELECT * FROM users WHERE id = ");
+///
+/// builder.push_bind(user_id);
+///
+/// let user = builder.build().fetch_one(&pool).await?;
+/// ```
+pub struct QueryBuilder<'args, DB>
+where
+    DB: Database,
+{
+    query: String,
+    init_len: usize,
+    arguments: Option<<DB as HasArguments<'args>>::Arguments>,
+
+    // the arguments the builder started with, to start over from on reset
+    template: <DB as HasArguments<'args>>::Arguments,
+}
+
+impl<'args, DB> QueryBuilder<'args, DB>
+where
+    DB: Database,
+{
+    /// Start building a query with an initial SQL fragment, which may be an empty string.
+    pub fn new(init: impl Into<String>) -> Self {
+        Self::with_arguments(init, Default::default())
+    }
+
+    /// Start building a query with an initial SQL fragment, binding parameters to `arguments`,
+    /// which should be empty.
+    ///
+    /// With the `Any` driver, pass [`AnyArguments::for_kind`] so the builder writes the
+    /// placeholders of the database the query will run on:
+    ///
+    /// ```rust,ignore
+    /// let mut builder = QueryBuilder::<Any>::with_arguments(
+    ///     "SELECT * FROM users WHERE id = ",
+    ///     AnyArguments::for_kind(conn.kind()),
+    /// );
+    /// ```
+    ///
+    /// [`AnyArguments::for_kind`]: crate::any::AnyArguments::for_kind
+    pub fn with_arguments(
+        init: impl Into<String>,
+        arguments: <DB as HasArguments<'args>>::Arguments,
+    ) -> Self {
+        let query = init.into();
+
+        QueryBuilder {
+            init_len: query.len(),
+            query,
+            template: arguments.empty_like(),
+            arguments: Some(arguments),
+        }
+    }
+
+    #[inline]
+    fn sanity_check(&self) {
+        assert!(
+            self.arguments.is_some(),
+            "QueryBuilder must be reset before reuse after `.build()`"
+        );
+    }
+
+    /// Append a SQL fragment to the query.
+    ///
+    /// The fragment is appended verbatim; see the [type-level documentation][Self] for the
+    /// caveats around untrusted input.
+    pub fn push(&mut self, sql: impl Display) -> &mut Self {
+        self.sanity_check();
+
+        use std::fmt::Write;
+        write!(self.query, "{}", sql).expect("error formatting `sql`");
+
+        self
+    }
+
+    /// Push a bind parameter placeholder to the query and bind `value` to it.
+    pub fn push_bind<T>(&mut self, value: T) -> &mut Self
+    where
+        T: 'args + Send + Encode<'args, DB> + Type<DB>,
+    {
+        self.sanity_check();
+
+        let arguments = self
+            .arguments
+            .as_mut()
+            .expect("BUG: Arguments taken already");
+
+        arguments.add(value);
+
+        arguments
+            .format_placeholder(&mut self.query)
+            .expect("error in format_placeholder");
+
+        self
+    }
+
+    /// Start a list of fragments and bind parameters separated by `separator`.
+    ///
+    /// The separator is pushed before every element except the first, so dynamic lists can
+    /// be built without tracking whether a trailing separator needs to be removed:
+    ///
+    /// ```rust,ignore
+    /// let mut builder = QueryBuilder::<Postgres>::new("SELECT * FROM users WHERE id IN (");
+    ///
+    /// let mut ids = builder.separated(", ");
+    ///
+    /// for id in user_ids {
+    ///     ids.push_bind(id);
+    /// }
+    ///
+    /// ids.push_unseparated(")");
+    /// ```
+    pub fn separated<'qb, Sep>(&'qb mut self, separator: Sep) -> Separated<'qb, 'args, DB, Sep>
+    where
+        Sep: Display,
+    {
+        self.sanity_check();
+
+        Separated {
+            builder: self,
+            separator,
+            push_separator: false,
+        }
+    }
+
+    /// Produce an executable query from this builder.
+    ///
+    /// The builder cannot be used again until [`reset`][Self::reset] is called.
+    pub fn build(&mut self) -> Query<'_, DB, <DB as HasArguments<'args>>::Arguments> {
+        self.sanity_check();
+
+        Query {
+            statement: Either::Left(&self.query),
+            arguments: self.arguments.take(),
+            database: PhantomData,
+            persistent: true,
+        }
+    }
+
+    /// Reset the builder to the initial SQL fragment, discarding any bound arguments.
+    pub fn reset(&mut self) -> &mut Self {
+        self.query.truncate(self.init_len);
+        self.arguments = Some(self.template.empty_like());
+
+        self
+    }
+
+    /// Get the current SQL of the builder.
+    pub fn sql(&self) -> &str {
+        &self.query
+    }
+
+    /// Deconstruct the builder into its SQL, discarding any bound arguments.
+    pub fn into_sql(self) -> String {
+        self.query
+    }
+}
+
+/// A list of SQL fragments and bind parameters with a separator between each element.
+///
+/// Returned by [`QueryBuilder::separated`].
+pub struct Separated<'qb, 'args, DB, Sep>
+where
+    DB: Database,
+{
+    builder: &'qb mut QueryBuilder<'args, DB>,
+    separator: Sep,
+    push_separator: bool,
+}
+
+impl<'qb, 'args, DB, Sep> Separated<'qb, 'args, DB, Sep>
+where
+    DB: Database,
+    Sep: Display,
+{
+    /// Append a SQL fragment to the list, preceded by the separator unless it is the first
+    /// element.
+    pub fn push(&mut self, sql: impl Display) -> &mut Self {
+        self.push_separator();
+        self.builder.push(sql);
+
+        self
+    }
+
+    /// Append a SQL fragment to the query without a separator and without counting it as an
+    /// element of the list.
+    pub fn push_unseparated(&mut self, sql: impl Display) -> &mut Self {
+        self.builder.push(sql);
+
+        self
+    }
+
+    /// Push a bind parameter placeholder to the list, preceded by the separator unless it is
+    /// the first element, and bind `value` to it.
+    pub fn push_bind<T>(&mut self, value: T) -> &mut Self
+    where
+        T: 'args + Send + Encode<'args, DB> + Type<DB>,
+    {
+        self.push_separator();
+        self.builder.push_bind(value);
+
+        self
+    }
+
+    /// Push a bind parameter placeholder to the query without a separator and without
+    /// counting it as an element of the list, and bind `value` to it.
+    pub fn push_bind_unseparated<T>(&mut self, value: T) -> &mut Self
+    where
+        T: 'args + Send + Encode<'args, DB> + Type<DB>,
+    {
+        self.builder.push_bind(value);
+
+        self
+    }
+
+    fn push_separator(&mut self) {
+        if self.push_separator {
+            self.builder.push(&self.separator);
+        }
+
+        self.push_separator = true;
+    }
+}
+
+#[cfg(all(test, feature = "postgres"))]
+mod tests {
+    use super::QueryBuilder;
+    use crate::postgres::Postgres;
+
+    #[test]
+    fn it_separates_list_elements() {
+        let mut builder = QueryBuilder::<Postgres>::new("INSERT INTO users (");
+
+        let mut columns = builder.separated(", ");
+
+        for column in &["id", "name", "age"] {
+            columns.push(column);
+        }
+
+        columns.push_unseparated(") VALUES (");
+
+        let mut values = builder.separated(", ");
+
+        values.push_bind(1_i64);
+        values.push_bind("Alice");
+        values.push("DEFAULT");
+        values.push_unseparated(")");
+
+        assert_eq!(
+            builder.sql(),
+            "INSERT INTO users (id, name, age) VALUES ($1, $2, DEFAULT)"
+        );
+    }
+
+    #[test]
+    fn it_does_not_separate_unseparated_elements() {
+        let mut builder = QueryBuilder::<Postgres>::new("SELECT ");
+
+        let mut list = builder.separated(" + ");
+
+        list.push_unseparated("(");
+        list.push_bind_unseparated(1_i32);
+        list.push_unseparated(")");
+        list.push_bind(2_i32);
+        list.push_bind(3_i32);
+
+        assert_eq!(builder.sql(), "SELECT ($1)$2 + $3");
+    }
+
+    #[test]
+    #[cfg(feature = "any")]
+    fn it_writes_the_placeholders_of_any_kind() {
+        use crate::any::{Any, AnyArguments, AnyKind};
+
+        let mut builder = QueryBuilder::<Any>::with_arguments(
+            "SELECT * FROM users WHERE id = ",
+            AnyArguments::for_kind(AnyKind::Postgres),
+        );
+
+        builder.push_bind(1_i64).push(" OR id = ").push_bind(2_i64);
+
+        assert_eq!(
+            builder.sql(),
+            "SELECT * FROM users WHERE id = $1 OR id = $2"
+        );
+
+        let _ = builder.build();
+
+        builder.reset().push_bind(3_i64);
+
+        assert_eq!(builder.sql(), "SELECT * FROM users WHERE id = $1");
+
+        let mut builder = QueryBuilder::<Any>::new("SELECT ");
+
+        builder.push_bind(1_i64);
+
+        assert_eq!(builder.sql(), "SELECT ?");
+    }
+}
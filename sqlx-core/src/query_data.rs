@@ -0,0 +1,64 @@
+//! Names shared by the query macros and `cargo sqlx prepare` for the database a query is
+//! checked against, which is either the default one or one named with `query!(db = "...")`.
+
+use std::path::{Path, PathBuf};
+
+/// The environment variable holding the URL of the database named `db`, or of the default
+/// database: `DATABASE_URL` followed by `_` and the name in upper case, with `-` replaced by `_`.
+pub fn database_url_var(db: Option<&str>) -> String {
+    match db {
+        Some(db) => format!("DATABASE_URL_{}", db.to_ascii_uppercase().replace('-', "_")),
+        None => "DATABASE_URL".to_owned(),
+    }
+}
+
+/// The file, in the manifest directory, holding the offline data of the queries checked
+/// against the database named `db`.
+pub fn data_file_name(db: Option<&str>) -> String {
+    match db {
+        Some(db) => format!("sqlx-data-{}.json", db),
+        None => "sqlx-data.json".to_owned(),
+    }
+}
+
+/// The directory the query macros save the data of the queries checked against the database
+/// named `db` in, for `cargo sqlx prepare` to collect.
+pub fn query_data_dir(target_dir: impl AsRef<Path>, db: Option<&str>) -> PathBuf {
+    let mut dir = target_dir.as_ref().join("sqlx");
+
+    // the data of each named database is prepared separately
+    if let Some(db) = db {
+        dir.push(db);
+    }
+
+    dir
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{data_file_name, database_url_var, query_data_dir};
+    use std::path::Path;
+
+    #[test]
+    fn it_names_the_default_database() {
+        assert_eq!(database_url_var(None), "DATABASE_URL");
+        assert_eq!(data_file_name(None), "sqlx-data.json");
+        assert_eq!(query_data_dir("target", None), Path::new("target/sqlx"));
+    }
+
+    #[test]
+    fn it_names_named_databases() {
+        assert_eq!(
+            database_url_var(Some("read-replica")),
+            "DATABASE_URL_READ_REPLICA"
+        );
+        assert_eq!(
+            data_file_name(Some("read-replica")),
+            "sqlx-data-read-replica.json"
+        );
+        assert_eq!(
+            query_data_dir("target", Some("read-replica")),
+            Path::new("target/sqlx/read-replica")
+        );
+    }
+}
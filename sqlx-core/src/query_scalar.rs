@@ -11,7 +11,8 @@ use crate::from_row::FromRow;
 use crate::query_as::{
     query_as, query_as_with, query_statement_as, query_statement_as_with, QueryAs,
 };
-use crate::types::Type;
+use crate::returning::{Returning, ReturningStream};
+use crate::types::{Null, Type};
 
 /// Raw SQL query with bind parameters, mapped to a concrete type using [`FromRow`] on `(O,)`.
 /// Returned from [`query_scalar`].
@@ -52,6 +53,17 @@ impl<'q, DB: Database, O> QueryScalar<'q, DB, O, <DB as HasArguments<'q>>::Argum
         self.inner = self.inner.bind(value);
         self
     }
+
+    /// Bind a `NULL` of the SQL type that `T` maps to.
+    ///
+    /// See [`Query::bind_null`](crate::query::Query::bind_null).
+    pub fn bind_null<T>(mut self) -> Self
+    where
+        Null<T>: 'q + Encode<'q, DB> + Type<DB>,
+    {
+        self.inner = self.inner.bind_null::<T>();
+        self
+    }
 }
 
 // FIXME: This is very close, nearly 1:1 with `Map`
@@ -96,6 +108,39 @@ where
             .boxed()
     }
 
+    /// Execute a statement with a `RETURNING` clause and return the rows it returns as a
+    /// stream that also counts the rows the statement affected.
+    ///
+    /// See [`Query::fetch_returning`](crate::query::Query::fetch_returning).
+    #[inline]
+    pub fn fetch_returning<'e, 'c: 'e, E>(self, executor: E) -> ReturningStream<'e, DB, O>
+    where
+        'q: 'e,
+        E: 'e + Executor<'c, Database = DB>,
+        DB: 'e,
+        A: 'e,
+        O: 'e,
+    {
+        ReturningStream::new(self.fetch_many(executor))
+    }
+
+    /// Execute a statement with a `RETURNING` clause and return the rows it returns along
+    /// with the number of rows it affected.
+    #[inline]
+    pub async fn execute_returning<'e, 'c: 'e, E>(
+        self,
+        executor: E,
+    ) -> Result<Returning<DB, O>, Error>
+    where
+        'q: 'e,
+        E: 'e + Executor<'c, Database = DB>,
+        DB: 'e,
+        A: 'e,
+        O: 'e,
+    {
+        self.fetch_returning(executor).finish().await
+    }
+
     /// Execute the query and return all the generated results, collected into a [`Vec`].
     #[inline]
     pub async fn fetch_all<'e, 'c: 'e, E>(self, executor: E) -> Result<Vec<O>, Error>
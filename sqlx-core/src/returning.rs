@@ -0,0 +1,161 @@
+//! Rows returned by data-modifying statements, along with the number of rows they affected.
+
+use std::fmt::{self, Debug, Formatter};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use either::Either;
+use futures_core::ready;
+use futures_core::stream::{BoxStream, Stream};
+use futures_util::TryStreamExt;
+
+use crate::database::Database;
+use crate::done::Done;
+use crate::error::Error;
+
+/// The rows returned by an `INSERT`, `UPDATE` or `DELETE` with a `RETURNING` clause, and the
+/// number of rows it affected.
+///
+/// Returned by `execute_returning` on queries.
+pub struct Returning<DB: Database, O = <DB as Database>::Row> {
+    rows: Vec<O>,
+    done: DB::Done,
+}
+
+impl<DB: Database, O> Returning<DB, O> {
+    /// The rows returned by the statement.
+    pub fn rows(&self) -> &[O] {
+        &self.rows
+    }
+
+    /// Returns the number of rows affected by the statement, which can differ from the number
+    /// of rows it returned.
+    pub fn rows_affected(&self) -> u64 {
+        self.done.rows_affected()
+    }
+
+    /// The result of the statement, as would be returned by `execute`.
+    pub fn done(&self) -> &DB::Done {
+        &self.done
+    }
+
+    pub fn into_rows(self) -> Vec<O> {
+        self.rows
+    }
+
+    pub fn into_parts(self) -> (Vec<O>, DB::Done) {
+        (self.rows, self.done)
+    }
+}
+
+impl<DB, O> Debug for Returning<DB, O>
+where
+    DB: Database,
+    DB::Done: Debug,
+    O: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Returning")
+            .field("rows", &self.rows)
+            .field("done", &self.done)
+            .finish()
+    }
+}
+
+/// A stream of the rows returned by an `INSERT`, `UPDATE` or `DELETE` with a `RETURNING`
+/// clause, which also counts the rows the statement affected.
+///
+/// Returned by `fetch_returning` on queries.
+///
+/// ```rust,ignore
+/// let mut returned = sqlx::query_as::<_, Job>("DELETE FROM jobs WHERE done RETURNING *")
+///     .fetch_returning(&mut conn);
+///
+/// while let Some(job) = returned.try_next().await? {
+///     archive(job).await?;
+/// }
+///
+/// log::info!("deleted {} jobs", returned.rows_affected());
+/// ```
+pub struct ReturningStream<'e, DB: Database, O> {
+    inner: BoxStream<'e, Result<Either<DB::Done, O>, Error>>,
+    done: DB::Done,
+    finished: bool,
+}
+
+impl<'e, DB: Database, O> ReturningStream<'e, DB, O> {
+    pub(crate) fn new(inner: BoxStream<'e, Result<Either<DB::Done, O>, Error>>) -> Self {
+        Self {
+            inner,
+            done: Default::default(),
+            finished: false,
+        }
+    }
+
+    /// Returns the number of rows affected by the statement.
+    ///
+    /// This is only known once the stream has ended; before that, this only counts the rows
+    /// affected by the statements that completed, if the query holds several.
+    pub fn rows_affected(&self) -> u64 {
+        self.done.rows_affected()
+    }
+
+    /// The result of the statements that completed so far.
+    pub fn done(&self) -> &DB::Done {
+        &self.done
+    }
+
+    /// Returns `true` once the stream has ended.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Read the rows remaining in the stream, returning them with the number of rows
+    /// affected by the statement.
+    pub async fn finish(mut self) -> Result<Returning<DB, O>, Error> {
+        let mut rows = Vec::new();
+
+        while let Some(row) = self.try_next().await? {
+            rows.push(row);
+        }
+
+        Ok(Returning {
+            rows,
+            done: self.done,
+        })
+    }
+}
+
+impl<'e, DB: Database, O> Stream for ReturningStream<'e, DB, O> {
+    type Item = Result<O, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+
+        loop {
+            match ready!(this.inner.as_mut().poll_next(cx)) {
+                Some(Ok(Either::Left(done))) => this.done.extend(Some(done)),
+                Some(Ok(Either::Right(row))) => return Poll::Ready(Some(Ok(row))),
+                Some(Err(error)) => return Poll::Ready(Some(Err(error))),
+
+                None => {
+                    this.finished = true;
+
+                    return Poll::Ready(None);
+                }
+            }
+        }
+    }
+}
+
+// the stream is never pinned structurally
+impl<'e, DB: Database, O> Unpin for ReturningStream<'e, DB, O> {}
+
+impl<'e, DB: Database, O> Debug for ReturningStream<'e, DB, O> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReturningStream")
+            .field("rows_affected", &self.rows_affected())
+            .field("finished", &self.finished)
+            .finish()
+    }
+}
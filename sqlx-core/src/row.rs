@@ -3,6 +3,7 @@ use crate::database::{Database, HasValueRef};
 use crate::decode::Decode;
 use crate::error::{mismatched_types, Error};
 use crate::type_info::TypeInfo;
+use crate::type_overrides::TypeOverrides;
 use crate::types::Type;
 use crate::value::ValueRef;
 
@@ -98,6 +99,10 @@ pub trait Row: private_row::Sealed + Unpin + Send + Sync + 'static {
     /// A string index can be used to access a column by name and a `usize` index
     /// can be used to access a column by position.
     ///
+    /// On PostgreSQL, integer and floating point types can also be read from columns of narrower
+    /// types of the same kind. Conversions to other types can be registered with
+    /// [`Conversions`](crate::convert::Conversions).
+    ///
     /// # Errors
     ///
     ///  * [`ColumnNotFound`] if the column by the given name was not found.
@@ -118,7 +123,7 @@ pub trait Row: private_row::Sealed + Unpin + Send + Sync + 'static {
         if !value.is_null() {
             let ty = value.type_info();
 
-            if !ty.is_null() && !T::compatible(&ty) {
+            if !ty.is_null() && !TypeOverrides::compatible::<T>(self.type_overrides(), &ty) {
                 return Err(Error::ColumnDecode {
                     index: format!("{:?}", index),
                     source: mismatched_types::<Self::Database, T>(&ty),
@@ -178,9 +183,20 @@ pub trait Row: private_row::Sealed + Unpin + Send + Sync + 'static {
     ) -> Result<<Self::Database as HasValueRef<'_>>::ValueRef, Error>
     where
         I: ColumnIndex<Self>;
+
+    // the overrides set on the connection this row was returned by
+    #[doc(hidden)]
+    fn type_overrides(&self) -> Option<&TypeOverrides<Self::Database>> {
+        None
+    }
 }
 
 // Prevent users from implementing the `Row` trait.
 pub(crate) mod private_row {
-    pub trait Sealed {}
+    pub trait Sealed {
+        // the number of bytes of row data held in memory, or 0 if not known
+        fn data_len(&self) -> usize {
+            0
+        }
+    }
 }
@@ -0,0 +1,587 @@
+//! Execute parameterized scripts of several statements.
+
+use crate::arguments::{Arguments, IntoArguments};
+use crate::database::{Database, HasArguments};
+use crate::encode::Encode;
+use crate::error::Error;
+use crate::executor::Executor;
+use crate::query::query_with;
+use crate::types::Type;
+
+/// A script of one or more SQL statements sharing a single list of bind parameters.
+///
+/// Databases generally refuse to prepare more than one statement at a time. A script is
+/// split on `;` into its statements, which are then prepared and executed in order with the
+/// parameters each one refers to. Placeholders are numbered across the whole script and
+/// renumbered for each statement, so `$2` (or the second `?`) always refers to the second
+/// bound value:
+///
+/// ```rust,ignore
+/// let done = Script::<Postgres>::new(
+///     "INSERT INTO accounts (id, owner) VALUES ($1, $2);
+///      INSERT INTO audit_log (account_id, action) VALUES ($1, 'created');",
+/// )
+/// .bind(account_id)
+/// .bind(owner)
+/// .execute(&mut tx)
+/// .await?;
+/// ```
+///
+/// The statements are executed one after another on the same connection, but not inside a
+/// transaction; execute the script on a [`Transaction`] if the statements must succeed or fail
+/// together.
+///
+/// The splitter understands quoted strings and identifiers, comments, and PostgreSQL
+/// dollar-quoted strings, so a `;` or placeholder inside any of these is left alone. A `;`
+/// inside a `BEGIN ... END` or `CASE ... END` block, such as the body of a `CREATE TRIGGER`
+/// or of a MySQL procedure, does not end the statement either; a `BEGIN` at the start of a
+/// statement is taken to start a transaction instead. It does not understand backslash
+/// escapes in string literals, nor procedural code outside of these blocks.
+///
+/// [`Transaction`]: crate::transaction::Transaction
+pub struct Script<DB: Database> {
+    sql: String,
+    values: Vec<Box<dyn BindValue<DB>>>,
+}
+
+// a bound value that can be added to the arguments of every statement that refers to it
+trait BindValue<DB: Database>: Send + Sync {
+    fn bind<'a>(&self, arguments: &mut <DB as HasArguments<'a>>::Arguments);
+}
+
+impl<DB, T> BindValue<DB> for T
+where
+    DB: Database,
+    T: 'static + Clone + Send + Sync + Type<DB>,
+    for<'a> T: Encode<'a, DB>,
+{
+    fn bind<'a>(&self, arguments: &mut <DB as HasArguments<'a>>::Arguments) {
+        arguments.add(self.clone());
+    }
+}
+
+impl<DB> Script<DB>
+where
+    DB: Database,
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+    for<'a> <DB as HasArguments<'a>>::Arguments: IntoArguments<'a, DB>,
+{
+    /// Start a script with the given SQL.
+    pub fn new(sql: impl Into<String>) -> Self {
+        Self {
+            sql: sql.into(),
+            values: Vec::new(),
+        }
+    }
+
+    /// Bind the next value to the script.
+    ///
+    /// The value is cloned for each statement that refers to it, and so must be owned;
+    /// bind a `String` rather than a `&str`.
+    pub fn bind<T>(mut self, value: T) -> Self
+    where
+        T: 'static + Clone + Send + Sync + Type<DB>,
+        for<'a> T: Encode<'a, DB>,
+    {
+        self.values.push(Box::new(value));
+        self
+    }
+
+    /// Execute every statement of the script, returning the combined result.
+    ///
+    /// Stops at the first statement that fails. Nothing is executed if a placeholder is
+    /// numbered from zero or refers to a parameter that has not been bound.
+    pub async fn execute(&self, conn: &mut DB::Connection) -> Result<DB::Done, Error> {
+        let mut done = DB::Done::default();
+
+        for statement in self.statements()? {
+            let mut sql = String::new();
+            let mut arguments = <DB as HasArguments<'_>>::Arguments::default();
+
+            self.build(&statement, &mut sql, &mut arguments);
+
+            done.extend(Some(query_with(&sql, arguments).execute(&mut *conn).await?));
+        }
+
+        Ok(done)
+    }
+
+    /// Execute every statement of the script, returning the rows of all statements in order.
+    ///
+    /// Stops at the first statement that fails. Nothing is executed if a placeholder is
+    /// numbered from zero or refers to a parameter that has not been bound.
+    pub async fn fetch_all(&self, conn: &mut DB::Connection) -> Result<Vec<DB::Row>, Error> {
+        let mut rows = Vec::new();
+
+        for statement in self.statements()? {
+            let mut sql = String::new();
+            let mut arguments = <DB as HasArguments<'_>>::Arguments::default();
+
+            self.build(&statement, &mut sql, &mut arguments);
+
+            rows.extend(query_with(&sql, arguments).fetch_all(&mut *conn).await?);
+        }
+
+        Ok(rows)
+    }
+
+    // split the script into its statements, checking that every parameter has been bound
+    fn statements(&self) -> Result<Vec<Vec<Piece<'_>>>, Error> {
+        let statements = split(&self.sql, placeholder_style::<DB>())?;
+
+        let unbound = statements
+            .iter()
+            .flatten()
+            .filter_map(|piece| match *piece {
+                Piece::Parameter(index) if index >= self.values.len() => Some(index),
+                _ => None,
+            })
+            .max();
+
+        if let Some(index) = unbound {
+            return Err(Error::Configuration(
+                format!(
+                    "script refers to parameter {} but only {} were bound",
+                    index + 1,
+                    self.values.len()
+                )
+                .into(),
+            ));
+        }
+
+        Ok(statements)
+    }
+
+    fn build<'a>(
+        &self,
+        statement: &[Piece<'_>],
+        sql: &mut String,
+        arguments: &mut <DB as HasArguments<'a>>::Arguments,
+    ) {
+        for piece in statement {
+            match *piece {
+                Piece::Sql(s) => sql.push_str(s),
+
+                Piece::Parameter(index) => {
+                    self.values[index].bind(arguments);
+
+                    arguments
+                        .format_placeholder(sql)
+                        .expect("error in format_placeholder");
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum PlaceholderStyle {
+    // `?` or `?NNN`
+    Question,
+
+    // `$N`
+    Dollar,
+
+    // `@pN`
+    AtP,
+}
+
+// find the placeholder syntax of the database from the placeholder it writes
+fn placeholder_style<DB: Database>() -> PlaceholderStyle {
+    let mut placeholder = String::new();
+
+    <DB as HasArguments<'_>>::Arguments::default()
+        .format_placeholder(&mut placeholder)
+        .expect("error in format_placeholder");
+
+    if placeholder.starts_with('$') {
+        PlaceholderStyle::Dollar
+    } else if placeholder.starts_with('@') {
+        PlaceholderStyle::AtP
+    } else {
+        PlaceholderStyle::Question
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Piece<'s> {
+    Sql(&'s str),
+
+    // zero-based index into the bound values
+    Parameter(usize),
+}
+
+// split `sql` into statements, each a sequence of SQL text and parameter references;
+// statements containing only whitespace and comments are dropped
+fn split(sql: &str, style: PlaceholderStyle) -> Result<Vec<Vec<Piece<'_>>>, Error> {
+    let bytes = sql.as_bytes();
+
+    let mut statements = Vec::new();
+    let mut pieces = Vec::new();
+
+    // start of the SQL text not yet added to `pieces`
+    let mut start = 0;
+    // whether the current statement contains anything other than whitespace and comments
+    let mut significant = false;
+    // the index of the next bare `?`
+    let mut next_question = 0;
+    // the number of `BEGIN` and `CASE` blocks open, inside which `;` does not end a statement
+    let mut depth = 0_usize;
+
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let b = bytes[i];
+        let prev_is_ident = i > 0 && is_ident(bytes[i - 1]);
+
+        match b {
+            b'\'' | b'"' | b'`' => {
+                i = skip_past(bytes, i + 1, &[b]);
+            }
+
+            b'[' if style == PlaceholderStyle::AtP => {
+                i = skip_past(bytes, i + 1, b"]");
+            }
+
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                i = skip_past(bytes, i + 2, b"\n");
+                continue;
+            }
+
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = skip_past(bytes, i + 2, b"*/");
+                continue;
+            }
+
+            b';' if depth == 0 => {
+                pieces.push(Piece::Sql(&sql[start..i]));
+
+                if significant {
+                    statements.push(pieces);
+                }
+
+                pieces = Vec::new();
+                significant = false;
+                start = i + 1;
+                i += 1;
+
+                continue;
+            }
+
+            b'?' if style == PlaceholderStyle::Question => {
+                let (digits, end) = read_number(bytes, i + 1);
+
+                let index = match digits {
+                    Some(n) => parameter_index(n, &sql[i..end])?,
+                    None => next_question,
+                };
+
+                next_question = index + 1;
+
+                pieces.push(Piece::Sql(&sql[start..i]));
+                pieces.push(Piece::Parameter(index));
+
+                start = end;
+                i = end;
+                significant = true;
+
+                continue;
+            }
+
+            b'$' if style == PlaceholderStyle::Dollar && !prev_is_ident => {
+                if let (Some(n), end) = read_number(bytes, i + 1) {
+                    pieces.push(Piece::Sql(&sql[start..i]));
+                    pieces.push(Piece::Parameter(parameter_index(n, &sql[i..end])?));
+
+                    start = end;
+                    i = end;
+                    significant = true;
+
+                    continue;
+                }
+
+                // a dollar-quoted string: `$$ ... $$` or `$tag$ ... $tag$`
+                let tag_end = bytes[i + 1..]
+                    .iter()
+                    .position(|&b| !is_ident(b))
+                    .map(|n| i + 1 + n);
+
+                if let Some(tag_end) = tag_end.filter(|&end| bytes[end] == b'$') {
+                    let tag = &bytes[i..=tag_end];
+
+                    i = skip_past(bytes, tag_end + 1, tag);
+                    significant = true;
+
+                    continue;
+                }
+            }
+
+            b'@' if style == PlaceholderStyle::AtP
+                && !prev_is_ident
+                && matches!(bytes.get(i + 1), Some(b'p') | Some(b'P')) =>
+            {
+                if let (Some(n), end) = read_number(bytes, i + 2) {
+                    pieces.push(Piece::Sql(&sql[start..i]));
+                    pieces.push(Piece::Parameter(parameter_index(n, &sql[i..end])?));
+
+                    start = end;
+                    i = end;
+                    significant = true;
+
+                    continue;
+                }
+            }
+
+            _ if b.is_ascii_alphabetic() && !prev_is_ident => {
+                let (word, mut end) = next_word(bytes, i);
+
+                // a `BEGIN` that starts a statement starts a transaction rather than a block
+                if word.eq_ignore_ascii_case(b"CASE")
+                    || (word.eq_ignore_ascii_case(b"BEGIN") && significant)
+                {
+                    depth += 1;
+                } else if word.eq_ignore_ascii_case(b"END") {
+                    let (next, next_end) = next_word(bytes, end);
+
+                    // `END IF`, `END LOOP`, `END WHILE` and `END REPEAT` close blocks whose
+                    // start is not counted, and `END CASE` closes a `CASE`
+                    if ![&b"IF"[..], b"LOOP", b"WHILE", b"REPEAT"]
+                        .iter()
+                        .any(|keyword| next.eq_ignore_ascii_case(keyword))
+                    {
+                        depth = depth.saturating_sub(1);
+                    }
+
+                    if next.eq_ignore_ascii_case(b"CASE") {
+                        end = next_end;
+                    }
+                }
+
+                i = end;
+                significant = true;
+
+                continue;
+            }
+
+            _ => {}
+        }
+
+        if !b.is_ascii_whitespace() {
+            significant = true;
+        }
+
+        i += 1;
+    }
+
+    if significant {
+        pieces.push(Piece::Sql(&sql[start..]));
+        statements.push(pieces);
+    }
+
+    Ok(statements)
+}
+
+// placeholders are numbered from one
+fn parameter_index(number: usize, placeholder: &str) -> Result<usize, Error> {
+    number.checked_sub(1).ok_or_else(|| {
+        Error::Configuration(
+            format!(
+                "invalid placeholder `{}` in script: parameters are numbered from 1",
+                placeholder
+            )
+            .into(),
+        )
+    })
+}
+
+fn is_ident(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_' || b >= 0x80
+}
+
+// returns the word starting at `from`, after any whitespace, and the index past it
+fn next_word(bytes: &[u8], from: usize) -> (&[u8], usize) {
+    let start = from
+        + bytes[from..]
+            .iter()
+            .take_while(|b| b.is_ascii_whitespace())
+            .count();
+
+    let end = start + bytes[start..].iter().take_while(|&&b| is_ident(b)).count();
+
+    (&bytes[start..end], end)
+}
+
+// returns the index just past the next occurrence of `end` at or after `from`,
+// or the end of input if it does not occur
+fn skip_past(bytes: &[u8], from: usize, end: &[u8]) -> usize {
+    bytes
+        .get(from..)
+        .and_then(|rest| rest.windows(end.len()).position(|w| w == end))
+        .map_or(bytes.len(), |n| from + n + end.len())
+}
+
+// read a decimal number starting at `from`, returning it (if any) and the index past it
+fn read_number(bytes: &[u8], from: usize) -> (Option<usize>, usize) {
+    let len = bytes[from..]
+        .iter()
+        .take_while(|b| b.is_ascii_digit())
+        .count();
+
+    let number = std::str::from_utf8(&bytes[from..from + len])
+        .ok()
+        .and_then(|digits| digits.parse().ok());
+
+    (number, from + len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{split, Piece, PlaceholderStyle};
+    use crate::error::Error;
+
+    fn parameters(statement: &[Piece<'_>]) -> Vec<usize> {
+        statement
+            .iter()
+            .filter_map(|piece| match piece {
+                Piece::Parameter(index) => Some(*index),
+                Piece::Sql(_) => None,
+            })
+            .collect()
+    }
+
+    fn sql(statement: &[Piece<'_>]) -> String {
+        statement
+            .iter()
+            .map(|piece| match piece {
+                Piece::Parameter(_) => "#",
+                Piece::Sql(s) => s,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn it_splits_statements() {
+        let statements = split(
+            "INSERT INTO t VALUES ($1, 'a;b'); -- first\n\
+             UPDATE t SET v = $2 WHERE k = $1 /* ; */;\n\
+             -- trailing comment",
+            PlaceholderStyle::Dollar,
+        )
+        .unwrap();
+
+        assert_eq!(statements.len(), 2);
+
+        assert_eq!(sql(&statements[0]), "INSERT INTO t VALUES (#, 'a;b')");
+        assert_eq!(parameters(&statements[0]), [0]);
+
+        assert_eq!(
+            sql(&statements[1]),
+            " -- first\nUPDATE t SET v = # WHERE k = # /* ; */"
+        );
+        assert_eq!(parameters(&statements[1]), [1, 0]);
+    }
+
+    #[test]
+    fn it_numbers_question_marks_across_statements() {
+        let statements = split(
+            "INSERT INTO t VALUES (?, '?'); UPDATE t SET v = ? WHERE k = ?1",
+            PlaceholderStyle::Question,
+        )
+        .unwrap();
+
+        assert_eq!(statements.len(), 2);
+        assert_eq!(parameters(&statements[0]), [0]);
+        assert_eq!(parameters(&statements[1]), [1, 0]);
+    }
+
+    #[test]
+    fn it_skips_dollar_quoted_strings() {
+        let statements = split(
+            "CREATE FUNCTION f() RETURNS int AS $body$ SELECT $1; $body$ LANGUAGE sql; \
+             SELECT a$1 FROM t WHERE b = $$;$$ AND c = $1",
+            PlaceholderStyle::Dollar,
+        )
+        .unwrap();
+
+        assert_eq!(statements.len(), 2);
+        assert!(parameters(&statements[0]).is_empty());
+        assert_eq!(parameters(&statements[1]), [0]);
+    }
+
+    #[test]
+    fn it_recognizes_mssql_placeholders() {
+        let statements = split(
+            "SELECT [a;b] FROM t WHERE x = @p2; SELECT @@ROWCOUNT, @p1",
+            PlaceholderStyle::AtP,
+        )
+        .unwrap();
+
+        assert_eq!(statements.len(), 2);
+        assert_eq!(parameters(&statements[0]), [1]);
+        assert_eq!(parameters(&statements[1]), [0]);
+    }
+
+    #[test]
+    fn it_keeps_blocks_together() {
+        let statements = split(
+            "CREATE TRIGGER t_audit AFTER INSERT ON t BEGIN \
+                 INSERT INTO audit VALUES (new.id, CASE WHEN new.v > ? THEN 'big' ELSE 'small' END); \
+                 UPDATE counts SET n = n + 1; \
+             END; \
+             BEGIN; \
+             INSERT INTO t VALUES (?); \
+             COMMIT",
+            PlaceholderStyle::Question,
+        ).unwrap();
+
+        assert_eq!(statements.len(), 4);
+        assert!(sql(&statements[0]).ends_with("UPDATE counts SET n = n + 1; END"));
+        assert_eq!(parameters(&statements[0]), [0]);
+        assert_eq!(sql(&statements[1]).trim(), "BEGIN");
+        assert_eq!(parameters(&statements[2]), [1]);
+    }
+
+    #[test]
+    fn it_does_not_count_control_flow_ends() {
+        let statements = split(
+            "CREATE PROCEDURE p(x INT) BEGIN \
+                 IF x > 0 THEN SELECT 1; END IF; \
+                 WHILE x > 0 DO SET x = x - 1; END WHILE; \
+                 CASE x WHEN 0 THEN SELECT 0; ELSE SELECT 2; END CASE; \
+             END; \
+             CALL p(?)",
+            PlaceholderStyle::Question,
+        )
+        .unwrap();
+
+        assert_eq!(statements.len(), 2);
+        assert!(sql(&statements[0]).ends_with("END CASE; END"));
+        assert_eq!(parameters(&statements[1]), [0]);
+    }
+
+    #[test]
+    fn it_rejects_placeholders_numbered_from_zero() {
+        for (sql, style) in &[
+            ("SELECT $0", PlaceholderStyle::Dollar),
+            ("SELECT ?, ?0", PlaceholderStyle::Question),
+            ("SELECT @p0", PlaceholderStyle::AtP),
+        ] {
+            assert!(matches!(split(sql, *style), Err(Error::Configuration(_))));
+        }
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn it_rejects_unbound_parameters() {
+        let script =
+            super::Script::<crate::sqlite::Sqlite>::new("SELECT ?1; SELECT ?3, ?").bind(1_i32);
+
+        let error = script.statements().unwrap_err().to_string();
+
+        assert!(
+            error.contains("parameter 4 but only 1 were bound"),
+            "{}",
+            error
+        );
+    }
+}
@@ -0,0 +1,407 @@
+//! Capture a query and its bound arguments so it can be logged and replayed later.
+
+use std::marker::PhantomData;
+
+use either::Either;
+
+use crate::arguments::Arguments;
+use crate::database::{Database, HasArguments};
+use crate::encode::{Encode, IsNull};
+use crate::query::Query;
+use crate::statement::Statement;
+use crate::types::Type;
+
+/// A bound value captured in a [`QuerySnapshot`].
+///
+/// Values are captured as the closest of a small set of primitive types. On replay they are
+/// bound as `bool`, `i64`, `f64`, `String` or `Vec<u8>`; a `NULL` is bound as a `NULL` text
+/// value. Queries relying on the exact type of a parameter may need an explicit cast in the
+/// SQL to be replayed.
+///
+/// Values of other types the driver knows how to render, such as UUIDs, timestamps and
+/// numerics, are captured as [`Typed`][SnapshotValue::Typed] and replayed as their own type.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "json", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "json",
+    serde(tag = "type", content = "value", rename_all = "lowercase")
+)]
+pub enum SnapshotValue {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Text(String),
+    Bytes(Vec<u8>),
+
+    /// A value captured as the name of its type in the database and its text representation.
+    ///
+    /// PostgreSQL and MySQL bind it on replay as a value of the named type; other drivers bind
+    /// the text.
+    Typed {
+        type_name: String,
+        text: String,
+    },
+
+    /// A value removed by a [`Redaction`] rule.
+    Redacted,
+}
+
+macro_rules! impl_from_for_snapshot_value {
+    ($($ty:ty => $variant:ident),* $(,)?) => {
+        $(
+            impl From<$ty> for SnapshotValue {
+                fn from(value: $ty) -> Self {
+                    SnapshotValue::$variant(value.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_for_snapshot_value!(
+    bool => Bool,
+    i8 => Int,
+    i16 => Int,
+    i32 => Int,
+    i64 => Int,
+    u8 => Int,
+    u16 => Int,
+    u32 => Int,
+    f32 => Float,
+    f64 => Float,
+    String => Text,
+    &'_ str => Text,
+    Vec<u8> => Bytes,
+    &'_ [u8] => Bytes,
+);
+
+impl<T> From<Option<T>> for SnapshotValue
+where
+    T: Into<SnapshotValue>,
+{
+    fn from(value: Option<T>) -> Self {
+        value.map_or(SnapshotValue::Null, Into::into)
+    }
+}
+
+/// Rules for removing sensitive values from a [`QuerySnapshot`] before it is written out.
+#[derive(Debug, Clone, Default)]
+pub struct Redaction {
+    parameters: Vec<usize>,
+    text: bool,
+    bytes: bool,
+}
+
+impl Redaction {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Redact the parameter at `index`, counting from zero.
+    pub fn parameter(mut self, index: usize) -> Self {
+        self.parameters.push(index);
+        self
+    }
+
+    /// Redact every text parameter, including the text of [typed][SnapshotValue::Typed] values.
+    pub fn text(mut self) -> Self {
+        self.text = true;
+        self
+    }
+
+    /// Redact every binary parameter.
+    pub fn bytes(mut self) -> Self {
+        self.bytes = true;
+        self
+    }
+
+    fn applies(&self, index: usize, value: &SnapshotValue) -> bool {
+        match value {
+            SnapshotValue::Text(_) | SnapshotValue::Typed { .. } if self.text => true,
+            SnapshotValue::Bytes(_) if self.bytes => true,
+
+            _ => self.parameters.contains(&index),
+        }
+    }
+}
+
+/// A query and its bound arguments, captured for logging and replay.
+///
+/// ```rust,ignore
+/// let query = sqlx::query("SELECT * FROM users WHERE email = $1 AND password = $2")
+///     .bind(email)
+///     .bind(password);
+///
+/// let snapshot = QuerySnapshot::capture(&query);
+///
+/// if let (Err(error), Some(snapshot)) = (query.fetch_one(&pool).await, snapshot) {
+///     let redacted = snapshot.redact(&Redaction::new().parameter(1));
+///
+///     log::error!("query failed: {}; snapshot: {}", error, redacted.to_json()?);
+/// }
+///
+/// // later, with the snapshot copied from the log
+/// let snapshot = QuerySnapshot::from_json(line)?;
+/// let row = snapshot.query::<Postgres>().fetch_one(&mut conn).await?;
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "json", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuerySnapshot {
+    sql: String,
+    arguments: Vec<SnapshotValue>,
+}
+
+impl QuerySnapshot {
+    /// Start capturing a query with the given SQL.
+    pub fn new(sql: impl Into<String>) -> Self {
+        Self {
+            sql: sql.into(),
+            arguments: Vec::new(),
+        }
+    }
+
+    /// Capture the SQL and the arguments bound to `query`.
+    ///
+    /// Returns `None` if the driver cannot read its arguments back; see
+    /// [`Arguments::snapshot`].
+    pub fn capture<'q, DB>(
+        query: &Query<'q, DB, <DB as HasArguments<'q>>::Arguments>,
+    ) -> Option<Self>
+    where
+        DB: Database,
+    {
+        let arguments = match &query.arguments {
+            Some(arguments) => arguments.snapshot()?,
+            None => Vec::new(),
+        };
+
+        let sql = match query.statement {
+            Either::Left(sql) => sql,
+            Either::Right(statement) => statement.sql(),
+        };
+
+        Some(Self {
+            sql: sql.to_owned(),
+            arguments,
+        })
+    }
+
+    /// Capture the next bound argument.
+    pub fn bind(mut self, value: impl Into<SnapshotValue>) -> Self {
+        self.arguments.push(value.into());
+        self
+    }
+
+    /// Get the SQL of the captured query.
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+
+    /// Get the captured arguments.
+    pub fn arguments(&self) -> &[SnapshotValue] {
+        &self.arguments
+    }
+
+    /// Returns `true` if any argument has been redacted, in which case the snapshot can no
+    /// longer be replayed.
+    pub fn is_redacted(&self) -> bool {
+        self.arguments.contains(&SnapshotValue::Redacted)
+    }
+
+    /// Returns a copy of this snapshot with the arguments matching `rules` redacted.
+    pub fn redact(&self, rules: &Redaction) -> Self {
+        let arguments = self
+            .arguments
+            .iter()
+            .enumerate()
+            .map(|(index, value)| {
+                if rules.applies(index, value) {
+                    SnapshotValue::Redacted
+                } else {
+                    value.clone()
+                }
+            })
+            .collect();
+
+        Self {
+            sql: self.sql.clone(),
+            arguments,
+        }
+    }
+
+    /// Build a query that executes the captured SQL with the captured arguments.
+    ///
+    /// # Panics
+    /// If the snapshot has been [redacted][Self::redact].
+    pub fn query<'q, DB>(&'q self) -> Query<'q, DB, <DB as HasArguments<'q>>::Arguments>
+    where
+        DB: Database,
+        SnapshotValue: Encode<'q, DB> + Type<DB>,
+    {
+        assert!(
+            !self.is_redacted(),
+            "cannot replay a snapshot with redacted arguments"
+        );
+
+        let mut arguments = <DB as HasArguments<'q>>::Arguments::default();
+
+        for value in &self.arguments {
+            arguments.add_snapshot_value(value);
+        }
+
+        Query {
+            statement: Either::Left(&self.sql),
+            arguments: Some(arguments),
+            database: PhantomData,
+            persistent: true,
+        }
+    }
+}
+
+#[cfg(feature = "json")]
+impl QuerySnapshot {
+    /// Serialize the snapshot to a single line of JSON.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+
+    /// Deserialize a snapshot from JSON produced by [`to_json`][Self::to_json].
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+// values are encoded by delegating to the primitive type they were captured as, which also
+// determines the parameter type sent to the database
+impl<DB: Database> Type<DB> for SnapshotValue
+where
+    String: Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo {
+        <String as Type<DB>>::type_info()
+    }
+}
+
+impl<'q, DB: Database> Encode<'q, DB> for SnapshotValue
+where
+    bool: Encode<'q, DB> + Type<DB>,
+    i64: Encode<'q, DB> + Type<DB>,
+    f64: Encode<'q, DB> + Type<DB>,
+    String: Encode<'q, DB> + Type<DB>,
+    Vec<u8>: Encode<'q, DB> + Type<DB>,
+{
+    fn encode_by_ref(&self, buf: &mut <DB as HasArguments<'q>>::ArgumentBuffer) -> IsNull {
+        match self {
+            SnapshotValue::Null => IsNull::Yes,
+            SnapshotValue::Bool(v) => v.encode_by_ref(buf),
+            SnapshotValue::Int(v) => v.encode_by_ref(buf),
+            SnapshotValue::Float(v) => v.encode_by_ref(buf),
+            SnapshotValue::Text(v) => v.encode_by_ref(buf),
+            SnapshotValue::Bytes(v) => v.encode_by_ref(buf),
+            SnapshotValue::Typed { text, .. } => text.encode_by_ref(buf),
+
+            SnapshotValue::Redacted => panic!("cannot encode a redacted value"),
+        }
+    }
+
+    fn produces(&self) -> Option<DB::TypeInfo> {
+        Some(match self {
+            SnapshotValue::Bool(_) => <bool as Type<DB>>::type_info(),
+            SnapshotValue::Int(_) => <i64 as Type<DB>>::type_info(),
+            SnapshotValue::Float(_) => <f64 as Type<DB>>::type_info(),
+            SnapshotValue::Bytes(_) => <Vec<u8> as Type<DB>>::type_info(),
+
+            SnapshotValue::Null
+            | SnapshotValue::Text(_)
+            | SnapshotValue::Typed { .. }
+            | SnapshotValue::Redacted => <String as Type<DB>>::type_info(),
+        })
+    }
+
+    fn size_hint(&self) -> usize {
+        match self {
+            SnapshotValue::Text(v) => v.len(),
+            SnapshotValue::Bytes(v) => v.len(),
+            SnapshotValue::Typed { text, .. } => text.len(),
+
+            _ => 8,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{QuerySnapshot, Redaction, SnapshotValue};
+
+    fn snapshot() -> QuerySnapshot {
+        QuerySnapshot::new("SELECT * FROM users WHERE id = $1 AND name = $2 AND token = $3")
+            .bind(10_i32)
+            .bind("alice")
+            .bind(Some(&b"secret"[..]))
+    }
+
+    #[test]
+    fn it_captures_arguments() {
+        let snapshot = snapshot().bind(None::<i64>);
+
+        assert_eq!(
+            snapshot.arguments(),
+            &[
+                SnapshotValue::Int(10),
+                SnapshotValue::Text("alice".into()),
+                SnapshotValue::Bytes(b"secret".to_vec()),
+                SnapshotValue::Null,
+            ][..]
+        );
+    }
+
+    #[test]
+    fn it_redacts_arguments() {
+        let snapshot = snapshot();
+
+        let redacted = snapshot.redact(&Redaction::new().parameter(0).bytes());
+
+        assert!(!snapshot.is_redacted());
+        assert!(redacted.is_redacted());
+
+        assert_eq!(
+            redacted.arguments(),
+            &[
+                SnapshotValue::Redacted,
+                SnapshotValue::Text("alice".into()),
+                SnapshotValue::Redacted,
+            ][..]
+        );
+
+        assert_eq!(
+            snapshot.redact(&Redaction::new().text()).arguments()[1],
+            SnapshotValue::Redacted
+        );
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn it_captures_the_arguments_of_a_query() {
+        let query = crate::query::query::<crate::sqlite::Sqlite>("SELECT ?, ?")
+            .bind(10_i32)
+            .bind("alice");
+
+        let snapshot = QuerySnapshot::capture(&query).unwrap();
+
+        assert_eq!(snapshot.sql(), "SELECT ?, ?");
+        assert_eq!(
+            snapshot.arguments(),
+            &[SnapshotValue::Int(10), SnapshotValue::Text("alice".into())][..]
+        );
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn it_round_trips_through_json() {
+        let snapshot = snapshot();
+        let json = snapshot.to_json().unwrap();
+
+        assert_eq!(QuerySnapshot::from_json(&json).unwrap(), snapshot);
+    }
+}
@@ -1,6 +1,7 @@
 use crate::arguments::Arguments;
 use crate::encode::{Encode, IsNull};
 use crate::error::Error;
+use crate::snapshot::SnapshotValue;
 use crate::sqlite::statement::StatementHandle;
 use crate::sqlite::Sqlite;
 use atoi::atoi;
@@ -46,6 +47,19 @@ impl<'q> Arguments<'q> for SqliteArguments<'q> {
     {
         self.add(value)
     }
+
+    fn snapshot(&self) -> Option<Vec<SnapshotValue>> {
+        let values = self.values.iter().map(|value| match value {
+            SqliteArgumentValue::Null => SnapshotValue::Null,
+            SqliteArgumentValue::Text(text) => SnapshotValue::Text(text.clone().into_owned()),
+            SqliteArgumentValue::Blob(blob) => SnapshotValue::Bytes(blob.clone().into_owned()),
+            SqliteArgumentValue::Double(double) => SnapshotValue::Float(*double),
+            SqliteArgumentValue::Int(int) => SnapshotValue::Int((*int).into()),
+            SqliteArgumentValue::Int64(int) => SnapshotValue::Int(*int),
+        });
+
+        Some(values.collect())
+    }
 }
 
 impl SqliteArguments<'_> {
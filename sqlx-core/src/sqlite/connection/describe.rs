@@ -22,7 +22,9 @@ pub(super) fn describe<'c: 'e, 'q: 'e, 'e>(
         let mut nullable = Vec::new();
         let mut num_params = 0;
 
-        let mut statement = statement?;
+        // if this future is dropped while the statement is stepped, the statement is only
+        // finalized once the step completes
+        let mut statement = conn.worker.guard(statement?);
 
         // we start by finding the first statement that *can* return results
         while let Some((stmt, ..)) = statement.prepare(&mut conn.handle)? {
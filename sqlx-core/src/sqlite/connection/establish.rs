@@ -14,6 +14,7 @@ use sqlx_rt::blocking;
 use std::io;
 use std::{
     convert::TryFrom,
+    mem::ManuallyDrop,
     ptr::{null, null_mut},
 };
 
@@ -109,11 +110,13 @@ pub(crate) async fn establish(options: &SqliteConnectOptions) -> Result<SqliteCo
     })?;
 
     Ok(SqliteConnection {
-        handle,
+        handle: ManuallyDrop::new(handle),
         worker: StatementWorker::new(),
         statements: StatementCache::new(options.statement_cache_capacity),
         statement: None,
         transaction_depth: 0,
+        last_sql: String::new(),
+        type_overrides: None,
         log_settings: options.log_settings.clone(),
     })
 }
@@ -75,15 +75,22 @@ impl<'c> Executor<'c> for &'c mut SqliteConnection {
         let arguments = query.take_arguments();
         let persistent = query.persistent() && arguments.is_some();
 
+        self.last_sql.clear();
+        self.last_sql.push_str(sql);
+
         Box::pin(try_stream! {
             let SqliteConnection {
                 handle: ref mut conn,
                 ref mut statements,
                 ref mut statement,
                 ref mut worker,
+                ref type_overrides,
                 ..
             } = self;
 
+            // a step abandoned by a dropped stream may still be running on the statement
+            worker.wait_until_ready().await;
+
             // prepare statement object (or checkout from cache)
             let stmt = prepare(statements, statement, sql, persistent)?;
 
@@ -123,7 +130,8 @@ impl<'c> Executor<'c> for &'c mut SqliteConnection {
                             let (row, weak_values_ref) = SqliteRow::current(
                                 *stmt,
                                 columns,
-                                column_names
+                                column_names,
+                                type_overrides,
                             );
 
                             let v = Either::Right(row);
@@ -154,15 +162,22 @@ impl<'c> Executor<'c> for &'c mut SqliteConnection {
         let arguments = query.take_arguments();
         let persistent = query.persistent() && arguments.is_some();
 
+        self.last_sql.clear();
+        self.last_sql.push_str(sql);
+
         Box::pin(async move {
             let SqliteConnection {
                 handle: ref mut conn,
                 ref mut statements,
                 ref mut statement,
                 ref mut worker,
+                ref type_overrides,
                 ..
             } = self;
 
+            // a step abandoned by a dropped future may still be running on the statement
+            worker.wait_until_ready().await;
+
             // prepare statement object (or checkout from cache)
             let virtual_stmt = prepare(statements, statement, sql, persistent)?;
 
@@ -186,7 +201,7 @@ impl<'c> Executor<'c> for &'c mut SqliteConnection {
 
                     Either::Right(()) => {
                         let (row, weak_values_ref) =
-                            SqliteRow::current(*stmt, columns, column_names);
+                            SqliteRow::current(*stmt, columns, column_names, type_overrides);
 
                         *last_row_values = Some(weak_values_ref);
 
@@ -214,9 +229,12 @@ impl<'c> Executor<'c> for &'c mut SqliteConnection {
                 handle: ref mut conn,
                 ref mut statements,
                 ref mut statement,
+                ref mut worker,
                 ..
             } = self;
 
+            worker.wait_until_ready().await;
+
             // prepare statement object (or checkout from cache)
             let statement = prepare(statements, statement, sql, true)?;
 
@@ -1,14 +1,19 @@
 use crate::common::StatementCache;
-use crate::connection::{Connection, LogSettings};
+use crate::connection::{Capability, Connection, ConnectionState, LogSettings};
 use crate::error::Error;
+use crate::executor::Executor;
+use crate::query_as::query_as;
 use crate::sqlite::statement::{StatementWorker, VirtualStatement};
 use crate::sqlite::{Sqlite, SqliteConnectOptions};
 use crate::transaction::Transaction;
+use crate::type_overrides::TypeOverrides;
 use futures_core::future::BoxFuture;
 use futures_util::future;
-use libsqlite3_sys::sqlite3;
+use libsqlite3_sys::{sqlite3, sqlite3_get_autocommit, sqlite3_libversion_number};
 use std::cmp::Ordering;
 use std::fmt::{self, Debug, Formatter};
+use std::mem::{self, ManuallyDrop};
+use std::sync::Arc;
 
 mod collation;
 mod describe;
@@ -21,7 +26,8 @@ pub(crate) use handle::ConnectionHandle;
 
 /// A connection to a [Sqlite] database.
 pub struct SqliteConnection {
-    pub(crate) handle: ConnectionHandle,
+    // closed by the worker thread when the connection is dropped
+    pub(crate) handle: ManuallyDrop<ConnectionHandle>,
     pub(crate) worker: StatementWorker,
 
     // transaction status
@@ -33,6 +39,12 @@ pub struct SqliteConnection {
     // most recent non-persistent statement
     pub(crate) statement: Option<VirtualStatement>,
 
+    // most recently executed query, kept for diagnostics
+    pub(crate) last_sql: String,
+
+    // shared with the rows returned by this connection
+    type_overrides: Option<Arc<TypeOverrides<Sqlite>>>,
+
     log_settings: LogSettings,
 }
 
@@ -63,8 +75,16 @@ impl Connection for SqliteConnection {
     type Options = SqliteConnectOptions;
 
     fn close(self) -> BoxFuture<'static, Result<(), Error>> {
-        // nothing explicit to do; connection will close in drop
-        Box::pin(future::ok(()))
+        let closed = self.worker.sync();
+
+        // the worker thread closes the connection once it is dropped
+        drop(self);
+
+        Box::pin(async move {
+            closed.await;
+
+            Ok(())
+        })
     }
 
     fn ping(&mut self) -> BoxFuture<'_, Result<(), Error>> {
@@ -72,6 +92,44 @@ impl Connection for SqliteConnection {
         Box::pin(future::ok(()))
     }
 
+    fn reset(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move {
+            self.worker.wait_until_ready().await;
+
+            // a transaction may also have been opened by a raw `BEGIN`
+            // SAFETY: the worker thread is not stepping a statement of this connection
+            if unsafe { sqlite3_get_autocommit(self.handle.as_ptr()) } == 0 {
+                self.execute("ROLLBACK").await?;
+            }
+
+            // dropping a table also drops its indexes and triggers
+            let objects: Vec<(String, String)> = query_as(
+                "SELECT type, name FROM temp.sqlite_master \
+                 WHERE type IN ('trigger', 'view', 'table') AND name NOT LIKE 'sqlite_%' \
+                 ORDER BY CASE type WHEN 'trigger' THEN 0 WHEN 'view' THEN 1 ELSE 2 END",
+            )
+            .fetch_all(&mut *self)
+            .await?;
+
+            for (ty, name) in objects {
+                let sql = format!(
+                    "DROP {} IF EXISTS temp.\"{}\"",
+                    ty.to_uppercase(),
+                    name.replace('"', "\"\"")
+                );
+
+                self.execute(&*sql).await?;
+            }
+
+            // the statements are finalized when dropped
+            self.statements.clear();
+            self.statement = None;
+            self.transaction_depth = 0;
+
+            Ok(())
+        })
+    }
+
     fn begin(&mut self) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
     where
         Self: Sized,
@@ -85,11 +143,49 @@ impl Connection for SqliteConnection {
 
     fn clear_cached_statements(&mut self) -> BoxFuture<'_, Result<(), Error>> {
         Box::pin(async move {
+            self.worker.wait_until_ready().await;
             self.statements.clear();
             Ok(())
         })
     }
 
+    fn close_cached_statement<'e>(
+        &'e mut self,
+        sql: &'e str,
+    ) -> BoxFuture<'e, Result<bool, Error>> {
+        Box::pin(async move {
+            self.worker.wait_until_ready().await;
+
+            // the statement is finalized when dropped
+            Ok(self.statements.remove(sql).is_some())
+        })
+    }
+
+    fn set_type_overrides(&mut self, overrides: TypeOverrides<Sqlite>) {
+        self.type_overrides = overrides.into_shared();
+    }
+
+    fn supports(&self, capability: Capability) -> bool {
+        // the version of the linked SQLite library, as `X * 1000000 + Y * 1000 + Z`
+        let version = unsafe { sqlite3_libversion_number() };
+
+        match capability {
+            Capability::Returning => version >= 3_035_000,
+            Capability::JsonOperators => version >= 3_038_000,
+            Capability::OnConflict => version >= 3_024_000,
+            Capability::SkipLocked => false,
+        }
+    }
+
+    fn dump_state(&self) -> ConnectionState {
+        ConnectionState {
+            transaction_depth: self.transaction_depth,
+            cached_statements: self.statements.len(),
+            last_sql: Some(self.last_sql.clone()).filter(|sql| !sql.is_empty()),
+            ..ConnectionState::default()
+        }
+    }
+
     #[doc(hidden)]
     fn flush(&mut self) -> BoxFuture<'_, Result<(), Error>> {
         // For SQLite, FLUSH does effectively nothing
@@ -104,9 +200,16 @@ impl Connection for SqliteConnection {
 
 impl Drop for SqliteConnection {
     fn drop(&mut self) {
-        // before the connection handle is dropped,
-        // we must explicitly drop the statements as the drop-order in a struct is undefined
-        self.statements.clear();
-        self.statement.take();
+        // the worker thread may still be stepping a statement of a dropped query, so it
+        // finalizes the statements and then closes the handle, in the order of the tuple, once
+        // it is done; this does not wait for it
+        let capacity = self.statements.capacity();
+        let statements = mem::replace(&mut self.statements, StatementCache::new(capacity));
+
+        // SAFETY: the handle is not used again
+        let handle = unsafe { ManuallyDrop::take(&mut self.handle) };
+
+        self.worker
+            .release((statements, self.statement.take(), handle));
     }
 }
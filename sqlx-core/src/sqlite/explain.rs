@@ -0,0 +1,124 @@
+use futures_core::future::BoxFuture;
+
+use crate::error::Error;
+use crate::executor::{Execute, Executor};
+use crate::explain::{Access, Explain, PlanStep, QueryPlan};
+use crate::row::Row;
+use crate::sqlite::{Sqlite, SqliteConnection};
+
+impl Explain for SqliteConnection {
+    fn explain<'e, 'q: 'e, E>(&'e mut self, mut query: E) -> BoxFuture<'e, Result<QueryPlan, Error>>
+    where
+        E: 'q + Execute<'q, Sqlite>,
+    {
+        let sql = format!("EXPLAIN QUERY PLAN {}", query.sql());
+        let arguments = query.take_arguments();
+
+        Box::pin(async move {
+            let rows = self.fetch_all((&*sql, arguments)).await?;
+
+            let steps = rows
+                .iter()
+                .map(|row| {
+                    row.try_get::<String, _>("detail")
+                        .map(|detail| parse_step(&detail))
+                })
+                .collect::<Result<_, _>>()?;
+
+            Ok(QueryPlan::new(steps))
+        })
+    }
+}
+
+// steps read a table with `SCAN users`, or `SEARCH users USING INDEX users_email (email=?)`;
+// versions of SQLite before 3.36 write `SCAN TABLE users`. A `SCAN` through an index, as in
+// `SCAN users USING COVERING INDEX users_email`, still visits every row and is a full scan
+fn parse_step(detail: &str) -> PlanStep {
+    let mut words = detail.split_whitespace().peekable();
+
+    let (access, table, index) = match words.next() {
+        Some(verb @ "SCAN") | Some(verb @ "SEARCH") => {
+            if words.peek() == Some(&"TABLE") {
+                words.next();
+            }
+
+            let table = words.next();
+            let rest: Vec<&str> = words.collect();
+
+            // `USING INDEX name`, `USING COVERING INDEX name`, `USING INTEGER PRIMARY KEY`
+            let index = rest
+                .windows(2)
+                .find(|pair| pair[0] == "INDEX")
+                .map(|pair| pair[1].to_owned());
+
+            let uses_index = index.is_some() || rest.contains(&"KEY");
+
+            match table {
+                // `SCAN CONSTANT ROW`, `SCAN SUBQUERY 1`, `SCAN (subquery-1)`
+                Some("CONSTANT") | Some("SUBQUERY") => (Access::Other, None, None),
+                Some(table) if table.starts_with('(') => (Access::Other, None, None),
+
+                Some(table) if verb == "SCAN" => (Access::FullScan, Some(table.to_owned()), index),
+                Some(table) if uses_index => (Access::Index, Some(table.to_owned()), index),
+                Some(table) => (Access::Other, Some(table.to_owned()), None),
+
+                None => (Access::Other, None, None),
+            }
+        }
+
+        _ => (Access::Other, None, None),
+    };
+
+    PlanStep {
+        access,
+        table,
+        index,
+        detail: detail.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_step;
+    use crate::explain::Access;
+
+    #[test]
+    fn it_parses_plan_steps() {
+        let scan = parse_step("SCAN orders");
+
+        assert_eq!(scan.access(), Access::FullScan);
+        assert_eq!(scan.table(), Some("orders"));
+
+        let old_scan = parse_step("SCAN TABLE orders");
+
+        assert_eq!(old_scan.access(), Access::FullScan);
+        assert_eq!(old_scan.table(), Some("orders"));
+
+        let search = parse_step("SEARCH users USING INDEX users_email (email=?)");
+
+        assert_eq!(search.access(), Access::Index);
+        assert_eq!(search.table(), Some("users"));
+        assert_eq!(search.index(), Some("users_email"));
+
+        let covering = parse_step("SCAN users USING COVERING INDEX users_email");
+
+        assert_eq!(covering.access(), Access::FullScan);
+        assert_eq!(covering.index(), Some("users_email"));
+
+        let covering_search = parse_step("SEARCH users USING COVERING INDEX users_email (email=?)");
+
+        assert_eq!(covering_search.access(), Access::Index);
+        assert_eq!(covering_search.index(), Some("users_email"));
+
+        let rowid = parse_step("SEARCH TABLE users USING INTEGER PRIMARY KEY (rowid=?)");
+
+        assert_eq!(rowid.access(), Access::Index);
+        assert_eq!(rowid.index(), None);
+
+        assert_eq!(parse_step("SCAN CONSTANT ROW").access(), Access::Other);
+        assert_eq!(
+            parse_step("USE TEMP B-TREE FOR ORDER BY").access(),
+            Access::Other
+        );
+    }
+}
@@ -11,6 +11,7 @@ mod connection;
 mod database;
 mod done;
 mod error;
+mod explain;
 mod options;
 mod row;
 mod statement;
@@ -13,6 +13,7 @@ use crate::ext::ustr::UStr;
 use crate::row::Row;
 use crate::sqlite::statement::StatementHandle;
 use crate::sqlite::{Sqlite, SqliteColumn, SqliteValue, SqliteValueRef};
+use crate::type_overrides::TypeOverrides;
 
 /// Implementation of [`Row`] for SQLite.
 pub struct SqliteRow {
@@ -30,6 +31,8 @@ pub struct SqliteRow {
 
     pub(crate) columns: Arc<Vec<SqliteColumn>>,
     pub(crate) column_names: Arc<HashMap<UStr, usize>>,
+
+    pub(crate) type_overrides: Option<Arc<TypeOverrides<Sqlite>>>,
 }
 
 impl crate::row::private_row::Sealed for SqliteRow {}
@@ -51,6 +54,7 @@ impl SqliteRow {
         statement: StatementHandle,
         columns: &Arc<Vec<SqliteColumn>>,
         column_names: &Arc<HashMap<UStr, usize>>,
+        type_overrides: &Option<Arc<TypeOverrides<Sqlite>>>,
     ) -> (Self, Weak<AtomicPtr<SqliteValue>>) {
         let values = Arc::new(AtomicPtr::new(null_mut()));
         let weak_values = Arc::downgrade(&values);
@@ -62,6 +66,7 @@ impl SqliteRow {
             num_values: size,
             columns: Arc::clone(columns),
             column_names: Arc::clone(column_names),
+            type_overrides: type_overrides.clone(),
         };
 
         (row, weak_values)
@@ -123,15 +128,21 @@ impl Row for SqliteRow {
             let values: &[SqliteValue] =
                 unsafe { slice::from_raw_parts(values_ptr, self.num_values) };
 
-            Ok(SqliteValueRef::value(&values[index]))
+            Ok(SqliteValueRef::value(&values[index])
+                .with_type_overrides(self.type_overrides.as_ref()))
         } else {
             Ok(SqliteValueRef::statement(
                 &self.statement,
                 self.columns[index].type_info.clone(),
                 index,
-            ))
+            )
+            .with_type_overrides(self.type_overrides.as_ref()))
         }
     }
+
+    fn type_overrides(&self) -> Option<&TypeOverrides<Sqlite>> {
+        self.type_overrides.as_deref()
+    }
 }
 
 impl Drop for SqliteRow {
@@ -163,6 +174,7 @@ impl From<SqliteRow> for crate::any::AnyRow {
         crate::any::AnyRow {
             columns: row.columns.iter().map(|col| col.clone().into()).collect(),
             kind: crate::any::row::AnyRowKind::Sqlite(row),
+            type_overrides: None,
         }
     }
 }
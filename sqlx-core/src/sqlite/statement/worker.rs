@@ -1,9 +1,12 @@
 use crate::error::Error;
-use crate::sqlite::statement::StatementHandle;
+use crate::sqlite::statement::{StatementHandle, VirtualStatement};
 use crossbeam_channel::{unbounded, Sender};
 use either::Either;
 use futures_channel::oneshot;
 use libsqlite3_sys::{sqlite3_step, SQLITE_DONE, SQLITE_ROW};
+use std::future::Future;
+use std::mem::ManuallyDrop;
+use std::ops::{Deref, DerefMut};
 use std::thread;
 
 // Each SQLite connection has a dedicated thread.
@@ -14,6 +17,10 @@ use std::thread;
 
 pub(crate) struct StatementWorker {
     tx: Sender<StatementWorkerCommand>,
+
+    // the response to the last step, if its future was dropped before the step completed;
+    // the statement may still be in use by the worker thread until it is received
+    pending: Option<oneshot::Receiver<Result<Either<u64, ()>, Error>>>,
 }
 
 enum StatementWorkerCommand {
@@ -21,6 +28,15 @@ enum StatementWorkerCommand {
         statement: StatementHandle,
         tx: oneshot::Sender<Result<Either<u64, ()>, Error>>,
     },
+
+    // drops the value once the commands sent before have been handled
+    Release {
+        value: Box<dyn Send>,
+    },
+
+    Sync {
+        tx: oneshot::Sender<()>,
+    },
 }
 
 impl StatementWorker {
@@ -41,23 +57,112 @@ impl StatementWorker {
 
                         let _ = tx.send(resp);
                     }
+
+                    StatementWorkerCommand::Release { value } => {
+                        drop(value);
+                    }
+
+                    StatementWorkerCommand::Sync { tx } => {
+                        let _ = tx.send(());
+                    }
                 }
             }
         });
 
-        Self { tx }
+        Self { tx, pending: None }
     }
 
     pub(crate) async fn step(
         &mut self,
         statement: StatementHandle,
     ) -> Result<Either<u64, ()>, Error> {
+        self.wait_until_ready().await;
+
         let (tx, rx) = oneshot::channel();
 
         self.tx
             .send(StatementWorkerCommand::Step { statement, tx })
             .map_err(|_| Error::WorkerCrashed)?;
 
-        rx.await.map_err(|_| Error::WorkerCrashed)?
+        let rx = self.pending.get_or_insert(rx);
+        let resp = rx.await.map_err(|_| Error::WorkerCrashed);
+
+        self.pending = None;
+
+        resp?
+    }
+
+    // waits until a step whose future was dropped has completed; a statement must not be
+    // reset or finalized while the worker thread may still be stepping it
+    pub(crate) async fn wait_until_ready(&mut self) {
+        if let Some(rx) = &mut self.pending {
+            let _ = rx.await;
+            self.pending = None;
+        }
+    }
+
+    // drops `value` on the worker thread once it is done with the commands sent before, without
+    // waiting for it; used to finalize statements the worker thread may still be stepping
+    pub(crate) fn release<T: Send + 'static>(&self, value: T) {
+        release(&self.tx, value);
+    }
+
+    // completes once the worker thread has handled the commands sent before the returned future
+    // is first polled, as it handles them in order
+    pub(crate) fn sync(&self) -> impl Future<Output = ()> + Send + 'static {
+        let worker = self.tx.clone();
+
+        async move {
+            let (tx, rx) = oneshot::channel();
+
+            if worker.send(StatementWorkerCommand::Sync { tx }).is_ok() {
+                let _ = rx.await;
+            }
+        }
+    }
+
+    // ties a statement that the connection does not keep to the worker thread, so that it is
+    // not finalized while the worker thread may still be stepping it
+    pub(crate) fn guard(&self, statement: VirtualStatement) -> GuardedStatement {
+        GuardedStatement {
+            statement: ManuallyDrop::new(statement),
+            tx: self.tx.clone(),
+        }
+    }
+}
+
+pub(crate) struct GuardedStatement {
+    statement: ManuallyDrop<VirtualStatement>,
+    tx: Sender<StatementWorkerCommand>,
+}
+
+impl Deref for GuardedStatement {
+    type Target = VirtualStatement;
+
+    fn deref(&self) -> &Self::Target {
+        &self.statement
     }
 }
+
+impl DerefMut for GuardedStatement {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.statement
+    }
+}
+
+impl Drop for GuardedStatement {
+    fn drop(&mut self) {
+        // SAFETY: the statement is not used again
+        let statement = unsafe { ManuallyDrop::take(&mut self.statement) };
+
+        release(&self.tx, statement);
+    }
+}
+
+fn release<T: Send + 'static>(worker: &Sender<StatementWorkerCommand>, value: T) {
+    let value = Box::new(value);
+
+    // if the worker thread is gone, it is no longer stepping any statement, and the value is
+    // dropped here instead
+    let _ = worker.send(StatementWorkerCommand::Release { value });
+}
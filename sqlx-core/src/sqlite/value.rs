@@ -13,6 +13,7 @@ use crate::error::BoxDynError;
 use crate::sqlite::statement::StatementHandle;
 use crate::sqlite::type_info::DataType;
 use crate::sqlite::{Sqlite, SqliteTypeInfo};
+use crate::type_overrides::TypeOverrides;
 use crate::value::{Value, ValueRef};
 use std::borrow::Cow;
 
@@ -26,11 +27,11 @@ enum SqliteValueData<'r> {
     Value(&'r SqliteValue),
 }
 
-pub struct SqliteValueRef<'r>(SqliteValueData<'r>);
+pub struct SqliteValueRef<'r>(SqliteValueData<'r>, Option<&'r Arc<TypeOverrides<Sqlite>>>);
 
 impl<'r> SqliteValueRef<'r> {
     pub(crate) fn value(value: &'r SqliteValue) -> Self {
-        Self(SqliteValueData::Value(value))
+        Self(SqliteValueData::Value(value), value.type_overrides.as_ref())
     }
 
     pub(crate) fn statement(
@@ -38,11 +39,23 @@ impl<'r> SqliteValueRef<'r> {
         type_info: SqliteTypeInfo,
         index: usize,
     ) -> Self {
-        Self(SqliteValueData::Statement {
-            statement,
-            type_info,
-            index,
-        })
+        Self(
+            SqliteValueData::Statement {
+                statement,
+                type_info,
+                index,
+            },
+            None,
+        )
+    }
+
+    // the overrides of the row this value was read from
+    pub(crate) fn with_type_overrides(
+        mut self,
+        type_overrides: Option<&'r Arc<TypeOverrides<Sqlite>>>,
+    ) -> Self {
+        self.1 = type_overrides;
+        self
     }
 
     pub(super) fn int(&self) -> i32 {
@@ -100,7 +113,7 @@ impl<'r> ValueRef<'r> for SqliteValueRef<'r> {
     type Database = Sqlite;
 
     fn to_owned(&self) -> SqliteValue {
-        match self.0 {
+        let mut value = match self.0 {
             SqliteValueData::Statement {
                 statement,
                 index,
@@ -108,7 +121,10 @@ impl<'r> ValueRef<'r> for SqliteValueRef<'r> {
             } => unsafe { SqliteValue::new(statement.column_value(index), type_info.clone()) },
 
             SqliteValueData::Value(v) => v.clone(),
-        }
+        };
+
+        value.type_overrides = self.1.cloned();
+        value
     }
 
     fn type_info(&self) -> Cow<'_, SqliteTypeInfo> {
@@ -141,6 +157,7 @@ impl<'r> ValueRef<'r> for SqliteValueRef<'r> {
 pub struct SqliteValue {
     pub(crate) handle: Arc<ValueHandle>,
     pub(crate) type_info: SqliteTypeInfo,
+    pub(crate) type_overrides: Option<Arc<TypeOverrides<Sqlite>>>,
 }
 
 pub(crate) struct ValueHandle(NonNull<sqlite3_value>);
@@ -158,6 +175,7 @@ impl SqliteValue {
             handle: Arc::new(ValueHandle(NonNull::new_unchecked(sqlite3_value_dup(
                 value,
             )))),
+            type_overrides: None,
         }
     }
 
@@ -218,6 +236,10 @@ impl Value for SqliteValue {
     fn is_null(&self) -> bool {
         unsafe { sqlite3_value_type(self.handle.0.as_ptr()) == SQLITE_NULL }
     }
+
+    fn type_overrides(&self) -> Option<&TypeOverrides<Sqlite>> {
+        self.type_overrides.as_deref()
+    }
 }
 
 impl Drop for ValueHandle {
@@ -235,6 +257,7 @@ impl<'r> From<SqliteValueRef<'r>> for crate::any::AnyValueRef<'r> {
         crate::any::AnyValueRef {
             type_info: value.type_info().clone().into_owned().into(),
             kind: crate::any::value::AnyValueRefKind::Sqlite(value),
+            type_overrides: None,
         }
     }
 }
@@ -246,6 +269,7 @@ impl From<SqliteValue> for crate::any::AnyValue {
         crate::any::AnyValue {
             type_info: value.type_info().clone().into_owned().into(),
             kind: crate::any::value::AnyValueKind::Sqlite(value),
+            type_overrides: None,
         }
     }
 }
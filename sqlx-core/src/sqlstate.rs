@@ -0,0 +1,282 @@
+//! Constants and classification for SQLSTATE error codes.
+//!
+//! SQLSTATE is the five character error code defined by the SQL standard. The first two
+//! characters are the _class_ of the error and the last three the _subclass_; a subclass of
+//! `000` denotes the class as a whole.
+//!
+//! PostgreSQL and MySQL report a SQLSTATE from [`DatabaseError::code`]. SQLite and MSSQL
+//! report their own error numbers instead, which are never classified as SQLSTATE codes.
+//!
+//! ```rust,ignore
+//! use sqlx::sqlstate::{self, SqlStateClass};
+//!
+//! match sqlx::query("INSERT INTO users (name) VALUES ($1)").bind(name).execute(&pool).await {
+//!     Err(sqlx::Error::Database(err)) if err.code().as_deref() == Some(sqlstate::UNIQUE_VIOLATION) => {
+//!         // the name is taken
+//!     }
+//!
+//!     Err(sqlx::Error::Database(err)) if err.sqlstate_class() == Some(SqlStateClass::TransactionRollback) => {
+//!         // retry the transaction
+//!     }
+//!
+//!     // ...
+//! }
+//! ```
+//!
+//! [`DatabaseError::code`]: crate::error::DatabaseError::code
+
+// Class 00 — Successful Completion
+pub const SUCCESSFUL_COMPLETION: &str = "00000";
+
+// Class 01 — Warning
+pub const WARNING: &str = "01000";
+
+// Class 02 — No Data
+pub const NO_DATA: &str = "02000";
+
+// Class 08 — Connection Exception
+pub const CONNECTION_EXCEPTION: &str = "08000";
+pub const CONNECTION_DOES_NOT_EXIST: &str = "08003";
+pub const CONNECTION_FAILURE: &str = "08006";
+
+// Class 0A — Feature Not Supported
+pub const FEATURE_NOT_SUPPORTED: &str = "0A000";
+
+// Class 21 — Cardinality Violation
+pub const CARDINALITY_VIOLATION: &str = "21000";
+
+// Class 22 — Data Exception
+pub const DATA_EXCEPTION: &str = "22000";
+pub const STRING_DATA_RIGHT_TRUNCATION: &str = "22001";
+pub const NUMERIC_VALUE_OUT_OF_RANGE: &str = "22003";
+pub const NULL_VALUE_NOT_ALLOWED: &str = "22004";
+pub const INVALID_DATETIME_FORMAT: &str = "22007";
+pub const DIVISION_BY_ZERO: &str = "22012";
+
+// Class 23 — Integrity Constraint Violation
+pub const INTEGRITY_CONSTRAINT_VIOLATION: &str = "23000";
+pub const RESTRICT_VIOLATION: &str = "23001";
+pub const NOT_NULL_VIOLATION: &str = "23502";
+pub const FOREIGN_KEY_VIOLATION: &str = "23503";
+pub const UNIQUE_VIOLATION: &str = "23505";
+pub const CHECK_VIOLATION: &str = "23514";
+pub const EXCLUSION_VIOLATION: &str = "23P01";
+
+// Class 24 — Invalid Cursor State
+pub const INVALID_CURSOR_STATE: &str = "24000";
+
+// Class 25 — Invalid Transaction State
+pub const INVALID_TRANSACTION_STATE: &str = "25000";
+pub const READ_ONLY_SQL_TRANSACTION: &str = "25006";
+
+// Class 28 — Invalid Authorization Specification
+pub const INVALID_AUTHORIZATION_SPECIFICATION: &str = "28000";
+pub const INVALID_PASSWORD: &str = "28P01";
+
+// Class 40 — Transaction Rollback
+pub const TRANSACTION_ROLLBACK: &str = "40000";
+pub const SERIALIZATION_FAILURE: &str = "40001";
+pub const TRANSACTION_INTEGRITY_CONSTRAINT_VIOLATION: &str = "40002";
+pub const STATEMENT_COMPLETION_UNKNOWN: &str = "40003";
+pub const DEADLOCK_DETECTED: &str = "40P01";
+
+// Class 42 — Syntax Error or Access Rule Violation
+pub const SYNTAX_ERROR_OR_ACCESS_RULE_VIOLATION: &str = "42000";
+pub const INSUFFICIENT_PRIVILEGE: &str = "42501";
+pub const SYNTAX_ERROR: &str = "42601";
+pub const UNDEFINED_COLUMN: &str = "42703";
+pub const UNDEFINED_TABLE: &str = "42P01";
+
+// Class 53 — Insufficient Resources
+pub const INSUFFICIENT_RESOURCES: &str = "53000";
+pub const TOO_MANY_CONNECTIONS: &str = "53300";
+
+// Class 57 — Operator Intervention
+pub const OPERATOR_INTERVENTION: &str = "57000";
+pub const QUERY_CANCELED: &str = "57014";
+pub const ADMIN_SHUTDOWN: &str = "57P01";
+pub const CRASH_SHUTDOWN: &str = "57P02";
+pub const CANNOT_CONNECT_NOW: &str = "57P03";
+
+// Class 58 — System Error
+pub const SYSTEM_ERROR: &str = "58000";
+
+/// The class of a SQLSTATE code, given by its first two characters.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SqlStateClass {
+    /// Class `00`.
+    SuccessfulCompletion,
+
+    /// Class `01`.
+    Warning,
+
+    /// Class `02`.
+    NoData,
+
+    /// Class `08`.
+    ConnectionException,
+
+    /// Class `0A`.
+    FeatureNotSupported,
+
+    /// Class `21`.
+    CardinalityViolation,
+
+    /// Class `22`.
+    DataException,
+
+    /// Class `23`.
+    IntegrityConstraintViolation,
+
+    /// Class `24`.
+    InvalidCursorState,
+
+    /// Class `25`.
+    InvalidTransactionState,
+
+    /// Class `28`.
+    InvalidAuthorizationSpecification,
+
+    /// Class `40`.
+    TransactionRollback,
+
+    /// Class `42`.
+    SyntaxErrorOrAccessRuleViolation,
+
+    /// Class `53`.
+    InsufficientResources,
+
+    /// Class `57`.
+    OperatorIntervention,
+
+    /// Class `58`.
+    SystemError,
+
+    /// A well-formed code in a class not listed here, including implementation-defined classes.
+    Other,
+}
+
+impl SqlStateClass {
+    /// Classify `code`.
+    ///
+    /// Returns `None` if `code` is not a well-formed SQLSTATE: five ASCII digits or
+    /// uppercase letters.
+    pub fn from_code(code: &str) -> Option<Self> {
+        if !is_sqlstate(code) {
+            return None;
+        }
+
+        Some(match &code[..2] {
+            "00" => SqlStateClass::SuccessfulCompletion,
+            "01" => SqlStateClass::Warning,
+            "02" => SqlStateClass::NoData,
+            "08" => SqlStateClass::ConnectionException,
+            "0A" => SqlStateClass::FeatureNotSupported,
+            "21" => SqlStateClass::CardinalityViolation,
+            "22" => SqlStateClass::DataException,
+            "23" => SqlStateClass::IntegrityConstraintViolation,
+            "24" => SqlStateClass::InvalidCursorState,
+            "25" => SqlStateClass::InvalidTransactionState,
+            "28" => SqlStateClass::InvalidAuthorizationSpecification,
+            "40" => SqlStateClass::TransactionRollback,
+            "42" => SqlStateClass::SyntaxErrorOrAccessRuleViolation,
+            "53" => SqlStateClass::InsufficientResources,
+            "57" => SqlStateClass::OperatorIntervention,
+            "58" => SqlStateClass::SystemError,
+
+            _ => SqlStateClass::Other,
+        })
+    }
+
+    /// Returns `true` if errors of this class are typically transient and the failed operation
+    /// may succeed if retried (serialization failures, deadlocks, lost connections, resource
+    /// exhaustion, and server shutdowns).
+    pub fn is_transient(self) -> bool {
+        matches!(
+            self,
+            SqlStateClass::TransactionRollback
+                | SqlStateClass::ConnectionException
+                | SqlStateClass::InsufficientResources
+                | SqlStateClass::OperatorIntervention
+        )
+    }
+}
+
+/// Returns `true` if `code` is a well-formed SQLSTATE: five ASCII digits or uppercase letters.
+pub fn is_sqlstate(code: &str) -> bool {
+    code.len() == 5
+        && code
+            .bytes()
+            .all(|b| b.is_ascii_digit() || b.is_ascii_uppercase())
+}
+
+/// Returns the two character class of `code`, or `None` if `code` is not a well-formed SQLSTATE.
+pub fn class_of(code: &str) -> Option<&str> {
+    if is_sqlstate(code) {
+        Some(&code[..2])
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_classifies_codes() {
+        assert_eq!(
+            SqlStateClass::from_code(UNIQUE_VIOLATION),
+            Some(SqlStateClass::IntegrityConstraintViolation)
+        );
+
+        assert_eq!(
+            SqlStateClass::from_code(DEADLOCK_DETECTED),
+            Some(SqlStateClass::TransactionRollback)
+        );
+
+        assert_eq!(
+            SqlStateClass::from_code(FEATURE_NOT_SUPPORTED),
+            Some(SqlStateClass::FeatureNotSupported)
+        );
+
+        assert_eq!(
+            SqlStateClass::from_code("HV000"),
+            Some(SqlStateClass::Other)
+        );
+    }
+
+    #[test]
+    fn it_rejects_malformed_codes() {
+        // SQLite reports extended result codes such as 2067
+        assert_eq!(SqlStateClass::from_code("2067"), None);
+        assert_eq!(SqlStateClass::from_code("23p01"), None);
+        assert_eq!(SqlStateClass::from_code("230000"), None);
+        assert_eq!(class_of(""), None);
+
+        assert_eq!(class_of(EXCLUSION_VIOLATION), Some("23"));
+    }
+
+    #[test]
+    fn it_identifies_transient_classes() {
+        let transient = [
+            SERIALIZATION_FAILURE,
+            DEADLOCK_DETECTED,
+            CONNECTION_FAILURE,
+            TOO_MANY_CONNECTIONS,
+            ADMIN_SHUTDOWN,
+        ];
+
+        for code in &transient {
+            assert!(SqlStateClass::from_code(code).unwrap().is_transient());
+        }
+
+        assert!(!SqlStateClass::from_code(UNIQUE_VIOLATION)
+            .unwrap()
+            .is_transient());
+        assert!(!SqlStateClass::from_code(SYNTAX_ERROR)
+            .unwrap()
+            .is_transient());
+    }
+}
@@ -1,12 +1,14 @@
 use crate::arguments::IntoArguments;
 use crate::column::ColumnIndex;
-use crate::database::{Database, HasArguments, HasStatement};
+use crate::connection::Connection;
+use crate::database::{Database, HasArguments, HasStatement, HasStatementCache};
 use crate::error::Error;
 use crate::from_row::FromRow;
 use crate::query::Query;
 use crate::query_as::QueryAs;
 use crate::query_scalar::QueryScalar;
 use either::Either;
+use futures_core::future::BoxFuture;
 
 /// An explicitly prepared statement.
 ///
@@ -59,6 +61,24 @@ pub trait Statement<'q>: Send + Sync {
         Ok(&self.columns()[index.index(self)?])
     }
 
+    /// Deallocate this statement on `conn` now, rather than when it is evicted from the
+    /// statement cache of the connection.
+    ///
+    /// Statements are prepared per connection so this only affects `conn`; the statement
+    /// can still be used afterwards and will be prepared again. Returns `false` if the
+    /// statement was not prepared on `conn`.
+    ///
+    /// See [`Connection::close_cached_statement`].
+    fn close<'e>(
+        &'e self,
+        conn: &'e mut <Self::Database as Database>::Connection,
+    ) -> BoxFuture<'e, Result<bool, Error>>
+    where
+        Self::Database: HasStatementCache,
+    {
+        conn.close_cached_statement(self.sql())
+    }
+
     fn query(&self) -> Query<'_, Self::Database, <Self::Database as HasArguments<'_>>::Arguments>;
 
     fn query_with<'s, A>(&'s self, arguments: A) -> Query<'s, Self::Database, A>
@@ -0,0 +1,273 @@
+//! Conformance checks for database drivers.
+//!
+//! These functions exercise the contracts that the rest of SQLx relies on from a driver:
+//! establishing and closing connections, pinging, preparing statements, transactions and
+//! savepoints, and encoding and decoding values. Driver authors can call them from their own
+//! integration tests against a live database:
+//!
+//! ```rust,ignore
+//! #[sqlx_rt::test]
+//! async fn it_conforms() -> anyhow::Result<()> {
+//!     sqlx_core::testing::run::<MyDatabase>(&std::env::var("DATABASE_URL")?).await?;
+//!
+//!     let mut conn = MyConnection::connect(&std::env::var("DATABASE_URL")?).await?;
+//!
+//!     sqlx_core::testing::round_trip::<MyDatabase, _>(&mut conn, 42_i32).await?;
+//!     sqlx_core::testing::round_trip::<MyDatabase, _>(&mut conn, String::from("hello")).await?;
+//!
+//!     Ok(())
+//! }
+//! ```
+//!
+//! Errors returned from the database are passed through; a driver that returns successfully
+//! but violates a contract causes a panic with a description of the violation.
+
+use std::fmt::Debug;
+
+use crate::arguments::{Arguments, IntoArguments};
+use crate::column::ColumnIndex;
+use crate::connection::{ConnectOptions, Connection};
+use crate::database::{Database, HasArguments};
+use crate::decode::Decode;
+use crate::encode::Encode;
+use crate::error::Error;
+use crate::executor::Executor;
+use crate::query::query_with;
+use crate::row::Row;
+use crate::statement::Statement;
+use crate::types::Type;
+
+/// The query used by the checks that need a statement returning a single row and column.
+///
+/// This is understood by every supported database.
+pub const SELECT_ONE: &str = "SELECT 1";
+
+/// The statement used by [`transactions`] to create the table it writes to, which is dropped
+/// once the check completes.
+///
+/// This is understood by PostgreSQL, MySQL and SQLite.
+pub const CREATE_TABLE: &str = "CREATE TEMPORARY TABLE _sqlx_conformance (id INTEGER)";
+
+/// Run every check that does not depend on the types supported by the driver.
+pub async fn run<DB>(url: &str) -> Result<(), Error>
+where
+    DB: Database,
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+    for<'a> <DB as HasArguments<'a>>::Arguments: IntoArguments<'a, DB>,
+    usize: ColumnIndex<DB::Row>,
+{
+    let mut conn = connect::<DB>(url).await?;
+
+    ping::<DB>(&mut conn).await?;
+    prepare::<DB>(&mut conn).await?;
+    transactions::<DB>(&mut conn).await?;
+
+    conn.close().await
+}
+
+/// Check that a connection can be established from both a URL and parsed options, and that
+/// the connection closes cleanly.
+///
+/// Returns a fresh connection for use in further checks.
+pub async fn connect<DB>(url: &str) -> Result<DB::Connection, Error>
+where
+    DB: Database,
+{
+    let conn = DB::Connection::connect(url).await?;
+    conn.close().await?;
+
+    let options: <DB::Connection as Connection>::Options = url.parse()?;
+
+    options.connect().await
+}
+
+/// Check that [`Connection::ping`] succeeds on an idle connection and after a query.
+pub async fn ping<DB>(conn: &mut DB::Connection) -> Result<(), Error>
+where
+    DB: Database,
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+{
+    conn.ping().await?;
+
+    conn.execute(SELECT_ONE).await?;
+
+    conn.ping().await
+}
+
+/// Check that a prepared statement reports its SQL and columns and can be executed.
+pub async fn prepare<DB>(conn: &mut DB::Connection) -> Result<(), Error>
+where
+    DB: Database,
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+    for<'a> <DB as HasArguments<'a>>::Arguments: IntoArguments<'a, DB>,
+    usize: ColumnIndex<DB::Row>,
+{
+    let statement = conn.prepare(SELECT_ONE).await?;
+
+    assert_eq!(
+        statement.sql(),
+        SELECT_ONE,
+        "prepared statement must report the SQL it was prepared from"
+    );
+
+    assert_eq!(
+        statement.columns().len(),
+        1,
+        "prepared statement must report its result columns"
+    );
+
+    let rows = statement.query().fetch_all(&mut *conn).await?;
+
+    assert_eq!(rows.len(), 1, "prepared statement must return its rows");
+    assert_eq!(rows[0].len(), 1, "row must contain every result column");
+
+    // the statement must be usable again after being executed
+    statement.query().fetch_one(&mut *conn).await?;
+
+    Ok(())
+}
+
+/// Check that transactions and savepoints can be committed and rolled back, and that
+/// dropping an open transaction rolls it back and leaves the connection usable.
+///
+/// The changes made inside transactions are checked against the rows of a temporary table
+/// created with [`CREATE_TABLE`].
+pub async fn transactions<DB>(conn: &mut DB::Connection) -> Result<(), Error>
+where
+    DB: Database,
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+{
+    let depth = conn.dump_state().transaction_depth;
+
+    conn.execute(CREATE_TABLE).await?;
+
+    {
+        let mut tx = conn.begin().await?;
+        insert::<DB>(&mut *tx, 1).await?;
+        tx.commit().await?;
+    }
+
+    assert_eq!(
+        ids::<DB>(conn).await?,
+        [1],
+        "committed transaction must keep its changes"
+    );
+
+    {
+        let mut tx = conn.begin().await?;
+        insert::<DB>(&mut *tx, 2).await?;
+        tx.rollback().await?;
+    }
+
+    assert_eq!(
+        ids::<DB>(conn).await?,
+        [1],
+        "rolled back transaction must discard its changes"
+    );
+
+    {
+        let mut tx = conn.begin().await?;
+
+        {
+            let mut savepoint = tx.begin().await?;
+            insert::<DB>(&mut *savepoint, 3).await?;
+            savepoint.rollback().await?;
+        }
+
+        {
+            let mut savepoint = tx.begin().await?;
+            insert::<DB>(&mut *savepoint, 4).await?;
+            savepoint.commit().await?;
+        }
+
+        tx.commit().await?;
+    }
+
+    assert_eq!(
+        ids::<DB>(conn).await?,
+        [1, 4],
+        "savepoints must keep or discard their changes when committed or rolled back"
+    );
+
+    {
+        // the rollback is queued on drop and must run before the next query
+        let mut tx = conn.begin().await?;
+        insert::<DB>(&mut *tx, 5).await?;
+    }
+
+    assert_eq!(
+        ids::<DB>(conn).await?,
+        [1, 4],
+        "dropped transaction must be rolled back"
+    );
+
+    assert_eq!(
+        conn.dump_state().transaction_depth,
+        depth,
+        "every transaction must be closed after commit, rollback or drop"
+    );
+
+    conn.execute("DROP TABLE _sqlx_conformance").await?;
+
+    Ok(())
+}
+
+async fn insert<DB>(conn: &mut DB::Connection, id: i32) -> Result<(), Error>
+where
+    DB: Database,
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+{
+    // inlined so the check does not depend on the types supported by the driver
+    let sql = format!("INSERT INTO _sqlx_conformance (id) VALUES ({})", id);
+
+    conn.execute(&*sql).await?;
+
+    Ok(())
+}
+
+// the ids inserted by `transactions` that are in the table, looked up one at a time so no
+// value has to be decoded
+async fn ids<DB>(conn: &mut DB::Connection) -> Result<Vec<i32>, Error>
+where
+    DB: Database,
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+{
+    let mut ids = Vec::new();
+
+    for id in &[1, 2, 3, 4, 5] {
+        let sql = format!("SELECT id FROM _sqlx_conformance WHERE id = {}", id);
+
+        if conn.fetch_optional(&*sql).await?.is_some() {
+            ids.push(*id);
+        }
+    }
+
+    Ok(ids)
+}
+
+/// Check that `value` is returned unchanged when bound as a parameter and selected back.
+pub async fn round_trip<DB, T>(conn: &mut DB::Connection, value: T) -> Result<(), Error>
+where
+    DB: Database,
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+    for<'a> <DB as HasArguments<'a>>::Arguments: IntoArguments<'a, DB>,
+    for<'r> T: Encode<'r, DB> + Decode<'r, DB>,
+    T: 'static + Type<DB> + Send + Clone + PartialEq + Debug,
+    usize: ColumnIndex<DB::Row>,
+{
+    let mut sql = String::from("SELECT ");
+
+    let mut arguments = <DB as HasArguments<'_>>::Arguments::default();
+    arguments.add(value.clone());
+
+    arguments
+        .format_placeholder(&mut sql)
+        .expect("error in format_placeholder");
+
+    let row = query_with(&sql, arguments).fetch_one(conn).await?;
+    let returned: T = row.try_get(0)?;
+
+    assert_eq!(value, returned, "bound value must be returned unchanged");
+
+    Ok(())
+}
@@ -0,0 +1,368 @@
+//! Best-effort two-phase commit of a transaction spanning several databases.
+//!
+//! A [`Coordinator`] drives one transaction branch per connection through the two-phase
+//! commit protocol of the database: `PREPARE TRANSACTION` on PostgreSQL and `XA` transactions
+//! on MySQL. Every branch is first prepared, which makes the server promise that it can
+//! commit the branch even after a crash, and only once all branches are prepared are they
+//! committed.
+//!
+//! ```rust,ignore
+//! use sqlx::two_phase::{Coordinator, TwoPhase};
+//!
+//! let coordinator = Coordinator::new(format!("transfer-{}", transfer_id))
+//!     .recovery_log(log);
+//!
+//! coordinator.begin(&mut [&mut pg, &mut mysql]).await?;
+//!
+//! sqlx::query("UPDATE accounts SET balance = balance - $1 WHERE id = $2")
+//!     .bind(amount)
+//!     .bind(from)
+//!     .execute(&mut pg)
+//!     .await?;
+//!
+//! sqlx::query("UPDATE ledger SET balance = balance + ? WHERE id = ?")
+//!     .bind(amount)
+//!     .bind(to)
+//!     .execute(&mut mysql)
+//!     .await?;
+//!
+//! coordinator.commit(&mut [&mut pg, &mut mysql]).await?;
+//! ```
+//!
+//! # Failures
+//!
+//! If a branch fails to prepare, every branch is rolled back and the error is returned. If
+//! the process dies or a connection is lost after every branch was prepared, the branches
+//! that were not committed yet stay prepared on their server, holding their locks, until
+//! they are resolved by [`Coordinator::recover`]. To know which way to resolve them, the
+//! decision to commit must have been recorded durably by a [`RecoveryLog`]; without one,
+//! recovery can only roll back, which may leave the transaction committed on some
+//! databases only.
+//!
+//! This is not a replacement for a transaction manager: the coordinator keeps no state of
+//! its own and relies entirely on the recovery log and on recovery being run.
+
+use std::ops::Range;
+
+use futures_core::future::BoxFuture;
+
+use crate::error::Error;
+
+/// A connection that can take part in a two-phase commit.
+///
+/// Implemented for PostgreSQL, which requires `max_prepared_transactions` to be set above
+/// zero on the server, and MySQL.
+pub trait TwoPhase: Send {
+    /// Start a transaction branch identified by `xid`.
+    fn begin_branch<'e>(&'e mut self, xid: &'e str) -> BoxFuture<'e, Result<(), Error>>;
+
+    /// Prepare the current branch so it can be committed later, even from another
+    /// connection or after the server restarted.
+    fn prepare_branch<'e>(&'e mut self, xid: &'e str) -> BoxFuture<'e, Result<(), Error>>;
+
+    /// Roll back the current branch, which has not been prepared.
+    fn abort_branch<'e>(&'e mut self, xid: &'e str) -> BoxFuture<'e, Result<(), Error>>;
+
+    /// Commit a prepared branch.
+    fn commit_prepared<'e>(&'e mut self, xid: &'e str) -> BoxFuture<'e, Result<(), Error>>;
+
+    /// Roll back a prepared branch.
+    fn rollback_prepared<'e>(&'e mut self, xid: &'e str) -> BoxFuture<'e, Result<(), Error>>;
+
+    /// List the ids of the branches that are prepared and not resolved yet in the database
+    /// of this connection.
+    fn prepared_branches(&mut self) -> BoxFuture<'_, Result<Vec<String>, Error>>;
+}
+
+/// A durable record of the transactions a [`Coordinator`] decided to commit.
+///
+/// This is typically a table in one of the databases, written outside of the transaction
+/// being coordinated.
+pub trait RecoveryLog: Send + Sync {
+    /// Record that the transaction `gid` is to be committed.
+    ///
+    /// Called once every branch has been prepared and before any of them is committed.
+    /// The transaction is rolled back if this returns an error.
+    fn record_commit<'a>(&'a self, gid: &'a str) -> BoxFuture<'a, Result<(), Error>>;
+
+    /// Called once every branch of `gid` has been committed; the record can be removed.
+    fn forget<'a>(&'a self, gid: &'a str) -> BoxFuture<'a, Result<(), Error>>;
+
+    /// Returns `true` if the decision to commit `gid` was recorded and not forgotten.
+    fn is_committed<'a>(&'a self, gid: &'a str) -> BoxFuture<'a, Result<bool, Error>>;
+}
+
+/// Coordinates a two-phase commit across connections.
+///
+/// See the [module documentation](self) for an example.
+pub struct Coordinator {
+    gid: String,
+    log: Option<Box<dyn RecoveryLog>>,
+}
+
+impl Coordinator {
+    /// Create a coordinator for the transaction with the global id `gid`.
+    ///
+    /// The id must be unique among the transactions that could be prepared at the same
+    /// time, at most 48 bytes long and made of ASCII letters, digits, `-`, `_`, `.` and `:`.
+    /// Each branch is identified by `sqlx:`, the global id, `.` and the index of its
+    /// participant, such as `sqlx:transfer-42.0`.
+    pub fn new(gid: impl Into<String>) -> Self {
+        Self {
+            gid: gid.into(),
+            log: None,
+        }
+    }
+
+    /// Record the decision to commit in `log` so that branches left prepared by a failure
+    /// can be resolved by [`recover`][Self::recover].
+    pub fn recovery_log(mut self, log: impl RecoveryLog + 'static) -> Self {
+        self.log = Some(Box::new(log));
+        self
+    }
+
+    /// The global id of the transaction.
+    pub fn gid(&self) -> &str {
+        &self.gid
+    }
+
+    /// Start a branch of the transaction on each participant.
+    ///
+    /// If a branch cannot be started, the branches already started are rolled back.
+    pub async fn begin(&self, participants: &mut [&mut dyn TwoPhase]) -> Result<(), Error> {
+        validate_gid(&self.gid)?;
+
+        for index in 0..participants.len() {
+            let xid = branch_id(&self.gid, index);
+
+            if let Err(error) = participants[index].begin_branch(&xid).await {
+                self.abort(participants, 0..index).await;
+
+                return Err(error);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Prepare every branch, then commit them.
+    ///
+    /// The participants must be given in the same order as to [`begin`][Self::begin].
+    ///
+    /// If a branch fails to prepare, or the decision to commit cannot be recorded, every
+    /// branch is rolled back and the error is returned. If a branch fails to commit, the
+    /// other branches are still committed and the first error is returned; the branch stays
+    /// prepared until it is resolved by [`recover`][Self::recover].
+    pub async fn commit(&self, participants: &mut [&mut dyn TwoPhase]) -> Result<(), Error> {
+        for index in 0..participants.len() {
+            let xid = branch_id(&self.gid, index);
+
+            if let Err(error) = participants[index].prepare_branch(&xid).await {
+                let len = participants.len();
+
+                self.rollback_prepared(participants, 0..index).await;
+                self.abort(participants, index..len).await;
+
+                return Err(error);
+            }
+        }
+
+        if let Some(log) = &self.log {
+            if let Err(error) = log.record_commit(&self.gid).await {
+                let len = participants.len();
+
+                self.rollback_prepared(participants, 0..len).await;
+
+                return Err(error);
+            }
+        }
+
+        let mut result = Ok(());
+
+        for (index, participant) in participants.iter_mut().enumerate() {
+            let xid = branch_id(&self.gid, index);
+
+            if let Err(error) = participant.commit_prepared(&xid).await {
+                log::warn!("failed to commit prepared transaction {:?}: {}", xid, error);
+
+                if result.is_ok() {
+                    result = Err(error);
+                }
+            }
+        }
+
+        if result.is_ok() {
+            if let Some(log) = &self.log {
+                // every branch is committed; a stale record only makes recovery commit
+                // branches that are no longer prepared
+                if let Err(error) = log.forget(&self.gid).await {
+                    log::warn!(
+                        "failed to forget committed transaction {:?}: {}",
+                        self.gid,
+                        error
+                    );
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Roll back every branch, none of which has been prepared.
+    pub async fn rollback(&self, participants: &mut [&mut dyn TwoPhase]) -> Result<(), Error> {
+        let mut result = Ok(());
+
+        for (index, participant) in participants.iter_mut().enumerate() {
+            if let Err(error) = participant.abort_branch(&branch_id(&self.gid, index)).await {
+                if result.is_ok() {
+                    result = Err(error);
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Resolve the branches left prepared on `participant` by a coordinator that failed
+    /// during [`commit`][Self::commit], returning how many were resolved.
+    ///
+    /// Branches of transactions recorded in `log` are committed and the others rolled back.
+    /// Prepared transactions whose id does not start with the `sqlx:` prefix of the branches
+    /// of a coordinator are left untouched. Run this on every participating database, and
+    /// only while no coordinator is committing, such as at startup; a transaction that is
+    /// being committed could otherwise be rolled back after it was prepared.
+    pub async fn recover(
+        participant: &mut dyn TwoPhase,
+        log: &dyn RecoveryLog,
+    ) -> Result<usize, Error> {
+        Self::recover_matching(participant, log, |_| true).await
+    }
+
+    /// Resolve the branches left prepared on `participant` like [`recover`][Self::recover],
+    /// but only those of the transactions whose global id matches `filter`.
+    ///
+    /// This allows recovery to run while coordinators with other global ids are committing.
+    pub async fn recover_matching(
+        participant: &mut dyn TwoPhase,
+        log: &dyn RecoveryLog,
+        filter: impl Fn(&str) -> bool,
+    ) -> Result<usize, Error> {
+        let mut resolved = 0;
+
+        for xid in participant.prepared_branches().await? {
+            let gid = match parse_branch_id(&xid) {
+                Some(gid) if filter(gid) => gid,
+                _ => continue,
+            };
+
+            if log.is_committed(gid).await? {
+                participant.commit_prepared(&xid).await?;
+            } else {
+                participant.rollback_prepared(&xid).await?;
+            }
+
+            resolved += 1;
+        }
+
+        Ok(resolved)
+    }
+
+    async fn abort(&self, participants: &mut [&mut dyn TwoPhase], indices: Range<usize>) {
+        for index in indices {
+            let xid = branch_id(&self.gid, index);
+
+            if let Err(error) = participants[index].abort_branch(&xid).await {
+                log::warn!("failed to roll back transaction {:?}: {}", xid, error);
+            }
+        }
+    }
+
+    async fn rollback_prepared(
+        &self,
+        participants: &mut [&mut dyn TwoPhase],
+        indices: Range<usize>,
+    ) {
+        for index in indices {
+            let xid = branch_id(&self.gid, index);
+
+            if let Err(error) = participants[index].rollback_prepared(&xid).await {
+                log::warn!(
+                    "failed to roll back prepared transaction {:?}: {}",
+                    xid,
+                    error
+                );
+            }
+        }
+    }
+}
+
+// the longest global id that leaves room for the branch prefix and suffix within the 64
+// bytes MySQL allows for an XA transaction id
+const MAX_GID_LEN: usize = 48;
+
+// distinguishes the branches of a coordinator from other prepared transactions
+const BRANCH_PREFIX: &str = "sqlx:";
+
+fn validate_gid(gid: &str) -> Result<(), Error> {
+    let valid_char = |c: char| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':');
+
+    if gid.is_empty() || gid.len() > MAX_GID_LEN || !gid.chars().all(valid_char) {
+        return Err(Error::Configuration(
+            format!("invalid global transaction id {:?}", gid).into(),
+        ));
+    }
+
+    Ok(())
+}
+
+fn branch_id(gid: &str, index: usize) -> String {
+    format!("{}{}.{}", BRANCH_PREFIX, gid, index)
+}
+
+fn parse_branch_id(xid: &str) -> Option<&str> {
+    if !xid.starts_with(BRANCH_PREFIX) {
+        return None;
+    }
+
+    let xid = &xid[BRANCH_PREFIX.len()..];
+    let dot = xid.rfind('.')?;
+    let (gid, index) = (&xid[..dot], &xid[dot + 1..]);
+
+    if index.parse::<usize>().is_err() || validate_gid(gid).is_err() {
+        return None;
+    }
+
+    Some(gid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{branch_id, parse_branch_id, validate_gid};
+
+    #[test]
+    fn it_validates_global_ids() {
+        assert!(validate_gid("transfer-42").is_ok());
+        assert!(validate_gid("app:orders_2021.07").is_ok());
+
+        assert!(validate_gid("").is_err());
+        assert!(validate_gid("it's").is_err());
+        assert!(validate_gid("with space").is_err());
+        assert!(validate_gid(&"x".repeat(49)).is_err());
+    }
+
+    #[test]
+    fn it_parses_branch_ids() {
+        assert_eq!(
+            parse_branch_id(&branch_id("transfer-42", 1)),
+            Some("transfer-42")
+        );
+        assert_eq!(parse_branch_id(&branch_id("a.1", 0)), Some("a.1"));
+
+        assert_eq!(branch_id("transfer-42", 1), "sqlx:transfer-42.1");
+
+        assert_eq!(parse_branch_id("transfer-42.1"), None);
+        assert_eq!(parse_branch_id("sqlx:transfer-42"), None);
+        assert_eq!(parse_branch_id("sqlx:transfer.x"), None);
+        assert_eq!(parse_branch_id("sqlx:.1"), None);
+    }
+}
@@ -0,0 +1,201 @@
+//! Per-connection overrides of the Rust types SQL values can be decoded as.
+//!
+//! [`Row::try_get`] refuses to decode a column into a Rust type that is not compatible with
+//! its SQL type, even when the value would decode fine; a `String` cannot be read from a
+//! PostgreSQL `CITEXT` column, for instance, as `CITEXT` is an extension type. A
+//! [`TypeOverrides`] set on a connection declares that the values of a SQL type are to be
+//! decoded as some Rust type, which makes every Rust type compatible with that target
+//! acceptable for the columns of that type in the rows returned by the connection.
+//!
+//! ```rust,ignore
+//! use sqlx::type_overrides::TypeOverrides;
+//!
+//! conn.set_type_overrides(
+//!     TypeOverrides::new()
+//!         .decode_as::<String>("CITEXT")
+//!         .decode_as::<Decimal>("NUMERIC"),
+//! );
+//!
+//! let email: String = sqlx::query("SELECT email FROM users")
+//!     .fetch_one(&mut conn)
+//!     .await?
+//!     .try_get("email")?;
+//! ```
+//!
+//! Overrides are consulted by [`Row::try_get`], by [`Value::try_decode`] on the values taken
+//! from those rows, by the conversions of a
+//! [`Conversions`](crate::convert::Conversions) registry and, when set on an `AnyConnection`,
+//! by the `Any` driver. They only relax the type check made before decoding: the value is
+//! still decoded by the requested Rust type, which must understand its representation.
+//!
+//! [`Row::try_get`]: crate::row::Row::try_get
+//! [`Value::try_decode`]: crate::value::Value::try_decode
+
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+
+use crate::database::Database;
+use crate::type_info::TypeInfo;
+use crate::types::Type;
+use crate::HashMap;
+
+/// A Rust type that the values of a SQL type can be declared to be decoded as.
+///
+/// Implemented for every type implementing [`Type`] for the database.
+pub trait DecodeTarget<DB: Database> {
+    // the SQL types the Rust type is declared as; the `Any` driver has one per database
+    #[doc(hidden)]
+    fn target_type_infos() -> Vec<DB::TypeInfo>;
+}
+
+macro_rules! impl_decode_target {
+    ($db:ty) => {
+        impl<T: ?Sized + Type<$db>> DecodeTarget<$db> for T {
+            fn target_type_infos() -> Vec<<$db as Database>::TypeInfo> {
+                vec![T::type_info()]
+            }
+        }
+    };
+}
+
+#[cfg(feature = "postgres")]
+impl_decode_target!(crate::postgres::Postgres);
+
+#[cfg(feature = "mysql")]
+impl_decode_target!(crate::mysql::MySql);
+
+#[cfg(feature = "sqlite")]
+impl_decode_target!(crate::sqlite::Sqlite);
+
+#[cfg(feature = "mssql")]
+impl_decode_target!(crate::mssql::Mssql);
+
+/// Maps SQL types to the Rust type their values are to be decoded as.
+///
+/// See the [module documentation](self) for an example.
+pub struct TypeOverrides<DB: Database> {
+    // keyed by the upper-cased name of the SQL type
+    targets: HashMap<String, Vec<DB::TypeInfo>>,
+}
+
+impl<DB: Database> TypeOverrides<DB> {
+    pub fn new() -> Self {
+        Self {
+            targets: HashMap::new(),
+        }
+    }
+
+    /// Decode the values of the SQL type named `sql_type`, compared case-insensitively, as
+    /// `T`. Replaces any previous override of the same SQL type.
+    pub fn decode_as<T>(mut self, sql_type: &str) -> Self
+    where
+        T: ?Sized + DecodeTarget<DB>,
+    {
+        self.targets
+            .insert(sql_type.to_ascii_uppercase(), T::target_type_infos());
+
+        self
+    }
+
+    /// Returns `true` if no override has been declared.
+    pub fn is_empty(&self) -> bool {
+        self.targets.is_empty()
+    }
+
+    // the overrides as shared by a connection with the rows it returns
+    pub(crate) fn into_shared(self) -> Option<Arc<Self>> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(Arc::new(self))
+        }
+    }
+
+    // whether `T` may be decoded from a value of the SQL type `ty`, either because it is
+    // compatible with `ty` itself or with the target declared for `ty`
+    pub(crate) fn compatible<T>(overrides: Option<&Self>, ty: &DB::TypeInfo) -> bool
+    where
+        T: ?Sized + Type<DB>,
+    {
+        if T::compatible(ty) {
+            return true;
+        }
+
+        let targets = match overrides {
+            Some(overrides) if !overrides.is_empty() => overrides
+                .targets
+                .get(&ty.name().to_ascii_uppercase())
+                .map_or(&[][..], Vec::as_slice),
+
+            _ => return false,
+        };
+
+        targets.iter().any(|target| T::compatible(target))
+    }
+
+    // drop the targets that do not apply to the connection the overrides are set on
+    #[cfg(all(
+        any(
+            feature = "postgres",
+            feature = "mysql",
+            feature = "mssql",
+            feature = "sqlite"
+        ),
+        feature = "any"
+    ))]
+    pub(crate) fn retain_targets(&mut self, mut f: impl FnMut(&DB::TypeInfo) -> bool) {
+        for targets in self.targets.values_mut() {
+            targets.retain(|target| f(target));
+        }
+    }
+}
+
+impl<DB: Database> Default for TypeOverrides<DB> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<DB: Database> Clone for TypeOverrides<DB> {
+    fn clone(&self) -> Self {
+        Self {
+            targets: self.targets.clone(),
+        }
+    }
+}
+
+impl<DB: Database> Debug for TypeOverrides<DB> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.targets.iter().map(|(name, targets)| {
+                let targets: Vec<_> = targets.iter().map(TypeInfo::name).collect();
+
+                (name, targets)
+            }))
+            .finish()
+    }
+}
+
+#[cfg(all(test, feature = "postgres"))]
+mod tests {
+    use super::TypeOverrides;
+    use crate::postgres::{PgTypeInfo, Postgres};
+
+    type PgOverrides = TypeOverrides<Postgres>;
+
+    #[test]
+    fn it_accepts_types_compatible_with_the_target() {
+        let citext = PgTypeInfo::with_name("citext");
+        let overrides = PgOverrides::new().decode_as::<String>("CITEXT");
+
+        assert!(!PgOverrides::compatible::<String>(None, &citext));
+        assert!(PgOverrides::compatible::<String>(Some(&overrides), &citext));
+        assert!(PgOverrides::compatible::<str>(Some(&overrides), &citext));
+        assert!(!PgOverrides::compatible::<i32>(Some(&overrides), &citext));
+
+        // types compatible with the column itself are still accepted
+        let int4 = PgTypeInfo::INT4;
+
+        assert!(PgOverrides::compatible::<i32>(Some(&overrides), &int4));
+    }
+}
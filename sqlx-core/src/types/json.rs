@@ -1,6 +1,9 @@
+use std::borrow::Cow;
+use std::fmt;
 use std::ops::{Deref, DerefMut};
 
-use serde::{Deserialize, Serialize};
+use serde::de::{self, DeserializeSeed, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::value::RawValue as JsonRawValue;
 use serde_json::Value as JsonValue;
 
@@ -132,3 +135,234 @@ where
         <Json<Self> as Decode<DB>>::decode(value).map(|item| item.0)
     }
 }
+
+// a step of a JSON path
+#[derive(Debug, PartialEq)]
+enum PathSegment<'p> {
+    Field(Cow<'p, str>),
+    Element(usize),
+}
+
+/// Deserialize the value at `path` in the JSON document `json`, skipping over the rest of the
+/// document. A missing document, or a path that leads to no value, deserializes as `null`.
+pub(crate) fn json_path<'r, T>(json: Option<&'r JsonRawValue>, path: &str) -> Result<T, BoxDynError>
+where
+    T: Deserialize<'r>,
+{
+    let mut value = json;
+
+    for segment in parse_path(path)? {
+        value = match value {
+            Some(value) => select(value, &segment)?,
+            None => break,
+        };
+    }
+
+    match value {
+        Some(value) => serde_json::from_str(value.get()),
+        None => serde_json::from_str("null"),
+    }
+    .map_err(Into::into)
+}
+
+// parse a path made of the root `$` followed by `.field`, `["field"]` and `[index]` segments
+fn parse_path(path: &str) -> Result<Vec<PathSegment<'_>>, BoxDynError> {
+    let invalid = || format!("invalid JSON path {:?}", path);
+
+    let mut rest = path.strip_prefix('$').ok_or_else(invalid)?;
+    let mut segments = Vec::new();
+
+    while !rest.is_empty() {
+        if let Some(field) = rest.strip_prefix('.') {
+            let end = field.find(&['.', '['][..]).unwrap_or(field.len());
+
+            if end == 0 {
+                return Err(invalid().into());
+            }
+
+            segments.push(PathSegment::Field(Cow::Borrowed(&field[..end])));
+            rest = &field[end..];
+        } else if let Some(subscript) = rest.strip_prefix("[\"") {
+            // the field name is a JSON string, so it may hold escapes, `.` or `]`
+            let mut strings = serde_json::Deserializer::from_str(&rest[1..]).into_iter::<String>();
+            let field = match strings.next() {
+                Some(Ok(field)) => field,
+                _ => return Err(invalid().into()),
+            };
+
+            rest = subscript[strings.byte_offset() - 1..]
+                .strip_prefix(']')
+                .ok_or_else(invalid)?;
+
+            segments.push(PathSegment::Field(Cow::Owned(field)));
+        } else if let Some(subscript) = rest.strip_prefix('[') {
+            let end = subscript.find(']').ok_or_else(invalid)?;
+            let index = subscript[..end].parse().map_err(|_| invalid())?;
+
+            segments.push(PathSegment::Element(index));
+            rest = &subscript[end + 1..];
+        } else {
+            return Err(invalid().into());
+        }
+    }
+
+    Ok(segments)
+}
+
+// select a field of an object or an element of an array, ignoring the other values
+fn select<'r>(
+    value: &'r JsonRawValue,
+    segment: &PathSegment<'_>,
+) -> Result<Option<&'r JsonRawValue>, serde_json::Error> {
+    let json = value.get();
+    let mut deserializer = serde_json::Deserializer::from_str(json);
+
+    match (json.trim_start().as_bytes().first(), segment) {
+        (Some(b'{'), PathSegment::Field(field)) => {
+            deserializer.deserialize_map(FieldVisitor(field))
+        }
+
+        (Some(b'['), PathSegment::Element(index)) => {
+            deserializer.deserialize_seq(ElementVisitor(*index))
+        }
+
+        _ => Ok(None),
+    }
+}
+
+struct FieldVisitor<'a>(&'a str);
+
+impl<'de> Visitor<'de> for FieldVisitor<'_> {
+    type Value = Option<&'de JsonRawValue>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a JSON object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut found = None;
+
+        // the last of duplicate fields wins, as when deserializing a whole document
+        while let Some(matches) = map.next_key_seed(FieldName(self.0))? {
+            if matches {
+                found = Some(map.next_value()?);
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+
+        Ok(found)
+    }
+}
+
+struct ElementVisitor(usize);
+
+impl<'de> Visitor<'de> for ElementVisitor {
+    type Value = Option<&'de JsonRawValue>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a JSON array")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut found = None;
+        let mut index = 0;
+
+        loop {
+            if index == self.0 {
+                found = seq.next_element()?;
+
+                if found.is_none() {
+                    break;
+                }
+            } else if seq.next_element::<IgnoredAny>()?.is_none() {
+                break;
+            }
+
+            index += 1;
+        }
+
+        Ok(found)
+    }
+}
+
+// compares an object key to a field name without allocating, unless the key has escapes
+struct FieldName<'a>(&'a str);
+
+impl<'de> DeserializeSeed<'de> for FieldName<'_> {
+    type Value = bool;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<bool, D::Error> {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl<'de> Visitor<'de> for FieldName<'_> {
+    type Value = bool;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a field name")
+    }
+
+    fn visit_str<E: de::Error>(self, key: &str) -> Result<bool, E> {
+        Ok(key == self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{json_path, parse_path, PathSegment};
+    use serde_json::value::RawValue as JsonRawValue;
+    use std::borrow::Cow;
+
+    #[test]
+    fn it_parses_json_paths() {
+        assert_eq!(parse_path("$").unwrap(), vec![]);
+        assert_eq!(
+            parse_path(r#"$.a["b.c]\"d"][10].e"#).unwrap(),
+            vec![
+                PathSegment::Field(Cow::Borrowed("a")),
+                PathSegment::Field(Cow::Owned("b.c]\"d".to_owned())),
+                PathSegment::Element(10),
+                PathSegment::Field(Cow::Borrowed("e")),
+            ]
+        );
+
+        assert!(parse_path("a.b").is_err());
+        assert!(parse_path("$.").is_err());
+        assert!(parse_path("$..a").is_err());
+        assert!(parse_path("$[x]").is_err());
+        assert!(parse_path("$[\"a\"").is_err());
+        assert!(parse_path("$a").is_err());
+    }
+
+    #[test]
+    fn it_extracts_values_at_json_paths() {
+        let json: &JsonRawValue = serde_json::from_str(
+            r#"{ "user": { "name": "Ann", "tags": ["a", "b"], "nick\u005fname": "A" }, "id": 7 }"#,
+        )
+        .unwrap();
+
+        let name: &str = json_path(Some(json), "$.user.name").unwrap();
+        let nickname: String = json_path(Some(json), "$.user.nick_name").unwrap();
+        let tag: String = json_path(Some(json), "$.user.tags[1]").unwrap();
+        let id: i64 = json_path(Some(json), "$[\"id\"]").unwrap();
+
+        assert_eq!(name, "Ann");
+        assert_eq!(nickname, "A");
+        assert_eq!(tag, "b");
+        assert_eq!(id, 7);
+
+        // missing values are `null`
+        let missing: Option<String> = json_path(Some(json), "$.user.tags[2]").unwrap();
+        let not_an_object: Option<i64> = json_path(Some(json), "$.id.value").unwrap();
+        let no_document: Option<i64> = json_path(None, "$.id").unwrap();
+
+        assert_eq!(missing, None);
+        assert_eq!(not_an_object, None);
+        assert_eq!(no_document, None);
+
+        assert!(json_path::<String>(Some(json), "$.user.missing").is_err());
+        assert!(json_path::<String>(Some(json), "$.id").is_err());
+    }
+}
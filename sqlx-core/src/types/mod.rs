@@ -17,6 +17,9 @@
 //! To represents nullable SQL types, `Option<T>` is supported where `T` implements `Type`.
 //! An `Option<T>` represents a potentially `NULL` value from SQL.
 //!
+//! To bind a `NULL` of a particular SQL type without a value at hand, use [`Null<T>`] or
+//! [`Query::bind_null`](crate::query::Query::bind_null).
+//!
 
 use crate::database::Database;
 
@@ -32,6 +35,8 @@ pub mod git2;
 #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
 mod json;
 
+mod null;
+
 #[cfg(feature = "uuid")]
 #[cfg_attr(docsrs, doc(cfg(feature = "uuid")))]
 pub use uuid::{self, Uuid};
@@ -71,6 +76,11 @@ pub mod ipnetwork {
 #[cfg(feature = "json")]
 pub use json::Json;
 
+#[cfg(feature = "json")]
+pub(crate) use json::json_path;
+
+pub use null::Null;
+
 /// Indicates that a SQL type is supported for a database.
 ///
 /// ## Compile-time verification
@@ -0,0 +1,81 @@
+use std::fmt::{self, Debug, Formatter};
+use std::marker::PhantomData;
+
+use crate::database::{Database, HasArguments};
+use crate::encode::{Encode, IsNull};
+use crate::types::Type;
+
+/// A `NULL` of the SQL type that `T` maps to.
+///
+/// Binding `Null::<T>::new()` sends the same typed `NULL` as binding `None::<T>`, without
+/// needing a value of `T` at hand or resorting to an opaque `None::<()>`:
+///
+/// ```rust,ignore
+/// sqlx::query("UPDATE users SET deleted_at = $1 WHERE id = $2")
+///     .bind(Null::<DateTime<Utc>>::new())
+///     .bind(id)
+///     .execute(&pool)
+///     .await?;
+/// ```
+///
+/// See also [`Query::bind_null`](crate::query::Query::bind_null).
+pub struct Null<T>(PhantomData<fn() -> T>);
+
+impl<T> Null<T> {
+    pub fn new() -> Self {
+        Null(PhantomData)
+    }
+}
+
+impl<T> Default for Null<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for Null<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Null<T> {}
+
+impl<T> Debug for Null<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Null<{}>", std::any::type_name::<T>())
+    }
+}
+
+impl<T, DB> Type<DB> for Null<T>
+where
+    T: Type<DB>,
+    DB: Database,
+{
+    fn type_info() -> DB::TypeInfo {
+        <T as Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <T as Type<DB>>::compatible(ty)
+    }
+}
+
+// encoded exactly as `None::<T>` so every driver sends the same typed NULL for both
+impl<'q, T, DB> Encode<'q, DB> for Null<T>
+where
+    Option<T>: Encode<'q, DB>,
+    DB: Database,
+{
+    fn encode_by_ref(&self, buf: &mut <DB as HasArguments<'q>>::ArgumentBuffer) -> IsNull {
+        None::<T>.encode_by_ref(buf)
+    }
+
+    fn produces(&self) -> Option<DB::TypeInfo> {
+        None::<T>.produces()
+    }
+
+    fn size_hint(&self) -> usize {
+        0
+    }
+}
@@ -2,6 +2,7 @@ use crate::database::{Database, HasValueRef};
 use crate::decode::Decode;
 use crate::error::{mismatched_types, Error};
 use crate::type_info::TypeInfo;
+use crate::type_overrides::TypeOverrides;
 use crate::types::Type;
 use std::borrow::Cow;
 
@@ -67,7 +68,7 @@ pub trait Value {
         if !self.is_null() {
             let ty = self.type_info();
 
-            if !ty.is_null() && !T::compatible(&ty) {
+            if !ty.is_null() && !TypeOverrides::compatible::<T>(self.type_overrides(), &ty) {
                 return Err(Error::Decode(mismatched_types::<Self::Database, T>(&ty)));
             }
         }
@@ -93,6 +94,48 @@ pub trait Value {
     {
         T::decode(self.as_ref()).map_err(Error::Decode)
     }
+
+    /// Deserialize the field at `path` in this JSON value, without deserializing the rest of
+    /// the document.
+    ///
+    /// `path` starts with the root `$`, followed by object fields, as `.field` or
+    /// `["field"]`, and array elements, as `[index]`. A path that leads to no value, or a
+    /// `NULL` value, is deserialized from a JSON `null`, which makes `Option<T>` return `None`.
+    ///
+    /// ```rust,ignore
+    /// let document = row.try_get_raw("document")?.to_owned();
+    ///
+    /// let city: Option<String> = document.json_path("$.customer.addresses[0].city")?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    ///  * [`Decode`] if the value is not JSON, the path is invalid or the field could not be
+    ///    deserialized into the requested type.
+    ///
+    /// [`Decode`]: Error::Decode
+    ///
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    fn json_path<'r, T>(&'r self, path: &str) -> Result<T, Error>
+    where
+        T: serde::Deserialize<'r>,
+        &'r serde_json::value::RawValue: Decode<'r, Self::Database> + Type<Self::Database>,
+    {
+        let json = if self.is_null() {
+            None
+        } else {
+            Some(self.try_decode::<&'r serde_json::value::RawValue>()?)
+        };
+
+        crate::types::json_path(json, path).map_err(Error::Decode)
+    }
+
+    // the overrides set on the connection the row of this value was returned by
+    #[doc(hidden)]
+    fn type_overrides(&self) -> Option<&TypeOverrides<Self::Database>> {
+        None
+    }
 }
 
 /// A reference to a single value from the database.
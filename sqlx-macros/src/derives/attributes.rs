@@ -37,10 +37,18 @@ pub enum RenameAll {
     PascalCase,
 }
 
+#[derive(Copy, Clone, PartialEq)]
+pub enum MatchColumns {
+    Exact,
+    SnakeCase,
+    CaseInsensitive,
+}
+
 pub struct SqlxContainerAttributes {
     pub transparent: bool,
     pub rename: Option<String>,
     pub rename_all: Option<RenameAll>,
+    pub match_columns: Option<MatchColumns>,
     pub repr: Option<Ident>,
 }
 
@@ -54,6 +62,7 @@ pub fn parse_container_attributes(input: &[Attribute]) -> syn::Result<SqlxContai
     let mut repr = None;
     let mut rename = None;
     let mut rename_all = None;
+    let mut match_columns = None;
 
     for attr in input
         .iter()
@@ -90,6 +99,21 @@ pub fn parse_container_attributes(input: &[Attribute]) -> syn::Result<SqlxContai
                                 try_set!(rename_all, val, value)
                             }
 
+                            Meta::NameValue(MetaNameValue {
+                                path,
+                                lit: Lit::Str(val),
+                                ..
+                            }) if path.is_ident("match_columns") => {
+                                let val = match &*val.value() {
+                                    "exact" => MatchColumns::Exact,
+                                    "snake_case" => MatchColumns::SnakeCase,
+                                    "case_insensitive" => MatchColumns::CaseInsensitive,
+                                    _ => fail!(meta, "unexpected value for match_columns"),
+                                };
+
+                                try_set!(match_columns, val, value)
+                            }
+
                             Meta::NameValue(MetaNameValue {
                                 path,
                                 lit: Lit::Str(val),
@@ -122,6 +146,7 @@ pub fn parse_container_attributes(input: &[Attribute]) -> syn::Result<SqlxContai
         repr,
         rename,
         rename_all,
+        match_columns,
     })
 }
 
@@ -167,6 +192,12 @@ pub fn check_transparent_attributes(
         field
     );
 
+    assert_attribute!(
+        attributes.match_columns.is_none(),
+        "unexpected #[sqlx(match_columns = ..)]",
+        input
+    );
+
     assert_attribute!(attributes.repr.is_none(), "unexpected #[repr(..)]", input);
 
     let ch_attributes = parse_child_attributes(&field.attrs)?;
@@ -189,6 +220,12 @@ pub fn check_enum_attributes(input: &DeriveInput) -> syn::Result<SqlxContainerAt
         input
     );
 
+    assert_attribute!(
+        attributes.match_columns.is_none(),
+        "unexpected #[sqlx(match_columns = ..)]",
+        input
+    );
+
     Ok(attributes)
 }
 
@@ -248,6 +285,12 @@ pub fn check_struct_attributes<'a>(
         input
     );
 
+    assert_attribute!(
+        attributes.match_columns.is_none(),
+        "unexpected #[sqlx(match_columns = ..)]",
+        input
+    );
+
     assert_attribute!(attributes.repr.is_none(), "unexpected #[repr(..)]", input);
 
     for field in fields {
@@ -6,7 +6,7 @@ use syn::{
 };
 
 use super::{
-    attributes::{parse_child_attributes, parse_container_attributes},
+    attributes::{parse_child_attributes, parse_container_attributes, MatchColumns},
     rename_all,
 };
 
@@ -59,9 +59,26 @@ fn expand_derive_from_row_struct(
         generics.params.insert(0, parse_quote!(#lifetime));
     }
 
+    let container_attributes = parse_container_attributes(&input.attrs)?;
+
+    // how the name of each field is found among the columns of the row
+    let match_columns = match container_attributes.match_columns {
+        Some(MatchColumns::SnakeCase) => Some(quote!(sqlx::from_row::MatchColumns::SnakeCase)),
+
+        Some(MatchColumns::CaseInsensitive) => {
+            Some(quote!(sqlx::from_row::MatchColumns::CaseInsensitive))
+        }
+
+        Some(MatchColumns::Exact) | None => None,
+    };
+
     let predicates = &mut generics.make_where_clause().predicates;
 
-    predicates.push(parse_quote!(&#lifetime str: sqlx::ColumnIndex<R>));
+    if match_columns.is_some() {
+        predicates.push(parse_quote!(usize: sqlx::ColumnIndex<R>));
+    } else {
+        predicates.push(parse_quote!(&#lifetime str: sqlx::ColumnIndex<R>));
+    }
 
     for field in fields {
         let ty = &field.ty;
@@ -72,43 +89,91 @@ fn expand_derive_from_row_struct(
 
     let (impl_generics, _, where_clause) = generics.split_for_impl();
 
-    let container_attributes = parse_container_attributes(&input.attrs)?;
+    // the name of the column of each field
+    let column_names: Vec<String> = fields
+        .iter()
+        .filter_map(|field| {
+            let id = field.ident.as_ref()?;
+            let attributes = parse_child_attributes(&field.attrs).unwrap();
+
+            attributes
+                .rename
+                .or_else(|| Some(id.to_string().trim_start_matches("r#").to_owned()))
+                .map(|s| match container_attributes.rename_all {
+                    Some(pattern) => rename_all(&s, pattern),
+                    None => s,
+                })
+        })
+        .collect();
+
+    // the columns are matched once for each set of column names, rather than for each row; the
+    // indexes are in a prefixed local so that the bindings of the fields do not shadow them
+    let resolve = match &match_columns {
+        Some(match_columns) => {
+            let len = column_names.len();
+
+            quote!(
+                std::thread_local! {
+                    static COLUMNS: std::cell::RefCell<sqlx::from_row::ColumnCache> =
+                        std::cell::RefCell::new(sqlx::from_row::ColumnCache::new());
+                }
+
+                let mut __sqlx_indexes = [None; #len];
+
+                COLUMNS.with(|columns| {
+                    columns.borrow_mut().resolve(
+                        row,
+                        &[#(#column_names),*],
+                        #match_columns,
+                        &mut __sqlx_indexes,
+                    )
+                });
+            )
+        }
 
-    let reads = fields.iter().filter_map(|field| -> Option<Stmt> {
-        let id = &field.ident.as_ref()?;
-        let attributes = parse_child_attributes(&field.attrs).unwrap();
-        let id_s = attributes
-            .rename
-            .or_else(|| Some(id.to_string().trim_start_matches("r#").to_owned()))
-            .map(|s| match container_attributes.rename_all {
-                Some(pattern) => rename_all(&s, pattern),
-                None => s,
-            })
-            .unwrap();
+        None => quote!(),
+    };
 
-        let ty = &field.ty;
+    let reads = fields.iter().zip(&column_names).enumerate().filter_map(
+        |(i, (field, id_s))| -> Option<Stmt> {
+            let id = &field.ident.as_ref()?;
+            let attributes = parse_child_attributes(&field.attrs).unwrap();
+
+            let ty = &field.ty;
+
+            let get = match &match_columns {
+                Some(_) => quote!(
+                    match __sqlx_indexes[#i] {
+                        Some(index) => row.try_get(index),
+                        None => Err(sqlx::Error::ColumnNotFound(#id_s.to_owned())),
+                    }
+                ),
+
+                None => quote!(row.try_get(#id_s)),
+            };
 
-        if attributes.default {
-            Some(
-                parse_quote!(let #id: #ty = row.try_get(#id_s).or_else(|e| match e {
+            if attributes.default {
+                Some(parse_quote!(let #id: #ty = #get.or_else(|e| match e {
                 sqlx::Error::ColumnNotFound(_) => {
                     Ok(Default::default())
                 },
                 e => Err(e)
-            })?;),
-            )
-        } else {
-            Some(parse_quote!(
-                let #id: #ty = row.try_get(#id_s)?;
-            ))
-        }
-    });
+            })?;))
+            } else {
+                Some(parse_quote!(
+                    let #id: #ty = #get?;
+                ))
+            }
+        },
+    );
 
     let names = fields.iter().map(|field| &field.ident);
 
     Ok(quote!(
         impl #impl_generics sqlx::FromRow<#lifetime, R> for #ident #ty_generics #where_clause {
             fn from_row(row: &#lifetime R) -> sqlx::Result<Self> {
+                #resolve
+
                 #(#reads)*
 
                 Ok(#ident {
@@ -18,6 +18,9 @@ pub struct QueryMacroInput {
     pub(super) arg_exprs: Vec<Expr>,
 
     pub(super) checked: bool,
+
+    /// The name of the database to check the query against, if not the default one.
+    pub(super) db: Option<String>,
 }
 
 enum QuerySrc {
@@ -36,6 +39,7 @@ impl Parse for QueryMacroInput {
         let mut args: Option<Vec<Expr>> = None;
         let mut record_type = RecordType::Generated;
         let mut checked = true;
+        let mut db = None;
 
         let mut expect_comma = false;
 
@@ -66,6 +70,9 @@ impl Parse for QueryMacroInput {
             } else if key == "checked" {
                 let lit_bool = input.parse::<LitBool>()?;
                 checked = lit_bool.value;
+            } else if key == "db" {
+                let lit_str = input.parse::<LitStr>()?;
+                db = Some(parse_db_name(&lit_str)?);
             } else {
                 let message = format!("unexpected input key: {}", key);
                 return Err(syn::Error::new_spanned(key, message));
@@ -85,10 +92,27 @@ impl Parse for QueryMacroInput {
             record_type,
             arg_exprs,
             checked,
+            db,
         })
     }
 }
 
+// database names become part of environment variable and file names
+fn parse_db_name(lit_str: &LitStr) -> syn::Result<String> {
+    let name = lit_str.value();
+
+    let valid_char = |c: char| c.is_ascii_alphanumeric() || c == '_' || c == '-';
+
+    if name.is_empty() || !name.chars().all(valid_char) {
+        return Err(syn::Error::new_spanned(
+            lit_str,
+            "database names may only contain ASCII letters, digits, `_` and `-`",
+        ));
+    }
+
+    Ok(name)
+}
+
 impl QuerySrc {
     /// If the query source is a file, read it to a string. Otherwise return the query string.
     fn resolve(self, source_span: Span) -> syn::Result<String> {
@@ -9,6 +9,7 @@ pub use input::QueryMacroInput;
 use quote::{format_ident, quote};
 use sqlx_core::connection::Connection;
 use sqlx_core::database::Database;
+use sqlx_core::query_data;
 use sqlx_core::{column::Column, describe::Describe, type_info::TypeInfo};
 use sqlx_rt::block_on;
 
@@ -51,31 +52,39 @@ pub fn expand_input(input: QueryMacroInput) -> crate::Result<TokenStream> {
             .map_err(|e| format!("failed to load environment from {:?}, {}", env_path, e))?
     }
 
+    let db = input.db.as_deref();
+    let url_var = query_data::database_url_var(db);
+
     // if `dotenv` wasn't initialized by the above we make sure to do it here
     match (
         dotenv::var("SQLX_OFFLINE")
             .map(|s| s.eq_ignore_ascii_case("true") || s == "1")
             .unwrap_or(false),
-        dotenv::var("DATABASE_URL"),
+        dotenv::var(&url_var),
     ) {
         (false, Ok(db_url)) => expand_from_db(input, &db_url),
 
         #[cfg(feature = "offline")]
         _ => {
-            let data_file_path = std::path::Path::new(&manifest_dir).join("sqlx-data.json");
+            let data_file = query_data::data_file_name(db);
+
+            let data_file_path = std::path::Path::new(&manifest_dir).join(&data_file);
 
-            let workspace_data_file_path = CRATE_ROOT.join("sqlx-data.json");
+            let workspace_data_file_path = CRATE_ROOT.join(&data_file);
 
             if data_file_path.exists() {
                 expand_from_file(input, data_file_path)
             } else if workspace_data_file_path.exists() {
                 expand_from_file(input, workspace_data_file_path)
             } else {
-                Err(
-                    "`DATABASE_URL` must be set, or `cargo sqlx prepare` must have been run \
-                     and sqlx-data.json must exist, to use query macros"
-                        .into(),
+                Err(format!(
+                    "`{}` must be set, or `cargo sqlx prepare{}` must have been run \
+                     and {} must exist, to use query macros",
+                    url_var,
+                    db.map_or_else(String::new, |db| format!(" --db {}", db)),
+                    data_file
                 )
+                .into())
             }
         }
 
@@ -85,7 +94,7 @@ pub fn expand_input(input: QueryMacroInput) -> crate::Result<TokenStream> {
         }
 
         #[cfg(not(feature = "offline"))]
-        (false, Err(_)) => Err("`DATABASE_URL` must be set to use query macros".into()),
+        (false, Err(_)) => Err(format!("`{}` must be set to use query macros", url_var).into()),
     }
 }
 
@@ -309,12 +318,11 @@ where
     // If the build is offline, the cache is our input so it's pointless to also write data for it.
     #[cfg(feature = "offline")]
     if !offline {
-        let mut save_dir = std::path::PathBuf::from(
+        let save_dir = query_data::query_data_dir(
             env::var("CARGO_TARGET_DIR").unwrap_or_else(|_| "target/".into()),
+            input.db.as_deref(),
         );
 
-        save_dir.push("sqlx");
-
         std::fs::create_dir_all(&save_dir)?;
         data.save_in(save_dir, input.src_span)?;
     }
@@ -1,6 +1,9 @@
+use futures::future::BoxFuture;
 use sqlx::pool::PoolOptions;
+use sqlx::two_phase::RecoveryLog;
 use sqlx::{Connection, Database, Pool};
 use std::env;
+use std::sync::Mutex;
 
 pub fn setup_if_needed() {
     let _ = dotenv::dotenv();
@@ -36,6 +39,33 @@ where
     Ok(pool)
 }
 
+// An in-memory recovery log for the two-phase commit tests
+pub struct TestRecoveryLog(Mutex<Vec<String>>);
+
+impl TestRecoveryLog {
+    // A log where the decision to commit `gids` was already recorded
+    pub fn with_committed(gids: &[&str]) -> Self {
+        Self(Mutex::new(gids.iter().map(|&gid| gid.to_owned()).collect()))
+    }
+}
+
+impl RecoveryLog for TestRecoveryLog {
+    fn record_commit<'a>(&'a self, gid: &'a str) -> BoxFuture<'a, sqlx::Result<()>> {
+        self.0.lock().unwrap().push(gid.to_owned());
+        Box::pin(async { Ok(()) })
+    }
+
+    fn forget<'a>(&'a self, gid: &'a str) -> BoxFuture<'a, sqlx::Result<()>> {
+        self.0.lock().unwrap().retain(|logged| logged != gid);
+        Box::pin(async { Ok(()) })
+    }
+
+    fn is_committed<'a>(&'a self, gid: &'a str) -> BoxFuture<'a, sqlx::Result<bool>> {
+        let committed = self.0.lock().unwrap().iter().any(|logged| logged == gid);
+        Box::pin(async move { Ok(committed) })
+    }
+}
+
 // Test type encoding and decoding
 #[macro_export]
 macro_rules! test_type {
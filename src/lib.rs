@@ -12,23 +12,41 @@ compile_error!(
 );
 
 pub use sqlx_core::acquire::Acquire;
+pub use sqlx_core::arena;
 pub use sqlx_core::arguments::{Arguments, IntoArguments};
+pub use sqlx_core::audit;
+pub use sqlx_core::bulk_update::{BulkUpdate, Changes};
 pub use sqlx_core::column::Column;
 pub use sqlx_core::column::ColumnIndex;
-pub use sqlx_core::connection::{ConnectOptions, Connection};
+pub use sqlx_core::connection::{
+    self, connect_with_retry, Capability, ConnectOptions, Connection, RetryPolicy,
+};
+pub use sqlx_core::convert;
 pub use sqlx_core::database::{self, Database};
+pub use sqlx_core::deadline::Deadline;
 pub use sqlx_core::describe::Describe;
 pub use sqlx_core::done::Done;
 pub use sqlx_core::executor::{Execute, Executor};
+pub use sqlx_core::explain;
+pub use sqlx_core::frame;
 pub use sqlx_core::from_row::FromRow;
+pub use sqlx_core::offload::DecodeOffload;
 pub use sqlx_core::pool::{self, Pool};
 pub use sqlx_core::query::{query, query_with};
 pub use sqlx_core::query_as::{query_as, query_as_with};
+pub use sqlx_core::query_builder::{self, QueryBuilder};
 pub use sqlx_core::query_scalar::{query_scalar, query_scalar_with};
+pub use sqlx_core::returning::{Returning, ReturningStream};
 pub use sqlx_core::row::Row;
+pub use sqlx_core::script::Script;
+pub use sqlx_core::snapshot::{QuerySnapshot, Redaction, SnapshotValue};
+pub use sqlx_core::sqlstate;
 pub use sqlx_core::statement::Statement;
+pub use sqlx_core::testing;
 pub use sqlx_core::transaction::{Transaction, TransactionManager};
+pub use sqlx_core::two_phase;
 pub use sqlx_core::type_info::TypeInfo;
+pub use sqlx_core::type_overrides;
 pub use sqlx_core::types::Type;
 pub use sqlx_core::value::{Value, ValueRef};
 
@@ -82,6 +100,15 @@ mod macros;
 #[doc(hidden)]
 pub mod ty_match;
 
+// derive support
+#[cfg(feature = "macros")]
+#[doc(hidden)]
+pub use sqlx_core::from_row;
+
+// names shared with `cargo sqlx prepare`
+#[doc(hidden)]
+pub use sqlx_core::query_data;
+
 /// Conversions between Rust and SQL types.
 ///
 /// To see how each SQL type maps to a Rust type, see the corresponding `types` module for each
@@ -269,6 +269,23 @@
 ///
 /// See [the README for `sqlx-cli`](https://crates.io/crate/sqlx-cli) for more information.
 ///
+/// ## Multiple Databases
+/// A query can be checked against another database than the one of `DATABASE_URL` by naming
+/// it with `db = "<name>"` before the query, in this macro and all of its variants:
+///
+/// ```rust,ignore
+/// let visits = sqlx::query!(db = "analytics", "select count(*) from visits where page = $1", page)
+///     .fetch_one(&analytics_pool)
+///     .await?;
+/// ```
+///
+/// The URL of the database named `analytics` is read from `DATABASE_URL_ANALYTICS`: the name
+/// is upper-cased and `-` is replaced with `_`. Each named database can be of a different kind
+/// than the others.
+///
+/// In offline mode, the data of the queries checked against a named database is read from
+/// `sqlx-data-<name>.json`, which is generated by `cargo sqlx prepare --db <name>`.
+///
 /// ## See Also
 /// * [query_as!] if you want to use a struct you can name,
 /// * [query_file!] if you want to define the SQL query out-of-line,
@@ -276,6 +293,13 @@
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query (
+    // checked against the database named `$db`; see "Multiple Databases" above
+    (db = $db:literal, $query:expr) => ({
+        $crate::sqlx_macros::expand_query!(db = $db, source = $query)
+    });
+    (db = $db:literal, $query:expr, $($args:tt)*) => ({
+        $crate::sqlx_macros::expand_query!(db = $db, source = $query, args = [$($args)*])
+    });
     // in Rust 1.45 we can now invoke proc macros in expression position
     ($query:expr) => ({
         $crate::sqlx_macros::expand_query!(source = $query)
@@ -298,6 +322,12 @@ macro_rules! query (
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_unchecked (
+    (db = $db:literal, $query:expr) => ({
+        $crate::sqlx_macros::expand_query!(db = $db, source = $query, checked = false)
+    });
+    (db = $db:literal, $query:expr, $($args:tt)*) => ({
+        $crate::sqlx_macros::expand_query!(db = $db, source = $query, args = [$($args)*], checked = false)
+    });
     ($query:expr) => ({
         $crate::sqlx_macros::expand_query!(source = $query, checked = false)
     });
@@ -351,6 +381,12 @@ macro_rules! query_unchecked (
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_file (
+    (db = $db:literal, $path:literal) => ({
+        $crate::sqlx_macros::expand_query!(db = $db, source_file = $path)
+    });
+    (db = $db:literal, $path:literal, $($args:tt)*) => ({
+        $crate::sqlx_macros::expand_query!(db = $db, source_file = $path, args = [$($args)*])
+    });
     ($path:literal) => ({
         $crate::sqlx_macros::expand_query!(source_file = $path)
     });
@@ -364,6 +400,12 @@ macro_rules! query_file (
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_file_unchecked (
+    (db = $db:literal, $path:literal) => ({
+        $crate::sqlx_macros::expand_query!(db = $db, source_file = $path, checked = false)
+    });
+    (db = $db:literal, $path:literal, $($args:tt)*) => ({
+        $crate::sqlx_macros::expand_query!(db = $db, source_file = $path, args = [$($args)*], checked = false)
+    });
     ($path:literal) => ({
         $crate::sqlx_macros::expand_query!(source_file = $path, checked = false)
     });
@@ -512,6 +554,12 @@ macro_rules! query_file_unchecked (
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_as (
+    (db = $db:literal, $out_struct:path, $query:expr) => ( {
+        $crate::sqlx_macros::expand_query!(db = $db, record = $out_struct, source = $query)
+    });
+    (db = $db:literal, $out_struct:path, $query:expr, $($args:tt)*) => ( {
+        $crate::sqlx_macros::expand_query!(db = $db, record = $out_struct, source = $query, args = [$($args)*])
+    });
     ($out_struct:path, $query:expr) => ( {
         $crate::sqlx_macros::expand_query!(record = $out_struct, source = $query)
     });
@@ -556,6 +604,12 @@ macro_rules! query_as (
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_file_as (
+    (db = $db:literal, $out_struct:path, $path:literal) => ( {
+        $crate::sqlx_macros::expand_query!(db = $db, record = $out_struct, source_file = $path)
+    });
+    (db = $db:literal, $out_struct:path, $path:literal, $($args:tt)*) => ( {
+        $crate::sqlx_macros::expand_query!(db = $db, record = $out_struct, source_file = $path, args = [$($args)*])
+    });
     ($out_struct:path, $path:literal) => ( {
         $crate::sqlx_macros::expand_query!(record = $out_struct, source_file = $path)
     });
@@ -569,6 +623,12 @@ macro_rules! query_file_as (
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_as_unchecked (
+    (db = $db:literal, $out_struct:path, $query:expr) => ( {
+        $crate::sqlx_macros::expand_query!(db = $db, record = $out_struct, source = $query, checked = false)
+    });
+    (db = $db:literal, $out_struct:path, $query:expr, $($args:tt)*) => ( {
+        $crate::sqlx_macros::expand_query!(db = $db, record = $out_struct, source = $query, args = [$($args)*], checked = false)
+    });
     ($out_struct:path, $query:expr) => ( {
         $crate::sqlx_macros::expand_query!(record = $out_struct, source = $query, checked = false)
     });
@@ -584,6 +644,12 @@ macro_rules! query_as_unchecked (
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_file_as_unchecked (
+    (db = $db:literal, $out_struct:path, $path:literal) => ( {
+        $crate::sqlx_macros::expand_query!(db = $db, record = $out_struct, source_file = $path, checked = false)
+    });
+    (db = $db:literal, $out_struct:path, $path:literal, $($args:tt)*) => ( {
+        $crate::sqlx_macros::expand_query!(db = $db, record = $out_struct, source_file = $path, args = [$($args)*], checked = false)
+    });
     ($out_struct:path, $path:literal) => ( {
         $crate::sqlx_macros::expand_query!(record = $out_struct, source_file = $path, checked = false)
     });
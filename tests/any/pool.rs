@@ -62,3 +62,36 @@ async fn pool_should_be_returned_failed_transactions() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn pool_should_account_for_detached_and_leaked_connections() -> anyhow::Result<()> {
+    let pool = AnyPoolOptions::new()
+        .max_connections(2)
+        .connect_timeout(Duration::from_secs(3))
+        .connect(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    // a detached connection gives up its slot
+    let mut detached = pool.acquire().await?.detach();
+    sqlx::query("SELECT 1").execute(&mut detached).await?;
+    assert_eq!(pool.size(), 0);
+
+    // a leaked connection keeps its slot
+    let leaked = pool.acquire().await?.leak();
+    assert_eq!(pool.size(), 1);
+
+    let _conn = pool.acquire().await?;
+    assert!(pool.try_acquire().is_none());
+
+    // the leaked connection takes back its own slot
+    assert!(pool.attach(leaked).is_ok());
+    assert_eq!(pool.size(), 2);
+
+    // the pool is full, so the detached connection is given back
+    let detached = pool.attach(detached).unwrap_err();
+    drop(detached);
+
+    assert!(pool.try_acquire().is_some());
+
+    Ok(())
+}
@@ -325,3 +325,37 @@ async fn it_can_prepare_then_execute() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_can_reset_the_session() -> anyhow::Result<()> {
+    let mut conn = new::<Mssql>().await?;
+
+    conn.execute("CREATE TABLE #reset_session (id INT)").await?;
+
+    let mut tx = conn.begin().await?;
+    tx.execute("INSERT INTO #reset_session (id) VALUES (1)")
+        .await?;
+
+    // the transaction is left open on the connection
+    std::mem::forget(tx);
+
+    let _: i32 = sqlx::query_scalar("SELECT @p1")
+        .bind(1_i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    conn.reset().await?;
+
+    assert_eq!(conn.cached_statements_size(), 0);
+
+    let count: i32 = sqlx::query_scalar("SELECT @@TRANCOUNT")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 0);
+
+    // the temporary table is gone
+    conn.execute("CREATE TABLE #reset_session (id INT)").await?;
+
+    Ok(())
+}
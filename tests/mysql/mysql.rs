@@ -1,8 +1,13 @@
 use futures::TryStreamExt;
-use sqlx::mysql::{MySql, MySqlConnection, MySqlPool, MySqlPoolOptions, MySqlRow};
+use sqlx::explain::Explain;
+use sqlx::mysql::{
+    MySql, MySqlConnectOptions, MySqlConnection, MySqlPool, MySqlPoolOptions, MySqlRow,
+};
+use sqlx::two_phase::{Coordinator, TwoPhase};
 use sqlx::{Column, Connection, Done, Executor, Row, Statement, TypeInfo};
-use sqlx_test::{new, setup_if_needed};
+use sqlx_test::{new, setup_if_needed, TestRecoveryLog};
 use std::env;
+use std::time::Duration;
 
 #[sqlx_macros::test]
 async fn it_connects() -> anyhow::Result<()> {
@@ -237,6 +242,44 @@ async fn it_caches_statements() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_closes_prepared_statements_explicitly() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let statement = conn.prepare("SELECT ? AS val").await?;
+    let _ = conn.prepare("SELECT 1").await?;
+
+    assert_eq!(2, conn.cached_statements_size());
+
+    // leave rows unread so the connection is still busy when the statements are closed
+    {
+        let mut rows = sqlx::query("SELECT 1 UNION ALL SELECT 2")
+            .persistent(false)
+            .fetch(&mut conn);
+
+        rows.try_next().await?;
+    }
+
+    assert!(statement.close(&mut conn).await?);
+    assert!(!statement.close(&mut conn).await?);
+    assert!(conn.close_cached_statement("SELECT 1").await?);
+
+    assert_eq!(0, conn.cached_statements_size());
+
+    // the statement is prepared again on its next use
+    let val: i64 = statement
+        .query()
+        .bind(5_i64)
+        .fetch_one(&mut conn)
+        .await?
+        .get(0);
+
+    assert_eq!(5, val);
+    assert_eq!(1, conn.cached_statements_size());
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_bind_null_and_non_null_issue_540() -> anyhow::Result<()> {
     let mut conn = new::<MySql>().await?;
@@ -387,3 +430,280 @@ async fn test_issue_622() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_commits_two_phase_transactions() -> anyhow::Result<()> {
+    let mut a = new::<MySql>().await?;
+    let mut b = new::<MySql>().await?;
+
+    let coordinator = Coordinator::new("sqlx-test-2pc-commit");
+
+    coordinator.begin(&mut [&mut a, &mut b]).await?;
+
+    sqlx::query("INSERT INTO tweet (text) VALUES ('2pc commit a')")
+        .execute(&mut a)
+        .await?;
+
+    sqlx::query("INSERT INTO tweet (text) VALUES ('2pc commit b')")
+        .execute(&mut b)
+        .await?;
+
+    coordinator.commit(&mut [&mut a, &mut b]).await?;
+
+    let done = sqlx::query("DELETE FROM tweet WHERE text LIKE '2pc commit %'")
+        .execute(&mut a)
+        .await?;
+
+    assert_eq!(2, done.rows_affected());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_recovers_prepared_two_phase_branches() -> anyhow::Result<()> {
+    let log = TestRecoveryLog::with_committed(&["sqlx-test-2pc-recover-commit"]);
+
+    // leave one branch prepared for each outcome, as a coordinator that failed would
+    for (xid, text) in &[
+        ("sqlx:sqlx-test-2pc-recover-commit.0", "2pc recover commit"),
+        (
+            "sqlx:sqlx-test-2pc-recover-rollback.0",
+            "2pc recover rollback",
+        ),
+    ] {
+        let mut conn = new::<MySql>().await?;
+
+        conn.begin_branch(xid).await?;
+
+        sqlx::query(&format!("INSERT INTO tweet (text) VALUES ('{}')", text))
+            .execute(&mut conn)
+            .await?;
+
+        conn.prepare_branch(xid).await?;
+        conn.close().await?;
+    }
+
+    let mut conn = new::<MySql>().await?;
+
+    // other tests may have branches of their own prepared at the same time
+    let own_branches = |xid: &String| xid.starts_with("sqlx:sqlx-test-2pc-recover-");
+    let own_gids = |gid: &str| gid.starts_with("sqlx-test-2pc-recover-");
+
+    let mut prepared = conn.prepared_branches().await?;
+    prepared.retain(own_branches);
+    prepared.sort();
+
+    assert_eq!(
+        prepared,
+        vec![
+            "sqlx:sqlx-test-2pc-recover-commit.0",
+            "sqlx:sqlx-test-2pc-recover-rollback.0"
+        ]
+    );
+
+    assert_eq!(
+        2,
+        Coordinator::recover_matching(&mut conn, &log, own_gids).await?
+    );
+
+    let mut prepared = conn.prepared_branches().await?;
+    prepared.retain(own_branches);
+
+    assert!(prepared.is_empty());
+
+    let texts: Vec<String> =
+        sqlx::query_scalar("SELECT text FROM tweet WHERE text LIKE '2pc recover %'")
+            .fetch_all(&mut conn)
+            .await?;
+
+    assert_eq!(texts, vec!["2pc recover commit"]);
+
+    sqlx::query("DELETE FROM tweet WHERE text LIKE '2pc recover %'")
+        .execute(&mut conn)
+        .await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_reset_the_session() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute("SET @reset = 1").await?;
+    conn.execute("CREATE TEMPORARY TABLE reset_session (id INT)")
+        .await?;
+
+    let _: i64 = sqlx::query_scalar("SELECT ?")
+        .bind(1_i64)
+        .fetch_one(&mut conn)
+        .await?;
+
+    conn.reset().await?;
+
+    assert_eq!(conn.cached_statements_size(), 0);
+
+    let (var, time_zone): (Option<i64>, String) = sqlx::query_as("SELECT @reset, @@time_zone")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(var, None);
+
+    // the session is configured again once reset
+    assert_eq!(time_zone, "+00:00");
+
+    // the temporary table is gone
+    conn.execute("CREATE TEMPORARY TABLE reset_session (id INT)")
+        .await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_explains_queries() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute(
+        r#"
+CREATE TEMPORARY TABLE explain_test (
+    id INTEGER PRIMARY KEY AUTO_INCREMENT,
+    email VARCHAR(255),
+    name VARCHAR(255),
+    INDEX explain_test_email_idx (email)
+);
+
+INSERT INTO explain_test (email, name)
+VALUES ('ann@example.com', 'Ann'), ('bob@example.com', 'Bob'), ('cat@example.com', 'Cat');
+        "#,
+    )
+    .await?;
+
+    let plan = conn
+        .explain(sqlx::query("SELECT * FROM explain_test WHERE email = ?").bind("ann@example.com"))
+        .await?;
+
+    plan.assert_uses_index("explain_test_email_idx");
+    plan.assert_no_full_scan("explain_test");
+
+    let plan = conn
+        .explain(sqlx::query("SELECT * FROM explain_test WHERE name = ?").bind("Ann"))
+        .await?;
+
+    assert!(plan.scans_table("explain_test"));
+    assert!(!plan.uses_index("explain_test_email_idx"));
+
+    // a join type of `index` reads every entry of the covering index
+    let plan = conn
+        .explain(sqlx::query("SELECT email FROM explain_test"))
+        .await?;
+
+    assert!(plan.scans_table("explain_test"));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_recovers_from_cancelled_queries() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    // dropped while waiting for the result
+    let cancelled = sqlx_rt::timeout(
+        Duration::from_millis(100),
+        sqlx::query("SELECT SLEEP(0.5), ?")
+            .bind(1_i64)
+            .fetch_one(&mut conn),
+    )
+    .await;
+
+    assert!(cancelled.is_err());
+
+    // dropped after the first of many rows, through both the binary and the text protocol
+    {
+        let mut rows = sqlx::query("SELECT * FROM information_schema.columns WHERE ? = 1")
+            .bind(1_i64)
+            .fetch(&mut conn);
+
+        rows.try_next().await?;
+    }
+
+    {
+        let mut rows = conn.fetch("SELECT * FROM information_schema.columns");
+        rows.try_next().await?;
+    }
+
+    let value: i64 = sqlx::query_scalar("SELECT ?")
+        .bind(2_i64)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(value, 2);
+    assert!(!conn.is_broken());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_streams_rows_over_the_maximum_frame_size() -> anyhow::Result<()> {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let streamed = Arc::new(AtomicUsize::new(0));
+    let sink = Arc::clone(&streamed);
+
+    let options: MySqlConnectOptions = env::var("DATABASE_URL")?.parse()?;
+    let options = options
+        .max_frame_size(64 * 1024)
+        .frame_sink(move |chunk: &[u8]| {
+            sink.fetch_add(chunk.len(), Ordering::SeqCst);
+        });
+
+    let mut conn = MySqlConnection::connect_with(&options).await?;
+
+    let res: Result<Vec<(i64, String)>, _> = sqlx::query_as(
+        "SELECT 1, REPEAT('x', 256 * 1024) UNION ALL SELECT 2, REPEAT('x', 512 * 1024)",
+    )
+    .fetch_all(&mut conn)
+    .await;
+
+    assert!(matches!(
+        res,
+        Err(sqlx::Error::FrameTooLarge { size, max }) if max == 64 * 1024 && size > 256 * 1024
+    ));
+
+    // the oversized row was streamed to the sink instead of being buffered
+    assert!(streamed.load(Ordering::SeqCst) > 256 * 1024);
+
+    // the rest of the result is discarded and the connection remains usable
+    let value: i64 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+
+    assert_eq!(value, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_compresses_traffic_when_enabled() -> anyhow::Result<()> {
+    let options: MySqlConnectOptions = env::var("DATABASE_URL")?.parse()?;
+    let mut conn = MySqlConnection::connect_with(&options.compression(true)).await?;
+
+    // short packets are sent as they are
+    let value: i64 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+
+    assert_eq!(value, 1);
+
+    // long packets, both ways, are compressed
+    let text = "the quick brown fox jumps over the lazy dog ".repeat(32 * 1024);
+
+    let rows: Vec<(i64, String)> =
+        sqlx::query_as("SELECT 1, ? UNION ALL SELECT 2, REPEAT('abc', 500000)")
+            .bind(&text)
+            .fetch_all(&mut conn)
+            .await?;
+
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].1, text);
+    assert_eq!(rows[1].1, "abc".repeat(500000));
+
+    conn.ping().await?;
+
+    Ok(())
+}
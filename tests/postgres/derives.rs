@@ -499,6 +499,65 @@ async fn test_from_row_with_rename_all() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[cfg(feature = "macros")]
+#[sqlx_macros::test]
+async fn test_from_row_with_match_columns() -> anyhow::Result<()> {
+    #[derive(Debug, sqlx::FromRow)]
+    #[sqlx(match_columns = "snake_case")]
+    struct SnakeAccount {
+        user_id: i32,
+        user_name: String,
+        #[sqlx(default)]
+        user_surname: Option<String>,
+    }
+
+    #[derive(Debug, sqlx::FromRow)]
+    #[sqlx(match_columns = "case_insensitive")]
+    struct InsensitiveAccount {
+        userid: i32,
+        username: String,
+    }
+
+    let mut conn = new::<Postgres>().await?;
+
+    let account: SnakeAccount =
+        sqlx::query_as(r#"SELECT * from (VALUES (1, 'foo')) accounts("UserId", "userName")"#)
+            .fetch_one(&mut conn)
+            .await?;
+
+    assert_eq!(1, account.user_id);
+    assert_eq!("foo", account.user_name);
+    assert_eq!(None, account.user_surname);
+
+    // the columns are matched again once their names change
+    let account: SnakeAccount = sqlx::query_as(
+        r#"SELECT * from (VALUES ('Bar', 'bar', 2)) accounts("USER_SURNAME", user_name, "userID")"#,
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(2, account.user_id);
+    assert_eq!("bar", account.user_name);
+    assert_eq!(Some("Bar"), account.user_surname.as_deref());
+
+    let account: InsensitiveAccount =
+        sqlx::query_as(r#"SELECT * from (VALUES (1, 'foo')) accounts("USERID", "UserName")"#)
+            .fetch_one(&mut conn)
+            .await?;
+
+    assert_eq!(1, account.userid);
+    assert_eq!("foo", account.username);
+
+    // a column missing from the row is still an error
+    let result: sqlx::Result<InsensitiveAccount> = sqlx::query_as("SELECT 1 AS userid")
+        .fetch_one(&mut conn)
+        .await;
+
+    assert!(matches!(result, Err(sqlx::Error::ColumnNotFound(_))));
+
+    Ok(())
+}
+
 #[cfg(feature = "macros")]
 #[sqlx_macros::test]
 async fn test_from_row_tuple() -> anyhow::Result<()> {
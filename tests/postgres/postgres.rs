@@ -1,11 +1,18 @@
 use futures::TryStreamExt;
+use sqlx::explain::Explain;
 use sqlx::postgres::{
-    PgConnectOptions, PgConnection, PgDatabaseError, PgErrorPosition, PgSeverity,
+    PgConnectOptions, PgConnection, PgDatabaseError, PgErrorPosition, PgListener,
+    PgListenerOverflow, PgSeverity,
 };
 use sqlx::postgres::{PgPoolOptions, PgRow, Postgres};
+use sqlx::two_phase::{Coordinator, TwoPhase};
+use sqlx::Capability;
+use sqlx::Deadline;
 use sqlx::{Column, Connection, Done, Executor, Row, Statement, TypeInfo};
-use sqlx_test::{new, setup_if_needed};
+use sqlx::{QuerySnapshot, SnapshotValue};
+use sqlx_test::{new, setup_if_needed, TestRecoveryLog};
 use std::env;
+use std::sync::Mutex;
 use std::thread;
 use std::time::Duration;
 
@@ -143,6 +150,43 @@ CREATE TEMPORARY TABLE json_stuff (obj json, obj2 jsonb);
     Ok(())
 }
 
+#[cfg(feature = "json")]
+#[sqlx_macros::test]
+async fn it_extracts_json_paths_from_values() -> anyhow::Result<()> {
+    use sqlx::{Value, ValueRef};
+
+    let mut conn = new::<Postgres>().await?;
+
+    let row = sqlx::query(
+        r#"SELECT '{"user": {"name": "Ann", "tags": ["a", "b"]}}'::jsonb, NULL::json, 1"#,
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    let document = row.try_get_raw(0)?.to_owned();
+
+    let name: String = document.json_path("$.user.name")?;
+    let tag: Option<String> = document.json_path("$.user.tags[1]")?;
+    let missing: Option<String> = document.json_path("$.user.email")?;
+
+    assert_eq!(name, "Ann");
+    assert_eq!(tag.as_deref(), Some("b"));
+    assert_eq!(missing, None);
+
+    let null: Option<String> = row.try_get_raw(1)?.to_owned().json_path("$.user")?;
+
+    assert_eq!(null, None);
+
+    // only JSON values can be searched
+    assert!(row
+        .try_get_raw(2)?
+        .to_owned()
+        .json_path::<Option<i32>>("$")
+        .is_err());
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_works_with_cache_disabled() -> anyhow::Result<()> {
     setup_if_needed();
@@ -654,6 +698,83 @@ async fn it_closes_statement_from_cache_issue_470() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_closes_prepared_statements_explicitly() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let statement = conn.prepare("SELECT $1::int4 AS val").await?;
+    let _ = conn.prepare("SELECT 1").await?;
+
+    assert_eq!(2, conn.cached_statements_size());
+
+    assert!(statement.close(&mut conn).await?);
+    assert!(!statement.close(&mut conn).await?);
+    assert!(conn.close_cached_statement("SELECT 1").await?);
+
+    assert_eq!(0, conn.cached_statements_size());
+
+    // the statement is prepared again on its next use
+    let val: i32 = statement
+        .query()
+        .bind(5_i32)
+        .fetch_one(&mut conn)
+        .await?
+        .get(0);
+
+    assert_eq!(5, val);
+    assert_eq!(1, conn.cached_statements_size());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_dumps_connection_state() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let state = conn.dump_state();
+
+    assert_eq!(state.transaction_depth, 0);
+    assert!(state.server_parameters.contains_key("server_version"));
+
+    let cached = state.cached_statements;
+
+    let mut tx = conn.begin().await?;
+
+    let state = tx.dump_state();
+
+    assert_eq!(state.transaction_depth, 1);
+    assert_eq!(state.last_sql.as_deref(), Some("BEGIN"));
+
+    let _: i32 = sqlx::query_scalar("SELECT $1 + 1 AS dumped")
+        .bind(1_i32)
+        .fetch_one(&mut tx)
+        .await?;
+
+    let state = tx.dump_state();
+
+    assert_eq!(state.cached_statements, cached + 1);
+    assert_eq!(state.last_sql.as_deref(), Some("SELECT $1 + 1 AS dumped"));
+
+    // parameters the server reports as they change are tracked too
+    tx.execute("SET application_name = 'dumped'").await?;
+
+    let state = tx.dump_state();
+
+    assert_eq!(
+        state
+            .server_parameters
+            .get("application_name")
+            .map(String::as_str),
+        Some("dumped")
+    );
+
+    tx.rollback().await?;
+
+    assert_eq!(conn.dump_state().transaction_depth, 0);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_sets_application_name() -> anyhow::Result<()> {
     sqlx_test::setup_if_needed();
@@ -763,3 +884,676 @@ async fn test_issue_622() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_binds_typed_nulls() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let (ty, is_null): (String, bool) = sqlx::query_as("SELECT pg_typeof($1)::text, $2 IS NULL")
+        .bind_null::<i32>()
+        .bind(sqlx::types::Null::<String>::new())
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(ty, "integer");
+    assert!(is_null);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_gets_values_through_conversions() -> anyhow::Result<()> {
+    let conversions = sqlx::convert::Conversions::<Postgres>::new()
+        .with_numeric_widening()
+        .register::<String, String>();
+
+    let mut conn = new::<Postgres>().await?;
+
+    let row = sqlx::query("SELECT 1::int2, 2::int4, 3::int8, 1.5::float4, 'text', NULL::int4")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(conversions.try_get::<i64, _>(&row, 0)?, 1);
+    assert_eq!(conversions.try_get::<i64, _>(&row, 1)?, 2);
+    assert_eq!(conversions.try_get::<i64, _>(&row, 2)?, 3);
+    assert_eq!(conversions.try_get::<f64, _>(&row, 3)?, 1.5);
+    assert_eq!(conversions.try_get::<String, _>(&row, 4)?, "text");
+    assert_eq!(conversions.try_get_optional::<i64, _>(&row, 5)?, None);
+
+    // narrowing is not registered
+    assert!(conversions.try_get::<i32, _>(&row, 2).is_err());
+
+    // widening is also built into `Row::try_get`
+    assert_eq!(row.try_get::<i64, _>(0)?, 1);
+    assert_eq!(row.try_get::<i64, _>(1)?, 2);
+    assert_eq!(row.try_get::<i32, _>(0)?, 1);
+    assert_eq!(row.try_get::<f64, _>(3)?, 1.5);
+    assert!(row.try_get::<i32, _>(2).is_err());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_offloads_decoding_of_large_rows() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // rows of 1 byte and 1 MiB, alternating; the large rows are sent in chunks of two
+    let offload = sqlx::DecodeOffload::new(1024).chunk_size(2 * 1024 * 1024);
+
+    let rows: Vec<(i32, Vec<u8>)> = sqlx::query_as(
+        "SELECT i, CASE WHEN i % 2 = 0 THEN repeat('x', 1024 * 1024)::bytea ELSE 'x'::bytea END \
+         FROM generate_series(1, 6) i",
+    )
+    .fetch_all_offloaded(&mut conn, &offload)
+    .await?;
+
+    assert_eq!(rows.len(), 6);
+
+    for (i, (id, data)) in rows.into_iter().enumerate() {
+        assert_eq!(id, i as i32 + 1);
+        assert_eq!(data.len(), if id % 2 == 0 { 1024 * 1024 } else { 1 });
+    }
+
+    Ok(())
+}
+
+// `#[tokio::test]` runs on the basic scheduler, where `block_in_place` would panic
+#[cfg(feature = "_rt-tokio")]
+#[tokio::test]
+async fn it_offloads_decoding_on_the_basic_scheduler() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let mut conn = PgConnection::connect(&env::var("DATABASE_URL")?).await?;
+
+    let offload = sqlx::DecodeOffload::new(1024);
+
+    let rows: Vec<(Vec<u8>,)> = sqlx::query_as("SELECT repeat('x', 1024 * 1024)::bytea")
+        .fetch_all_offloaded(&mut conn, &offload)
+        .await?;
+
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].0.len(), 1024 * 1024);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_skips_frames_over_the_maximum_size() -> anyhow::Result<()> {
+    let mut options: PgConnectOptions = env::var("DATABASE_URL")?.parse().unwrap();
+    options = options.max_frame_size(64 * 1024);
+
+    let mut conn = PgConnection::connect_with(&options).await?;
+
+    let res: Result<Vec<(i32, String)>, _> =
+        sqlx::query_as("SELECT i, repeat('x', i * 64 * 1024) FROM generate_series(0, 3) i")
+            .fetch_all(&mut conn)
+            .await;
+
+    assert!(matches!(res, Err(sqlx::Error::FrameTooLarge { max, .. }) if max == 64 * 1024));
+
+    // the rest of the result is discarded and the connection remains usable
+    let value: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+
+    assert_eq!(value, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_streams_frames_over_the_maximum_size_to_the_sink() -> anyhow::Result<()> {
+    use sqlx::frame::FrameSink;
+    use std::sync::Arc;
+
+    // the size of each frame and the number of bytes streamed for it
+    struct Collect(Arc<Mutex<Vec<(usize, usize)>>>);
+
+    impl FrameSink for Collect {
+        fn begin(&self, size: usize) {
+            self.0.lock().unwrap().push((size, 0));
+        }
+
+        fn write(&self, chunk: &[u8]) {
+            self.0.lock().unwrap().last_mut().unwrap().1 += chunk.len();
+        }
+    }
+
+    let frames = Arc::new(Mutex::new(Vec::new()));
+
+    let mut options: PgConnectOptions = env::var("DATABASE_URL")?.parse().unwrap();
+    options = options
+        .max_frame_size(64 * 1024)
+        .frame_sink(Collect(Arc::clone(&frames)));
+
+    let mut conn = PgConnection::connect_with(&options).await?;
+
+    let res: Result<Vec<(i32, String)>, _> =
+        sqlx::query_as("SELECT i, repeat('x', i * 64 * 1024) FROM generate_series(0, 3) i")
+            .fetch_all(&mut conn)
+            .await;
+
+    assert!(matches!(res, Err(sqlx::Error::FrameTooLarge { .. })));
+
+    // the rest of the result, holding the other oversized rows, is drained before this query
+    let value: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+
+    assert_eq!(value, 1);
+
+    // the rows of 64, 128 and 192 KiB were streamed whole
+    let frames = frames.lock().unwrap().clone();
+
+    assert_eq!(frames.len(), 3);
+
+    for (i, &(size, streamed)) in frames.iter().enumerate() {
+        assert!(size > (i + 1) * 64 * 1024);
+        assert_eq!(streamed, size);
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_drains_the_pool_on_server_shutdown_notice() -> anyhow::Result<()> {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let notices = Arc::new(AtomicUsize::new(0));
+
+    let pool = PgPoolOptions::new()
+        .max_connections(2)
+        .test_before_acquire(false)
+        .on_server_shutdown({
+            let notices = Arc::clone(&notices);
+            move || {
+                notices.fetch_add(1, Ordering::SeqCst);
+            }
+        })
+        .connect(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let mut conn = pool.acquire().await?;
+
+    let pid: i32 = sqlx::query_scalar("SELECT pg_backend_pid()")
+        .fetch_one(&mut conn)
+        .await?;
+
+    // terminated backends are sent an `admin_shutdown` error before being disconnected
+    sqlx::query("SELECT pg_terminate_backend($1)")
+        .bind(pid)
+        .execute(&pool)
+        .await?;
+
+    assert_eq!(pool.num_idle(), 1);
+
+    let err = sqlx::query("SELECT 1")
+        .execute(&mut conn)
+        .await
+        .unwrap_err();
+
+    assert!(err
+        .as_database_error()
+        .map_or(false, |err| err.is_server_shutdown()));
+    assert!(conn.received_shutdown_notice());
+
+    drop(conn);
+
+    assert_eq!(notices.load(Ordering::SeqCst), 1);
+    assert_eq!(pool.num_idle(), 0);
+    assert_eq!(pool.size(), 0);
+
+    // the pool reconnects as it is used
+    let value: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&pool).await?;
+
+    assert_eq!(value, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_commits_two_phase_transactions() -> anyhow::Result<()> {
+    let mut a = new::<Postgres>().await?;
+    let mut b = new::<Postgres>().await?;
+
+    let coordinator = Coordinator::new("sqlx-test-2pc-commit");
+
+    coordinator.begin(&mut [&mut a, &mut b]).await?;
+
+    // the branches are transactions of their own connections
+    assert_eq!(a.dump_state().transaction_depth, 1);
+    assert!(a.begin_branch("sqlx:sqlx-test-2pc-nested.0").await.is_err());
+
+    sqlx::query("INSERT INTO tweet (text) VALUES ('2pc commit a')")
+        .execute(&mut a)
+        .await?;
+
+    sqlx::query("INSERT INTO tweet (text) VALUES ('2pc commit b')")
+        .execute(&mut b)
+        .await?;
+
+    coordinator.commit(&mut [&mut a, &mut b]).await?;
+
+    assert_eq!(a.dump_state().transaction_depth, 0);
+    assert_eq!(b.dump_state().transaction_depth, 0);
+
+    let done = sqlx::query("DELETE FROM tweet WHERE text LIKE '2pc commit %'")
+        .execute(&mut a)
+        .await?;
+
+    assert_eq!(2, done.rows_affected());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_recovers_prepared_two_phase_branches() -> anyhow::Result<()> {
+    let log = TestRecoveryLog::with_committed(&["sqlx-test-2pc-recover-commit"]);
+
+    // leave one branch prepared for each outcome, as a coordinator that failed would
+    for (xid, text) in &[
+        ("sqlx:sqlx-test-2pc-recover-commit.0", "2pc recover commit"),
+        (
+            "sqlx:sqlx-test-2pc-recover-rollback.0",
+            "2pc recover rollback",
+        ),
+    ] {
+        let mut conn = new::<Postgres>().await?;
+
+        conn.begin_branch(xid).await?;
+
+        sqlx::query(&format!("INSERT INTO tweet (text) VALUES ('{}')", text))
+            .execute(&mut conn)
+            .await?;
+
+        conn.prepare_branch(xid).await?;
+        conn.close().await?;
+    }
+
+    let mut conn = new::<Postgres>().await?;
+
+    // other tests may have branches of their own prepared at the same time
+    let own_branches = |xid: &String| xid.starts_with("sqlx:sqlx-test-2pc-recover-");
+    let own_gids = |gid: &str| gid.starts_with("sqlx-test-2pc-recover-");
+
+    let mut prepared = conn.prepared_branches().await?;
+    prepared.retain(own_branches);
+    prepared.sort();
+
+    assert_eq!(
+        prepared,
+        vec![
+            "sqlx:sqlx-test-2pc-recover-commit.0",
+            "sqlx:sqlx-test-2pc-recover-rollback.0"
+        ]
+    );
+
+    assert_eq!(
+        2,
+        Coordinator::recover_matching(&mut conn, &log, own_gids).await?
+    );
+
+    let mut prepared = conn.prepared_branches().await?;
+    prepared.retain(own_branches);
+
+    assert!(prepared.is_empty());
+
+    let texts: Vec<String> =
+        sqlx::query_scalar("SELECT text FROM tweet WHERE text LIKE '2pc recover %'")
+            .fetch_all(&mut conn)
+            .await?;
+
+    assert_eq!(texts, vec!["2pc recover commit"]);
+
+    sqlx::query("DELETE FROM tweet WHERE text LIKE '2pc recover %'")
+        .execute(&mut conn)
+        .await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_returns_rows_with_rows_affected() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute(
+        r#"
+CREATE TEMPORARY TABLE returning_test (id INTEGER PRIMARY KEY, done BOOLEAN NOT NULL);
+INSERT INTO returning_test VALUES (1, TRUE), (2, FALSE), (3, TRUE), (4, FALSE);
+        "#,
+    )
+    .await?;
+
+    let deleted =
+        sqlx::query_scalar::<_, i32>("DELETE FROM returning_test WHERE done RETURNING id")
+            .execute_returning(&mut conn)
+            .await?;
+
+    assert_eq!(deleted.rows_affected(), 2);
+
+    let mut ids = deleted.into_rows();
+    ids.sort();
+
+    assert_eq!(ids, [1, 3]);
+
+    let mut updated =
+        sqlx::query("UPDATE returning_test SET done = TRUE WHERE id = 2 RETURNING id")
+            .fetch_returning(&mut conn);
+
+    let row = updated.try_next().await?.unwrap();
+
+    assert_eq!(row.try_get::<i32, _>(0)?, 2);
+    assert!(updated.try_next().await?.is_none());
+    assert!(updated.is_finished());
+    assert_eq!(updated.rows_affected(), 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_resets_the_session_on_release() -> anyhow::Result<()> {
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .reset_on_release(true)
+        .connect(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let mut conn = pool.acquire().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE reset_on_release (id INT)")
+        .await?;
+    conn.execute("SET application_name = 'reset_on_release'")
+        .await?;
+
+    let _: i32 = sqlx::query_scalar("SELECT $1")
+        .bind(1_i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    drop(conn);
+
+    // the pool only has one connection, which is given back once reset
+    let mut conn = pool.acquire().await?;
+
+    let (table, application_name): (Option<String>, String) = sqlx::query_as(
+        "SELECT to_regclass('reset_on_release')::text, current_setting('application_name')",
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(table, None);
+    assert_ne!(application_name, "reset_on_release");
+
+    // only the statement prepared since the reset is cached
+    assert_eq!(conn.cached_statements_size(), 1);
+
+    // statements are prepared again after the reset
+    let value: i32 = sqlx::query_scalar("SELECT $1")
+        .bind(1_i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(value, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_opens_new_connections_with_replaced_options() -> anyhow::Result<()> {
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse().unwrap();
+
+    let pool = PgPoolOptions::new()
+        .max_connections(2)
+        .connect_with(options.clone().application_name("before_rotation"))
+        .await?;
+
+    let mut before = pool.acquire().await?;
+
+    pool.set_connect_options(options.application_name("after_rotation"));
+
+    let mut after = pool.acquire().await?;
+
+    let query = "SELECT current_setting('application_name')";
+
+    // the connection opened before keeps being used
+    let name: String = sqlx::query_scalar(query).fetch_one(&mut before).await?;
+    assert_eq!(name, "before_rotation");
+
+    let name: String = sqlx::query_scalar(query).fetch_one(&mut after).await?;
+    assert_eq!(name, "after_rotation");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_reports_capabilities() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    assert!(conn.supports(Capability::Returning));
+    assert!(conn.supports(Capability::JsonOperators));
+
+    conn.execute("CREATE TEMPORARY TABLE capability_test (id INT)")
+        .await?;
+
+    let sql = if conn.supports(Capability::SkipLocked) {
+        "SELECT id FROM capability_test FOR UPDATE SKIP LOCKED"
+    } else {
+        "SELECT id FROM capability_test FOR UPDATE"
+    };
+
+    conn.execute(sql).await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_recovers_from_cancelled_queries() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // dropped while waiting for the result
+    let cancelled = sqlx_rt::timeout(
+        Duration::from_millis(100),
+        sqlx::query("SELECT pg_sleep(0.5), $1::int4")
+            .bind(1_i32)
+            .fetch_one(&mut conn),
+    )
+    .await;
+
+    assert!(cancelled.is_err());
+
+    // dropped after the first of many rows
+    {
+        let mut rows = conn.fetch("SELECT generate_series(1, 100000)");
+        rows.try_next().await?;
+    }
+
+    let value: i32 = sqlx::query_scalar("SELECT $1::int4")
+        .bind(2_i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(value, 2);
+    assert!(!conn.is_broken());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_times_out_queries_past_their_deadline() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let mut deadline = Deadline::after(&mut conn, Duration::from_millis(100));
+
+    let result = sqlx::query("SELECT pg_sleep(0.5)")
+        .execute(&mut deadline)
+        .await;
+
+    assert!(matches!(result, Err(sqlx::Error::QueryTimedOut)));
+
+    // the deadline has passed so the query is not sent
+    sqlx_rt::sleep(Duration::from_millis(100)).await;
+
+    let result = deadline.fetch_one("SELECT 1").await;
+
+    assert!(matches!(result, Err(sqlx::Error::QueryTimedOut)));
+
+    // queries within a deadline are unaffected, and the connection is still usable
+    let mut deadline = Deadline::after(&mut conn, Duration::from_secs(10));
+
+    let value: i32 = sqlx::query_scalar("SELECT $1::int4")
+        .bind(2_i32)
+        .fetch_one(&mut deadline)
+        .await?;
+
+    assert_eq!(value, 2);
+
+    let value: i32 = sqlx::query_scalar("SELECT 3").fetch_one(&mut conn).await?;
+
+    assert_eq!(value, 3);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_replays_captured_queries() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let query = sqlx::query("SELECT $1::int8 + 1, $2::text || '!', $3::int8 IS NULL, length($4)")
+        .bind(41_i64)
+        .bind("hello")
+        .bind(None::<i64>)
+        .bind(vec![1_u8, 2, 3]);
+
+    let snapshot = QuerySnapshot::capture(&query).unwrap();
+
+    // as if copied from a log
+    #[cfg(feature = "json")]
+    let snapshot = QuerySnapshot::from_json(&snapshot.to_json()?)?;
+
+    let executed = query.fetch_one(&mut conn).await?;
+    let replayed = snapshot.query::<Postgres>().fetch_one(&mut conn).await?;
+
+    for row in &[executed, replayed] {
+        assert_eq!(row.try_get::<i64, _>(0)?, 42);
+        assert_eq!(row.try_get::<String, _>(1)?, "hello!");
+        assert!(row.try_get::<bool, _>(2)?);
+        assert_eq!(row.try_get::<i32, _>(3)?, 3);
+    }
+
+    Ok(())
+}
+
+#[cfg(all(feature = "uuid", feature = "chrono"))]
+#[sqlx_macros::test]
+async fn it_replays_captured_queries_with_typed_arguments() -> anyhow::Result<()> {
+    use sqlx::types::chrono::{DateTime, TimeZone, Utc};
+    use sqlx::types::Uuid;
+
+    let mut conn = new::<Postgres>().await?;
+
+    let id = Uuid::parse_str("a0ee1b3c-5d8a-4c2e-9f3b-7d6e2a1c4b5f")?;
+    let at = Utc.ymd(2020, 2, 29).and_hms_micro(13, 45, 10, 250_000);
+
+    // no casts, the parameters are typed by the arguments alone
+    let query = sqlx::query("SELECT $1, $2").bind(id).bind(at);
+
+    let snapshot = QuerySnapshot::capture(&query).unwrap();
+
+    assert_eq!(
+        snapshot.arguments(),
+        &[
+            SnapshotValue::Typed {
+                type_name: "UUID".into(),
+                text: "a0ee1b3c-5d8a-4c2e-9f3b-7d6e2a1c4b5f".into(),
+            },
+            SnapshotValue::Typed {
+                type_name: "TIMESTAMPTZ".into(),
+                text: "2020-02-29 13:45:10.250000+00".into(),
+            },
+        ][..]
+    );
+
+    #[cfg(feature = "json")]
+    let snapshot = QuerySnapshot::from_json(&snapshot.to_json()?)?;
+
+    let row = snapshot.query::<Postgres>().fetch_one(&mut conn).await?;
+
+    assert_eq!(row.try_get::<Uuid, _>(0)?, id);
+    assert_eq!(row.try_get::<DateTime<Utc>, _>(1)?, at);
+
+    // captured again from the replayed query, the arguments are unchanged
+    let replayed = snapshot.query::<Postgres>();
+
+    assert_eq!(QuerySnapshot::capture(&replayed).unwrap(), snapshot);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_explains_queries() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute(
+        r#"
+CREATE TEMPORARY TABLE explain_test (id SERIAL PRIMARY KEY, email TEXT, name TEXT);
+CREATE INDEX explain_test_email_idx ON explain_test (email);
+
+-- the table is too small for the planner to prefer the index otherwise
+SET enable_seqscan = off;
+        "#,
+    )
+    .await?;
+
+    let plan = conn
+        .explain(sqlx::query("SELECT * FROM explain_test WHERE email = $1").bind("ann@example.com"))
+        .await?;
+
+    plan.assert_uses_index("explain_test_email_idx");
+    plan.assert_no_full_scan("explain_test");
+
+    let plan = conn
+        .explain(sqlx::query("SELECT * FROM explain_test WHERE name = $1").bind("Ann"))
+        .await?;
+
+    assert!(plan.scans_table("explain_test"));
+    assert!(!plan.uses_index("explain_test_email_idx"));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_overflows_the_listener_buffer() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+    let mut listener = PgListener::connect(&env::var("DATABASE_URL")?).await?;
+
+    listener.set_buffer_limit(2, PgListenerOverflow::RejectQueries);
+    listener.listen("test_overflow").await?;
+
+    for payload in &["1", "2", "3"] {
+        sqlx::query("SELECT pg_notify('test_overflow', $1)")
+            .bind(payload)
+            .execute(&mut conn)
+            .await?;
+    }
+
+    // the notifications arrive while this query runs, and are all kept
+    listener.execute("SELECT 1").await?;
+
+    let stats = listener.buffer_stats();
+    assert_eq!(stats.buffered(), 3);
+    assert_eq!(stats.dropped(), 0);
+
+    let err = listener.execute("SELECT 1").await.unwrap_err();
+
+    assert!(matches!(
+        err,
+        sqlx::Error::NotificationBufferBlocked { buffered: 3 }
+    ));
+
+    assert_eq!(listener.recv().await?.payload(), "1");
+    assert_eq!(listener.recv().await?.payload(), "2");
+
+    // there is room again
+    listener.execute("SELECT 1").await?;
+
+    assert_eq!(listener.recv().await?.payload(), "3");
+
+    Ok(())
+}
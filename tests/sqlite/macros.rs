@@ -16,6 +16,25 @@ async fn macro_select() -> anyhow::Result<()> {
     Ok(())
 }
 
+// checked against the database at `DATABASE_URL_SECONDARY`, which is the same test database
+#[sqlx_macros::test]
+async fn macro_select_from_named_database() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let account = sqlx::query!(
+        db = "secondary",
+        "select id, name from accounts where id = ?",
+        1_i32
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(1, account.id);
+    assert_eq!("Herp Derpinson", account.name);
+
+    Ok(())
+}
+
 macro_rules! gen_macro_select_concats {
     ($param:literal) => {
         #[sqlx_macros::test]
@@ -1,10 +1,21 @@
 use futures::TryStreamExt;
+use sqlx::arena::{ArenaBuilder, Layout};
+use sqlx::audit::{AuditEvent, AuditLog};
+use sqlx::explain::Explain;
+use sqlx::pool::SingleFlight;
 use sqlx::sqlite::SqlitePoolOptions;
+use sqlx::type_overrides::TypeOverrides;
+use sqlx::Capability;
+use sqlx::Deadline;
 use sqlx::{
     query, sqlite::Sqlite, sqlite::SqliteRow, Column, Connection, Done, Executor, Row,
-    SqliteConnection, SqlitePool, Statement, TypeInfo,
+    SqliteConnection, SqlitePool, Statement, TypeInfo, Value, ValueRef,
 };
+use sqlx::{QuerySnapshot, Redaction, SnapshotValue};
 use sqlx_test::new;
+use std::panic::AssertUnwindSafe;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 #[sqlx_macros::test]
 async fn it_connects() -> anyhow::Result<()> {
@@ -416,6 +427,44 @@ CREATE TEMPORARY TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL COLLATE
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_dumps_connection_state() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let state = conn.dump_state();
+
+    assert_eq!(state.transaction_depth, 0);
+    assert_eq!(state.cached_statements, 0);
+
+    let mut tx = conn.begin().await?;
+
+    let state = tx.dump_state();
+
+    assert_eq!(state.transaction_depth, 1);
+    assert_eq!(state.last_sql.as_deref(), Some("BEGIN"));
+
+    let _: i32 = sqlx::query_scalar("SELECT ? + 1 AS dumped")
+        .bind(1_i32)
+        .fetch_one(&mut tx)
+        .await?;
+
+    let state = tx.dump_state();
+
+    assert_eq!(state.cached_statements, 1);
+    assert_eq!(state.last_sql.as_deref(), Some("SELECT ? + 1 AS dumped"));
+
+    tx.rollback().await?;
+
+    let state = conn.dump_state();
+
+    assert_eq!(state.transaction_depth, 0);
+
+    // SQLite has no server to report parameters
+    assert!(state.server_parameters.is_empty());
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_caches_statements() -> anyhow::Result<()> {
     let mut conn = new::<Sqlite>().await?;
@@ -455,6 +504,36 @@ async fn it_caches_statements() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_closes_prepared_statements_explicitly() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let statement = conn.prepare("SELECT ? AS val").await?;
+    let _ = conn.prepare("SELECT 1").await?;
+
+    assert_eq!(2, conn.cached_statements_size());
+
+    assert!(statement.close(&mut conn).await?);
+    assert!(!statement.close(&mut conn).await?);
+    assert!(conn.close_cached_statement("SELECT 1").await?);
+    assert!(!conn.close_cached_statement("SELECT 2").await?);
+
+    assert_eq!(0, conn.cached_statements_size());
+
+    // the statement is prepared again on its next use
+    let val: i32 = statement
+        .query()
+        .bind(5_i32)
+        .fetch_one(&mut conn)
+        .await?
+        .get(0);
+
+    assert_eq!(5, val);
+    assert_eq!(1, conn.cached_statements_size());
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_prepare_then_execute() -> anyhow::Result<()> {
     let mut conn = new::<Sqlite>().await?;
@@ -485,3 +564,518 @@ async fn it_can_prepare_then_execute() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_passes_conformance_checks() -> anyhow::Result<()> {
+    sqlx::testing::run::<Sqlite>(&dotenv::var("DATABASE_URL")?).await?;
+
+    let mut conn = new::<Sqlite>().await?;
+
+    sqlx::testing::round_trip::<Sqlite, _>(&mut conn, 42_i64).await?;
+    sqlx::testing::round_trip::<Sqlite, _>(&mut conn, String::from("hello")).await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_executes_parameterized_scripts() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let done = sqlx::Script::<Sqlite>::new(
+        "CREATE TEMPORARY TABLE script_test (id INTEGER, text TEXT);
+         INSERT INTO script_test (id, text) VALUES (?, ?);
+         INSERT INTO script_test (id, text) VALUES (?1 + 1, ?2 || ';');",
+    )
+    .bind(1_i64)
+    .bind(String::from("hello"))
+    .execute(&mut conn)
+    .await?;
+
+    assert_eq!(done.rows_affected(), 2);
+
+    let rows = sqlx::Script::<Sqlite>::new(
+        "SELECT text FROM script_test WHERE id = ?; SELECT text FROM script_test WHERE id = ?",
+    )
+    .bind(1_i64)
+    .bind(2_i64)
+    .fetch_all(&mut conn)
+    .await?;
+
+    let texts: Vec<String> = rows.iter().map(|row| row.get(0)).collect();
+
+    assert_eq!(texts, ["hello", "hello;"]);
+
+    // the statements of the trigger are not split apart
+    sqlx::Script::<Sqlite>::new(
+        "CREATE TEMPORARY TABLE script_log (id INTEGER);
+         CREATE TEMPORARY TRIGGER script_test_log AFTER INSERT ON script_test BEGIN
+             INSERT INTO script_log (id) VALUES (new.id);
+             INSERT INTO script_log (id) VALUES (-new.id);
+         END;
+         INSERT INTO script_test (id, text) VALUES (?, 'triggered');",
+    )
+    .bind(3_i64)
+    .execute(&mut conn)
+    .await?;
+
+    let logged: Vec<i64> = sqlx::query_scalar("SELECT id FROM script_log ORDER BY id")
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(logged, [-3, 3]);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_audits_mutations() -> anyhow::Result<()> {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&events);
+
+    let audit = AuditLog::new(move |event: AuditEvent| sink.lock().unwrap().push(event))
+        .redaction(Redaction::new().parameter(1));
+
+    let mut conn = new::<Sqlite>().await?;
+    let mut audited = audit.wrap(&mut conn);
+
+    audited
+        .execute("CREATE TEMPORARY TABLE audit_test (id INTEGER, secret TEXT)")
+        .await?;
+
+    sqlx::query("INSERT INTO audit_test (id, secret) VALUES (?, ?), (?, ?)")
+        .bind(1_i32)
+        .bind("hunter2")
+        .bind(2_i32)
+        .bind("swordfish")
+        .execute(&mut audited)
+        .await?;
+
+    sqlx::query("SELECT * FROM audit_test")
+        .fetch_all(&mut audited)
+        .await?;
+
+    // failed mutations are not recorded
+    assert!(sqlx::query("DELETE FROM audit_test_missing")
+        .execute(&mut audited)
+        .await
+        .is_err());
+
+    // neither are mutations dropped before they complete
+    drop(sqlx::query("DELETE FROM audit_test").execute(&mut audited));
+
+    sqlx::query("UPDATE audit_test SET secret = NULL WHERE id = 1")
+        .fetch_optional(&mut audited)
+        .await?;
+
+    let mut tx = audit.transaction(conn.begin().await?);
+
+    sqlx::query("DELETE FROM audit_test WHERE id = ?")
+        .bind(2_i32)
+        .execute(&mut tx)
+        .await?;
+
+    let transaction_id = tx.transaction_id();
+    tx.into_inner().commit().await?;
+
+    let events = events.lock().unwrap();
+
+    assert_eq!(events.len(), 3);
+
+    assert_eq!(
+        events[0].query.arguments(),
+        &[
+            SnapshotValue::Int(1),
+            SnapshotValue::Redacted,
+            SnapshotValue::Int(2),
+            SnapshotValue::Text("swordfish".into()),
+        ][..]
+    );
+    assert_eq!(events[0].rows_affected, 2);
+    assert_eq!(events[0].transaction_id, None);
+
+    assert_eq!(
+        events[1].query.sql(),
+        "UPDATE audit_test SET secret = NULL WHERE id = 1"
+    );
+    assert_eq!(events[1].rows_affected, 1);
+
+    assert_eq!(events[2].query.sql(), "DELETE FROM audit_test WHERE id = ?");
+    assert_eq!(events[2].rows_affected, 1);
+    assert!(transaction_id.is_some());
+    assert_eq!(events[2].transaction_id, transaction_id);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_decodes_overridden_types() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let row = conn.fetch_one("SELECT '42'").await?;
+    assert!(row.try_get::<i64, _>(0).is_err());
+
+    conn.set_type_overrides(TypeOverrides::new().decode_as::<i64>("text"));
+
+    let overridden = conn.fetch_one("SELECT '42'").await?;
+    assert_eq!(overridden.try_get::<i64, _>(0)?, 42);
+
+    // values taken out of the row keep its overrides
+    let value = overridden.try_get_raw(0)?.to_owned();
+    assert_eq!(value.try_decode::<i64>()?, 42);
+
+    conn.set_type_overrides(TypeOverrides::new());
+
+    // rows returned before the overrides were replaced keep the previous ones
+    assert_eq!(overridden.try_get::<i64, _>(0)?, 42);
+
+    // while the rows returned afterwards use the new ones
+    let row = conn.fetch_one("SELECT '42'").await?;
+    assert!(row.try_get::<i64, _>(0).is_err());
+    assert!(row.try_get_raw(0)?.to_owned().try_decode::<i64>().is_err());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_explains_queries() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.execute(
+        r#"
+CREATE TEMPORARY TABLE explain_test (id INTEGER PRIMARY KEY, email TEXT, name TEXT);
+CREATE INDEX explain_test_email_idx ON explain_test (email);
+        "#,
+    )
+    .await?;
+
+    let plan = conn
+        .explain(query("SELECT * FROM explain_test WHERE email = ?").bind("ann@example.com"))
+        .await?;
+
+    plan.assert_uses_index("explain_test_email_idx");
+    plan.assert_no_full_scan("explain_test");
+
+    let plan = conn
+        .explain(query("SELECT * FROM explain_test WHERE name = ?").bind("Ann"))
+        .await?;
+
+    assert!(plan.scans_table("explain_test"));
+    assert!(!plan.uses_index("explain_test_email_idx"));
+
+    // reads every entry of the covering index
+    let plan = conn
+        .explain(query("SELECT email FROM explain_test"))
+        .await?;
+
+    assert!(plan.scans_table("explain_test"));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_coalesces_identical_queries() -> anyhow::Result<()> {
+    let pool: SqlitePool = SqlitePoolOptions::new()
+        .max_connections(2)
+        .connect(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let flights = SingleFlight::new(pool);
+    let select = |n: i64| query("SELECT ?1 + 1, random()").bind(n);
+
+    let (a, b, c) = futures::join!(
+        flights.fetch_all(select(41)),
+        flights.fetch_all(select(41)),
+        flights.fetch_all(select(1)),
+    );
+
+    let (a, b, c) = (a?, b?, c?);
+
+    assert!(Arc::ptr_eq(&a, &b));
+    assert!(!Arc::ptr_eq(&a, &c));
+
+    assert_eq!(a[0].try_get::<i64, _>(0)?, 42);
+    assert_eq!(c[0].try_get::<i64, _>(0)?, 2);
+
+    // a query that has completed is run again
+    let d = flights.fetch_all(select(41)).await?;
+
+    assert!(!Arc::ptr_eq(&a, &d));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_runs_coalesced_queries_as_given() -> anyhow::Result<()> {
+    let pool: SqlitePool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let flights = SingleFlight::new(pool.clone());
+
+    let rows = flights
+        .fetch_all(query("SELECT ?1 * 2").bind(21_i64).persistent(false))
+        .await?;
+
+    assert_eq!(rows[0].try_get::<i64, _>(0)?, 42);
+    assert_eq!(pool.acquire().await?.cached_statements_size(), 0);
+
+    // a failure is coalesced even when no other caller joined the query
+    let err = flights
+        .fetch_all(query("SELECT * FROM not_a_table WHERE id = ?1").bind(1_i64))
+        .await
+        .err()
+        .unwrap();
+
+    assert!(matches!(err, sqlx::Error::Coalesced(_)));
+    assert!(err.as_database_error().is_some());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_reports_capabilities() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    assert!(conn.supports(Capability::OnConflict));
+    assert!(!conn.supports(Capability::SkipLocked));
+
+    // the capability can be relied on
+    conn.execute(
+        "CREATE TEMPORARY TABLE capability_test (id INTEGER PRIMARY KEY, hits INTEGER NOT NULL)",
+    )
+    .await?;
+
+    for _ in 0..2 {
+        conn.execute(
+            "INSERT INTO capability_test (id, hits) VALUES (1, 1) \
+             ON CONFLICT (id) DO UPDATE SET hits = hits + 1",
+        )
+        .await?;
+    }
+
+    let hits: i64 = sqlx::query_scalar("SELECT hits FROM capability_test WHERE id = 1")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(hits, 2);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_recovers_from_cancelled_queries() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    // dropped while the query is still running on the worker thread of the connection
+    let cancelled = sqlx_rt::timeout(
+        Duration::from_millis(10),
+        conn.fetch_one(
+            "WITH RECURSIVE n(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM n WHERE x < 1000000) \
+             SELECT sum(x) FROM n",
+        ),
+    )
+    .await;
+
+    assert!(cancelled.is_err());
+
+    let value: i64 = sqlx::query_scalar("SELECT ?")
+        .bind(2_i64)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(value, 2);
+    assert!(!conn.is_broken());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_fetches_into_an_arena() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    for &layout in &[Layout::Columnar, Layout::RowMajor] {
+        let arena = ArenaBuilder::<Sqlite>::new()
+            .column::<i64>("id")
+            .column::<String>("name")
+            .column::<Option<f64>>("score")
+            .layout(layout)
+            .fetch(
+                &mut conn,
+                "WITH RECURSIVE n(id) AS (SELECT 1 UNION ALL SELECT id + 1 FROM n WHERE id < 1000) \
+                 SELECT 'user ' || id AS name, \
+                        CASE WHEN id % 2 = 0 THEN NULL ELSE id * 0.5 END AS score, \
+                        id \
+                 FROM n",
+            )
+            .await?;
+
+        assert_eq!(arena.len(), 1000);
+        assert_eq!(arena.column::<i64, _>("id").sum::<i64>(), 500_500);
+
+        let row = arena.row(41).unwrap();
+
+        assert_eq!(row.try_get::<&str, _>("name")?, "user 42");
+        assert_eq!(row.try_get::<Option<f64>, _>("score")?, None);
+        assert!(row.try_get::<f64, _>("score").is_err());
+
+        let scores: Vec<f64> = arena.column::<Option<f64>, _>(2).flatten().collect();
+
+        assert_eq!(scores.len(), 500);
+        assert_eq!(scores[0], 0.5);
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_discards_connections_in_use_during_a_panic() -> anyhow::Result<()> {
+    let pool: SqlitePool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let conn = pool.acquire().await?;
+
+    assert_eq!(pool.size(), 1);
+
+    // dropped while unwinding
+    let result: std::thread::Result<()> = std::panic::catch_unwind(AssertUnwindSafe(move || {
+        let _conn = conn;
+        panic!("in the middle of a query");
+    }));
+
+    assert!(result.is_err());
+    assert_eq!(pool.size(), 0);
+
+    // dropped after the panic was caught
+    let mut conn = pool.acquire().await?;
+    let result: std::thread::Result<()> = conn.catch_unwind(|_| panic!("in the middle of a query"));
+
+    assert!(result.is_err());
+    assert!(conn.is_poisoned());
+
+    drop(conn);
+
+    assert_eq!(pool.size(), 0);
+
+    // the pool replaces the discarded connections
+    let mut conn = pool.acquire().await?;
+    let value: i64 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+
+    assert_eq!(value, 1);
+    assert!(!conn.is_poisoned());
+
+    drop(conn);
+
+    assert_eq!(pool.size(), 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_times_out_queries_past_their_deadline() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let mut deadline = Deadline::after(&mut conn, Duration::from_millis(10));
+
+    let result = deadline
+        .fetch_one(
+            "WITH RECURSIVE n(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM n WHERE x < 1000000) \
+             SELECT sum(x) FROM n",
+        )
+        .await;
+
+    assert!(matches!(result, Err(sqlx::Error::QueryTimedOut)));
+
+    // queries within a deadline are unaffected, and the connection is still usable
+    let mut deadline = Deadline::after(&mut conn, Duration::from_secs(30));
+
+    let value: i64 = sqlx::query_scalar("SELECT ?")
+        .bind(2_i64)
+        .fetch_one(&mut deadline)
+        .await?;
+
+    assert_eq!(value, 2);
+
+    let value: i64 = sqlx::query_scalar("SELECT 3").fetch_one(&mut conn).await?;
+
+    assert_eq!(value, 3);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_replays_captured_queries() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let query = sqlx::query("SELECT ?1 + 1, ?2 || '!', ?3 IS NULL, length(?4)")
+        .bind(41_i64)
+        .bind("hello")
+        .bind(None::<i64>)
+        .bind(vec![1_u8, 2, 3]);
+
+    let snapshot = QuerySnapshot::capture(&query).unwrap();
+
+    assert_eq!(
+        snapshot.arguments(),
+        &[
+            SnapshotValue::Int(41),
+            SnapshotValue::Text("hello".into()),
+            SnapshotValue::Null,
+            SnapshotValue::Bytes(vec![1, 2, 3]),
+        ][..]
+    );
+
+    // as if copied from a log
+    #[cfg(feature = "json")]
+    let snapshot = QuerySnapshot::from_json(&snapshot.to_json()?)?;
+
+    let executed = query.fetch_one(&mut conn).await?;
+    let replayed = snapshot.query::<Sqlite>().fetch_one(&mut conn).await?;
+
+    for row in &[executed, replayed] {
+        assert_eq!(row.try_get::<i64, _>(0)?, 42);
+        assert_eq!(row.try_get::<String, _>(1)?, "hello!");
+        assert!(row.try_get::<bool, _>(2)?);
+        assert_eq!(row.try_get::<i64, _>(3)?, 3);
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_reset_the_session() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.execute("BEGIN").await?;
+    conn.execute("CREATE TEMPORARY TABLE reset_session (id INTEGER)")
+        .await?;
+    conn.execute("CREATE TEMPORARY VIEW reset_session_view AS SELECT id FROM reset_session")
+        .await?;
+
+    let _: i64 = sqlx::query_scalar("SELECT ?")
+        .bind(1_i64)
+        .fetch_one(&mut conn)
+        .await?;
+
+    conn.reset().await?;
+
+    assert_eq!(conn.cached_statements_size(), 0);
+
+    // the transaction was rolled back, so a new one can begin
+    conn.execute("BEGIN").await?;
+    conn.execute("ROLLBACK").await?;
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM temp.sqlite_master")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 0);
+
+    // the temporary table is gone
+    conn.execute("CREATE TEMPORARY TABLE reset_session (id INTEGER)")
+        .await?;
+
+    Ok(())
+}
@@ -49,6 +49,9 @@ def run(command, comment=None, env=None, service=None, tag=None, args=None, data
 
         environ["DATABASE_URL"] = database_url
 
+        # the database named with `query!(db = "secondary", ...)` in the macro tests
+        environ["DATABASE_URL_SECONDARY"] = database_url
+
         # show the database url
         print(f"\x1b[94m @ {database_url}\x1b[0m")
 